@@ -0,0 +1,9 @@
+pub mod abstract_boolean;
+pub mod abstract_domain;
+pub mod abstract_environment;
+pub mod abstract_evaluator;
+pub mod abstract_value;
+pub mod analysis;
+pub mod interval;
+pub mod program_point;
+pub mod sign;
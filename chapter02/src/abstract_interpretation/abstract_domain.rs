@@ -0,0 +1,40 @@
+use super::abstract_boolean::AbstractBoolean;
+use std::fmt::{Debug, Display};
+
+pub trait AbstractDomain: Debug + Clone + PartialEq + Display {
+    fn top() -> Self;
+    fn from_number(value: i64) -> Self;
+    fn join(&self, other: &Self) -> Self;
+
+    fn meet(&self, other: &Self) -> Self;
+
+    fn widen(&self, other: &Self) -> Self {
+        self.join(other)
+    }
+
+    fn narrow(&self, other: &Self) -> Self {
+        self.meet(other)
+    }
+
+    fn add(&self, other: &Self) -> Self;
+    fn subtract(&self, other: &Self) -> Self;
+    fn multiply(&self, other: &Self) -> Self;
+    fn divide(&self, other: &Self) -> Self;
+    fn negate(&self) -> Self;
+    fn equal(&self, other: &Self) -> AbstractBoolean;
+    fn less_than(&self, other: &Self) -> AbstractBoolean;
+    fn may_be_zero(&self) -> bool;
+    fn may_overflow_add(&self, other: &Self) -> bool;
+    fn may_overflow_subtract(&self, other: &Self) -> bool;
+    fn may_overflow_multiply(&self, other: &Self) -> bool;
+    fn may_overflow_divide(&self, other: &Self) -> bool;
+    fn may_overflow_negate(&self) -> bool;
+
+    fn assume_less_than(&self, _other: &Self, _inclusive: bool) -> Self {
+        self.clone()
+    }
+
+    fn assume_greater_than(&self, _other: &Self, _inclusive: bool) -> Self {
+        self.clone()
+    }
+}
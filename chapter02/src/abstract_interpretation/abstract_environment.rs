@@ -0,0 +1,102 @@
+use super::{abstract_domain::AbstractDomain, abstract_value::AbstractValue};
+use crate::environment::Environment;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AbstractEnvironment<D> {
+    variables: HashMap<String, AbstractValue<D>>,
+}
+
+impl<D> Default for AbstractEnvironment<D> {
+    fn default() -> Self {
+        Self {
+            variables: HashMap::new(),
+        }
+    }
+}
+
+impl<D: AbstractDomain> AbstractEnvironment<D> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, key: &str, value: AbstractValue<D>) {
+        self.variables.insert(key.into(), value);
+    }
+
+    pub fn get(&self, key: &str) -> AbstractValue<D> {
+        match self.variables.get(key) {
+            Some(value) => value.clone(),
+            None => AbstractValue::Unknown,
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<AbstractValue<D>> {
+        self.variables.remove(key)
+    }
+
+    pub fn variables(&self) -> impl Iterator<Item = (&str, &AbstractValue<D>)> {
+        self.variables
+            .iter()
+            .map(|(key, value)| (key.as_str(), value))
+    }
+
+    pub fn join(&self, other: &Self) -> Self {
+        self.combine(other, AbstractValue::join)
+    }
+
+    pub fn widen(&self, other: &Self) -> Self {
+        self.combine(other, AbstractValue::widen)
+    }
+
+    pub fn narrow(&self, other: &Self) -> Self {
+        self.combine(other, AbstractValue::narrow)
+    }
+
+    fn combine(
+        &self,
+        other: &Self,
+        combine: impl Fn(&AbstractValue<D>, &AbstractValue<D>) -> AbstractValue<D>,
+    ) -> Self {
+        let mut variables = self.variables.clone();
+
+        for (key, value) in &other.variables {
+            let value = match self.variables.get(key) {
+                Some(current) => combine(current, value),
+                None => value.clone(),
+            };
+            variables.insert(key.clone(), value);
+        }
+
+        Self { variables }
+    }
+}
+
+impl<D: AbstractDomain> From<&Environment> for AbstractEnvironment<D> {
+    fn from(environment: &Environment) -> Self {
+        let mut abstract_environment = Self::new();
+
+        for (key, value) in environment.variables() {
+            abstract_environment.insert(key, AbstractValue::from_expression(value));
+        }
+
+        abstract_environment
+    }
+}
+
+impl<D: Display> Display for AbstractEnvironment<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{{ {} }}",
+            self.variables
+                .iter()
+                .map(|(k, v)| format!("{} = {}", k, v))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
@@ -0,0 +1,419 @@
+use super::{
+    abstract_boolean::AbstractBoolean, abstract_domain::AbstractDomain,
+    abstract_environment::AbstractEnvironment, abstract_value::AbstractValue, analysis::Analysis,
+};
+use crate::{expression::Expression, statement::Statement};
+
+const NARROWING_ITERATIONS: usize = 3;
+
+pub trait AbstractEvaluator<D: AbstractDomain> {
+    type Output;
+
+    fn abstract_evaluate(
+        &self,
+        environment: &mut AbstractEnvironment<D>,
+        analysis: &mut Analysis<D>,
+    ) -> Self::Output;
+}
+
+impl<D: AbstractDomain> AbstractEvaluator<D> for Expression {
+    type Output = AbstractValue<D>;
+
+    fn abstract_evaluate(
+        &self,
+        environment: &mut AbstractEnvironment<D>,
+        analysis: &mut Analysis<D>,
+    ) -> AbstractValue<D> {
+        match self {
+            expression @ Self::Number(_) => AbstractValue::from_expression(expression),
+            expression @ Self::Boolean(_) => AbstractValue::from_expression(expression),
+            Self::String(_) | Self::Input => AbstractValue::Unknown,
+            Self::List(elements) => {
+                for element in elements {
+                    element.abstract_evaluate(environment, analysis);
+                }
+
+                AbstractValue::Unknown
+            }
+            Self::Variable(value) => environment.get(value),
+            Self::Add(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+
+                analysis.record_overflow(self, left.number().may_overflow_add(&right.number()));
+                AbstractValue::Number(left.number().add(&right.number()))
+            }
+            Self::Subtract(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+
+                analysis
+                    .record_overflow(self, left.number().may_overflow_subtract(&right.number()));
+                AbstractValue::Number(left.number().subtract(&right.number()))
+            }
+            Self::Multiply(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+
+                analysis
+                    .record_overflow(self, left.number().may_overflow_multiply(&right.number()));
+                AbstractValue::Number(left.number().multiply(&right.number()))
+            }
+            Self::Divide(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+
+                analysis.record_division(self, right.number().may_be_zero());
+                analysis.record_overflow(self, left.number().may_overflow_divide(&right.number()));
+                AbstractValue::Number(left.number().divide(&right.number()))
+            }
+            Self::Negate(expression) => {
+                let expression = expression.abstract_evaluate(environment, analysis);
+
+                analysis.record_overflow(self, expression.number().may_overflow_negate());
+                AbstractValue::Number(expression.number().negate())
+            }
+            Self::Not(expression) => {
+                let expression = expression.abstract_evaluate(environment, analysis);
+                AbstractValue::Boolean(!expression.boolean())
+            }
+            Self::And(first, second) => {
+                let left = first.abstract_evaluate(environment, analysis);
+
+                if left.boolean() == AbstractBoolean::False {
+                    return left;
+                }
+
+                let mut refined = environment.clone();
+                assume(first, true, &mut refined, analysis);
+                let right = second.abstract_evaluate(&mut refined, analysis);
+                AbstractValue::Boolean(left.boolean().and(right.boolean()))
+            }
+            Self::Or(first, second) => {
+                let left = first.abstract_evaluate(environment, analysis);
+
+                if left.boolean() == AbstractBoolean::True {
+                    return left;
+                }
+
+                let mut refined = environment.clone();
+                assume(first, false, &mut refined, analysis);
+                let right = second.abstract_evaluate(&mut refined, analysis);
+                AbstractValue::Boolean(left.boolean().or(right.boolean()))
+            }
+            Self::Equal(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+                AbstractValue::Boolean(left.equal(&right))
+            }
+            Self::NotEqual(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+                AbstractValue::Boolean(!left.equal(&right))
+            }
+            Self::LessThan(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+                AbstractValue::Boolean(left.number().less_than(&right.number()))
+            }
+            Self::LessThanOrEqual(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+                AbstractValue::Boolean(!right.number().less_than(&left.number()))
+            }
+            Self::GreaterThan(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+                AbstractValue::Boolean(right.number().less_than(&left.number()))
+            }
+            Self::GreaterThanOrEqual(left, right) => {
+                let left = left.abstract_evaluate(environment, analysis);
+                let right = right.abstract_evaluate(environment, analysis);
+                AbstractValue::Boolean(!left.number().less_than(&right.number()))
+            }
+            Self::Concatenate(left, right)
+            | Self::Index(left, right)
+            | Self::Append(left, right) => {
+                left.abstract_evaluate(environment, analysis);
+                right.abstract_evaluate(environment, analysis);
+                AbstractValue::Unknown
+            }
+            Self::Length(expression) => {
+                expression.abstract_evaluate(environment, analysis);
+                AbstractValue::Number(D::top())
+            }
+            Self::Call(_, arguments) => {
+                for argument in arguments {
+                    argument.abstract_evaluate(environment, analysis);
+                }
+
+                AbstractValue::Unknown
+            }
+            Self::Invocation(_, _, _) => AbstractValue::Unknown,
+        }
+    }
+}
+
+impl<D: AbstractDomain> AbstractEvaluator<D> for Statement {
+    type Output = ();
+
+    fn abstract_evaluate(
+        &self,
+        environment: &mut AbstractEnvironment<D>,
+        analysis: &mut Analysis<D>,
+    ) {
+        let index = analysis.visit();
+        let before = (analysis.is_recording() && !matches!(self, Self::Sequence(_, _)))
+            .then(|| environment.clone());
+
+        match self {
+            Self::Expression(expression) | Self::Print(expression) => {
+                expression.abstract_evaluate(environment, analysis);
+            }
+            Self::Assign(name, expression) => {
+                let value = expression.abstract_evaluate(environment, analysis);
+                environment.insert(name, value);
+            }
+            Self::If(condition, consequence, alternative) => {
+                match condition.abstract_evaluate(environment, analysis).boolean() {
+                    AbstractBoolean::True => {
+                        consequence.abstract_evaluate(environment, analysis);
+                        analysis.skip(alternative);
+                    }
+                    AbstractBoolean::False => {
+                        analysis.skip(consequence);
+                        alternative.abstract_evaluate(environment, analysis);
+                    }
+                    AbstractBoolean::Unknown => {
+                        let mut other = environment.clone();
+                        assume(condition, true, environment, analysis);
+                        assume(condition, false, &mut other, analysis);
+                        consequence.abstract_evaluate(environment, analysis);
+                        alternative.abstract_evaluate(&mut other, analysis);
+                        *environment = environment.join(&other);
+                    }
+                }
+            }
+            Self::Sequence(first, second) => {
+                let (mut first, mut second) = (first, second);
+
+                loop {
+                    first.abstract_evaluate(environment, analysis);
+
+                    match &**second {
+                        Self::Sequence(next, rest) => {
+                            analysis.visit();
+                            (first, second) = (next, rest);
+                        }
+                        last => break last.abstract_evaluate(environment, analysis),
+                    }
+                }
+            }
+            Self::While(condition, body) => {
+                let entry = environment.clone();
+                let start = analysis.cursor();
+                let breaks = fixpoint(environment, analysis, |invariant, analysis| {
+                    analysis.seek(start);
+                    let mut iteration = invariant.clone();
+                    condition.abstract_evaluate(&mut iteration, analysis);
+                    assume(condition, true, &mut iteration, analysis);
+                    let breaks = repeat(body, &mut iteration, analysis);
+                    (entry.join(&iteration), breaks)
+                });
+
+                assume(condition, false, environment, analysis);
+                leave(environment, &breaks);
+            }
+            Self::For(name, start, end, body) => {
+                let value = start.abstract_evaluate(environment, analysis);
+                environment.insert(name, value);
+
+                let counter = Expression::Variable(name.clone());
+                let entry = environment.clone();
+                let start = analysis.cursor();
+                let breaks = fixpoint(environment, analysis, |invariant, analysis| {
+                    analysis.seek(start);
+                    let mut iteration = invariant.clone();
+                    end.abstract_evaluate(&mut iteration, analysis);
+                    compare(&counter, end, true, &mut iteration, analysis);
+                    let breaks = repeat(body, &mut iteration, analysis);
+                    let next = iteration.get(name).number().add(&D::from_number(1));
+                    iteration.insert(name, AbstractValue::Number(next));
+                    (entry.join(&iteration), breaks)
+                });
+
+                end.abstract_evaluate(environment, analysis);
+                compare(end, &counter, false, environment, analysis);
+                leave(environment, &breaks);
+            }
+            Self::Iteration(body, next) => {
+                let breaks = repeat(body, environment, analysis);
+                next.abstract_evaluate(environment, analysis);
+                leave(environment, &breaks);
+            }
+            Self::Break | Self::Continue => analysis.record_jump(self, environment),
+            Self::Let(name, expression, body) => {
+                let value = expression.abstract_evaluate(environment, analysis);
+                let shadowed = environment.remove(name);
+                environment.insert(name, value);
+
+                let jumps = analysis.jumps();
+                body.abstract_evaluate(environment, analysis);
+
+                let restore = |environment: &mut AbstractEnvironment<D>| match &shadowed {
+                    Some(shadowed) => environment.insert(name, shadowed.clone()),
+                    None => {
+                        environment.remove(name);
+                    }
+                };
+
+                analysis.jumps_since(jumps).for_each(restore);
+                restore(environment);
+            }
+            Self::Return(expression) => {
+                expression.abstract_evaluate(environment, analysis);
+            }
+            Self::Function(_, _, body) => analysis.skip(body),
+            Self::Nothing => {}
+        }
+
+        if let Some(before) = before {
+            analysis.record_point(index, before, environment.clone());
+        }
+    }
+}
+
+fn fixpoint<D: AbstractDomain>(
+    environment: &mut AbstractEnvironment<D>,
+    analysis: &mut Analysis<D>,
+    iterate: impl Fn(
+        &AbstractEnvironment<D>,
+        &mut Analysis<D>,
+    ) -> (AbstractEnvironment<D>, Vec<AbstractEnvironment<D>>),
+) -> Vec<AbstractEnvironment<D>> {
+    loop {
+        let next = environment.widen(&iterate(environment, analysis).0);
+
+        if next == *environment {
+            break;
+        }
+
+        *environment = next;
+    }
+
+    for _ in 0..NARROWING_ITERATIONS {
+        let next = environment.narrow(&iterate(environment, analysis).0);
+
+        if next == *environment {
+            break;
+        }
+
+        *environment = next;
+    }
+
+    iterate(environment, analysis).1
+}
+
+fn repeat<D: AbstractDomain>(
+    body: &Statement,
+    environment: &mut AbstractEnvironment<D>,
+    analysis: &mut Analysis<D>,
+) -> Vec<AbstractEnvironment<D>> {
+    analysis.enter_loop();
+    body.abstract_evaluate(environment, analysis);
+    let (breaks, continues) = analysis.leave_loop();
+
+    for continued in &continues {
+        *environment = environment.join(continued);
+    }
+
+    breaks
+}
+
+fn leave<D: AbstractDomain>(
+    environment: &mut AbstractEnvironment<D>,
+    breaks: &[AbstractEnvironment<D>],
+) {
+    for broken in breaks {
+        *environment = environment.join(broken);
+    }
+}
+
+fn assume<D: AbstractDomain>(
+    condition: &Expression,
+    outcome: bool,
+    environment: &mut AbstractEnvironment<D>,
+    analysis: &mut Analysis<D>,
+) {
+    match (condition, outcome) {
+        (Expression::Not(expression), outcome) => {
+            assume(expression, !outcome, environment, analysis)
+        }
+        (Expression::And(left, right), true) | (Expression::Or(left, right), false) => {
+            assume(left, outcome, environment, analysis);
+            assume(right, outcome, environment, analysis);
+        }
+        (Expression::And(left, right), false) | (Expression::Or(left, right), true) => {
+            let mut other = environment.clone();
+            assume(left, outcome, environment, analysis);
+            assume(left, !outcome, &mut other, analysis);
+            assume(right, outcome, &mut other, analysis);
+            *environment = environment.join(&other);
+        }
+        (Expression::Equal(left, right), true) | (Expression::NotEqual(left, right), false) => {
+            refine(left, right, environment, analysis, |value, other| {
+                value.meet(other)
+            });
+            refine(right, left, environment, analysis, |value, other| {
+                value.meet(other)
+            });
+        }
+        (Expression::LessThan(left, right), true)
+        | (Expression::GreaterThanOrEqual(left, right), false) => {
+            compare(left, right, false, environment, analysis)
+        }
+        (Expression::LessThanOrEqual(left, right), true)
+        | (Expression::GreaterThan(left, right), false) => {
+            compare(left, right, true, environment, analysis)
+        }
+        (Expression::GreaterThan(left, right), true)
+        | (Expression::LessThanOrEqual(left, right), false) => {
+            compare(right, left, false, environment, analysis)
+        }
+        (Expression::GreaterThanOrEqual(left, right), true)
+        | (Expression::LessThan(left, right), false) => {
+            compare(right, left, true, environment, analysis)
+        }
+        _ => {}
+    }
+}
+
+fn compare<D: AbstractDomain>(
+    smaller: &Expression,
+    larger: &Expression,
+    inclusive: bool,
+    environment: &mut AbstractEnvironment<D>,
+    analysis: &mut Analysis<D>,
+) {
+    refine(smaller, larger, environment, analysis, |value, other| {
+        value.assume_less_than(other, inclusive)
+    });
+    refine(larger, smaller, environment, analysis, |value, other| {
+        value.assume_greater_than(other, inclusive)
+    });
+}
+
+fn refine<D: AbstractDomain>(
+    target: &Expression,
+    bound: &Expression,
+    environment: &mut AbstractEnvironment<D>,
+    analysis: &mut Analysis<D>,
+    restrict: impl Fn(&D, &D) -> D,
+) {
+    if let Expression::Variable(name) = target
+        && let AbstractValue::Number(value) = environment.get(name)
+    {
+        let bound = bound.abstract_evaluate(environment, analysis).number();
+        environment.insert(name, AbstractValue::Number(restrict(&value, &bound)));
+    }
+}
@@ -0,0 +1,77 @@
+use super::{abstract_boolean::AbstractBoolean, abstract_domain::AbstractDomain};
+use crate::expression::Expression;
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AbstractValue<D> {
+    Number(D),
+    Boolean(AbstractBoolean),
+    Unknown,
+}
+
+impl<D: AbstractDomain> AbstractValue<D> {
+    pub fn from_expression(expression: &Expression) -> Self {
+        match expression {
+            Expression::Number(value) => Self::Number(D::from_number(*value)),
+            Expression::Boolean(value) => Self::Boolean((*value).into()),
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn number(&self) -> D {
+        match self {
+            Self::Number(value) => value.clone(),
+            _ => D::top(),
+        }
+    }
+
+    pub fn boolean(&self) -> AbstractBoolean {
+        match self {
+            Self::Boolean(value) => *value,
+            _ => AbstractBoolean::Unknown,
+        }
+    }
+
+    pub fn join(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Number(left), Self::Number(right)) => Self::Number(left.join(right)),
+            (Self::Boolean(left), Self::Boolean(right)) => Self::Boolean(left.join(*right)),
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn widen(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Number(left), Self::Number(right)) => Self::Number(left.widen(right)),
+            _ => self.join(other),
+        }
+    }
+
+    pub fn narrow(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Number(left), Self::Number(right)) => Self::Number(left.narrow(right)),
+            _ => other.clone(),
+        }
+    }
+
+    pub fn equal(&self, other: &Self) -> AbstractBoolean {
+        match (self, other) {
+            (Self::Number(left), Self::Number(right)) => left.equal(right),
+            (Self::Boolean(left), Self::Boolean(right)) => left.equal(*right),
+            (Self::Number(_), Self::Boolean(_)) | (Self::Boolean(_), Self::Number(_)) => {
+                AbstractBoolean::False
+            }
+            _ => AbstractBoolean::Unknown,
+        }
+    }
+}
+
+impl<D: Display> Display for AbstractValue<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Number(value) => write!(f, "{}", value),
+            Self::Boolean(value) => write!(f, "{}", value),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use super::{
+    abstract_domain::AbstractDomain, abstract_environment::AbstractEnvironment,
+    abstract_evaluator::AbstractEvaluator, program_point::ProgramPoint,
+};
+use crate::{expression::Expression, statement::Statement};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct Analysis<D> {
+    environment: AbstractEnvironment<D>,
+    points: Vec<ProgramPoint<D>>,
+    indices: HashMap<usize, usize>,
+    recording: bool,
+    cursor: usize,
+    divisions: Vec<(Expression, bool)>,
+    division_sites: HashMap<*const Expression, usize>,
+    overflows: Vec<(Expression, bool)>,
+    overflow_sites: HashMap<*const Expression, usize>,
+    breaks: Vec<Vec<AbstractEnvironment<D>>>,
+    continues: Vec<Vec<AbstractEnvironment<D>>>,
+}
+
+impl<D: AbstractDomain> Analysis<D> {
+    pub fn analyze(statement: &Statement, environment: AbstractEnvironment<D>) -> Self {
+        Self::run(statement, environment, true)
+    }
+
+    pub fn summarize(statement: &Statement, environment: AbstractEnvironment<D>) -> Self {
+        Self::run(statement, environment, false)
+    }
+
+    fn run(
+        statement: &Statement,
+        mut environment: AbstractEnvironment<D>,
+        recording: bool,
+    ) -> Self {
+        let mut analysis = Self {
+            environment: AbstractEnvironment::new(),
+            points: Vec::new(),
+            indices: HashMap::new(),
+            recording,
+            cursor: 0,
+            divisions: Vec::new(),
+            division_sites: HashMap::new(),
+            overflows: Vec::new(),
+            overflow_sites: HashMap::new(),
+            breaks: Vec::new(),
+            continues: Vec::new(),
+        };
+
+        statement.abstract_evaluate(&mut environment, &mut analysis);
+        analysis.environment = environment;
+
+        analysis
+    }
+
+    pub fn environment(&self) -> &AbstractEnvironment<D> {
+        &self.environment
+    }
+
+    pub fn points(&self) -> &[ProgramPoint<D>] {
+        &self.points
+    }
+
+    pub fn point(&self, index: usize) -> Option<&ProgramPoint<D>> {
+        self.indices
+            .get(&index)
+            .map(|position| &self.points[*position])
+    }
+
+    pub fn division_hazards(&self) -> impl Iterator<Item = &Expression> {
+        hazards(&self.divisions)
+    }
+
+    pub fn overflow_hazards(&self) -> impl Iterator<Item = &Expression> {
+        hazards(&self.overflows)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    pub fn visit(&mut self) -> usize {
+        self.cursor += 1;
+        self.cursor - 1
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn seek(&mut self, cursor: usize) {
+        self.cursor = cursor;
+    }
+
+    pub fn skip(&mut self, statement: &Statement) {
+        let mut statements = vec![statement];
+
+        while let Some(statement) = statements.pop() {
+            self.cursor += 1;
+
+            match statement {
+                Statement::If(_, consequence, alternative) => {
+                    statements.push(consequence);
+                    statements.push(alternative);
+                }
+                Statement::Sequence(first, second) => {
+                    statements.push(first);
+                    statements.push(second);
+                }
+                Statement::Iteration(body, next) => {
+                    statements.push(body);
+                    statements.push(next);
+                }
+                Statement::While(_, body)
+                | Statement::For(_, _, _, body)
+                | Statement::Let(_, _, body)
+                | Statement::Function(_, _, body) => statements.push(body),
+                Statement::Expression(_)
+                | Statement::Assign(_, _)
+                | Statement::Break
+                | Statement::Continue
+                | Statement::Return(_)
+                | Statement::Print(_)
+                | Statement::Nothing => {}
+            }
+        }
+    }
+
+    pub fn record_point(
+        &mut self,
+        index: usize,
+        before: AbstractEnvironment<D>,
+        after: AbstractEnvironment<D>,
+    ) {
+        let point = ProgramPoint::new(index, before, after);
+
+        match self.indices.get(&index) {
+            Some(position) => self.points[*position] = point,
+            None => {
+                self.indices.insert(index, self.points.len());
+                self.points.push(point);
+            }
+        }
+    }
+
+    pub fn record_jump(&mut self, statement: &Statement, environment: &AbstractEnvironment<D>) {
+        let targets = match statement {
+            Statement::Break => self.breaks.last_mut(),
+            Statement::Continue => self.continues.last_mut(),
+            _ => None,
+        };
+
+        if let Some(targets) = targets {
+            targets.push(environment.clone());
+        }
+    }
+
+    pub fn enter_loop(&mut self) {
+        self.breaks.push(Vec::new());
+        self.continues.push(Vec::new());
+    }
+
+    pub fn leave_loop(&mut self) -> (Vec<AbstractEnvironment<D>>, Vec<AbstractEnvironment<D>>) {
+        (
+            self.breaks.pop().unwrap_or_default(),
+            self.continues.pop().unwrap_or_default(),
+        )
+    }
+
+    pub fn jumps(&self) -> (usize, usize) {
+        (
+            self.breaks.last().map_or(0, Vec::len),
+            self.continues.last().map_or(0, Vec::len),
+        )
+    }
+
+    pub fn jumps_since(
+        &mut self,
+        (breaks, continues): (usize, usize),
+    ) -> impl Iterator<Item = &mut AbstractEnvironment<D>> {
+        let breaks = self
+            .breaks
+            .last_mut()
+            .into_iter()
+            .flat_map(move |jumps| &mut jumps[breaks..]);
+        let continues = self
+            .continues
+            .last_mut()
+            .into_iter()
+            .flat_map(move |jumps| &mut jumps[continues..]);
+
+        breaks.chain(continues)
+    }
+
+    pub fn record_division(&mut self, expression: &Expression, hazardous: bool) {
+        record(
+            &mut self.divisions,
+            &mut self.division_sites,
+            expression,
+            hazardous,
+        );
+    }
+
+    pub fn record_overflow(&mut self, expression: &Expression, hazardous: bool) {
+        record(
+            &mut self.overflows,
+            &mut self.overflow_sites,
+            expression,
+            hazardous,
+        );
+    }
+}
+
+fn hazards(sites: &[(Expression, bool)]) -> impl Iterator<Item = &Expression> {
+    sites
+        .iter()
+        .filter(|(_, hazardous)| *hazardous)
+        .map(|(expression, _)| expression)
+}
+
+fn record(
+    sites: &mut Vec<(Expression, bool)>,
+    indices: &mut HashMap<*const Expression, usize>,
+    expression: &Expression,
+    hazardous: bool,
+) {
+    match indices.get(&(expression as *const Expression)) {
+        Some(index) => sites[*index].1 = hazardous,
+        None => {
+            indices.insert(expression as *const Expression, sites.len());
+            sites.push((expression.clone(), hazardous));
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use super::{abstract_boolean::AbstractBoolean, abstract_domain::AbstractDomain};
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interval {
+    Empty,
+    Range { low: i64, high: i64 },
+}
+
+impl Interval {
+    pub fn new(low: i64, high: i64) -> Self {
+        if high < low {
+            Self::Empty
+        } else {
+            Self::Range { low, high }
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        match self {
+            Self::Empty => false,
+            Self::Range { low, high } => *low <= value && value <= *high,
+        }
+    }
+
+    fn clamp(low: i128, high: i128) -> Self {
+        Self::new(
+            low.clamp(i64::MIN.into(), i64::MAX.into()) as i64,
+            high.clamp(i64::MIN.into(), i64::MAX.into()) as i64,
+        )
+    }
+
+    fn exceeds(bounds: Option<(i128, i128)>) -> bool {
+        bounds.is_some_and(|(low, high)| low < i64::MIN.into() || high > i64::MAX.into())
+    }
+
+    fn corners(&self, other: &Self, operate: fn(i128, i128) -> i128) -> Option<(i128, i128)> {
+        match (self, other) {
+            (Self::Empty, _) | (_, Self::Empty) => None,
+            (
+                Self::Range { low, high },
+                Self::Range {
+                    low: other_low,
+                    high: other_high,
+                },
+            ) => {
+                let corners = [
+                    operate(*low as i128, *other_low as i128),
+                    operate(*low as i128, *other_high as i128),
+                    operate(*high as i128, *other_low as i128),
+                    operate(*high as i128, *other_high as i128),
+                ];
+                Some((
+                    *corners.iter().min().unwrap(),
+                    *corners.iter().max().unwrap(),
+                ))
+            }
+        }
+    }
+
+    fn quotients(&self, other: &Self) -> Option<(i128, i128)> {
+        let negative = other.meet(&Self::new(i64::MIN, -1));
+        let positive = other.meet(&Self::new(1, i64::MAX));
+
+        [negative, positive]
+            .iter()
+            .filter_map(|divisor| self.corners(divisor, |left, right| left / right))
+            .reduce(|(low, high), (other_low, other_high)| {
+                (low.min(other_low), high.max(other_high))
+            })
+    }
+
+    fn from_bounds(bounds: Option<(i128, i128)>) -> Self {
+        match bounds {
+            Some((low, high)) => Self::clamp(low, high),
+            None => Self::Empty,
+        }
+    }
+}
+
+impl AbstractDomain for Interval {
+    fn top() -> Self {
+        Self::new(i64::MIN, i64::MAX)
+    }
+
+    fn from_number(value: i64) -> Self {
+        Self::new(value, value)
+    }
+
+    fn join(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Empty, interval) | (interval, Self::Empty) => *interval,
+            (
+                Self::Range { low, high },
+                Self::Range {
+                    low: other_low,
+                    high: other_high,
+                },
+            ) => Self::new(*low.min(other_low), *high.max(other_high)),
+        }
+    }
+
+    fn meet(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Empty, _) | (_, Self::Empty) => Self::Empty,
+            (
+                Self::Range { low, high },
+                Self::Range {
+                    low: other_low,
+                    high: other_high,
+                },
+            ) => Self::new(*low.max(other_low), *high.min(other_high)),
+        }
+    }
+
+    fn widen(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Empty, interval) | (interval, Self::Empty) => *interval,
+            (
+                Self::Range { low, high },
+                Self::Range {
+                    low: other_low,
+                    high: other_high,
+                },
+            ) => Self::new(
+                if other_low < low { i64::MIN } else { *low },
+                if other_high > high { i64::MAX } else { *high },
+            ),
+        }
+    }
+
+    fn narrow(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Empty, _) | (_, Self::Empty) => Self::Empty,
+            (
+                Self::Range { low, high },
+                Self::Range {
+                    low: other_low,
+                    high: other_high,
+                },
+            ) => Self::new(
+                if *low == i64::MIN { *other_low } else { *low },
+                if *high == i64::MAX {
+                    *other_high
+                } else {
+                    *high
+                },
+            ),
+        }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self::from_bounds(self.corners(other, |left, right| left + right))
+    }
+
+    fn subtract(&self, other: &Self) -> Self {
+        Self::from_bounds(self.corners(other, |left, right| left - right))
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        Self::from_bounds(self.corners(other, |left, right| left * right))
+    }
+
+    fn divide(&self, other: &Self) -> Self {
+        Self::from_bounds(self.quotients(other))
+    }
+
+    fn negate(&self) -> Self {
+        Self::from_bounds(self.corners(self, |value, _| -value))
+    }
+
+    fn equal(&self, other: &Self) -> AbstractBoolean {
+        match (self, other) {
+            (
+                Self::Range { low, high },
+                Self::Range {
+                    low: other_low,
+                    high: other_high,
+                },
+            ) if low == high && other_low == other_high && low == other_low => {
+                AbstractBoolean::True
+            }
+            _ if self.meet(other) == Self::Empty => AbstractBoolean::False,
+            _ => AbstractBoolean::Unknown,
+        }
+    }
+
+    fn less_than(&self, other: &Self) -> AbstractBoolean {
+        match (self, other) {
+            (Self::Empty, _) | (_, Self::Empty) => AbstractBoolean::Unknown,
+            (
+                Self::Range { low, high },
+                Self::Range {
+                    low: other_low,
+                    high: other_high,
+                },
+            ) => {
+                if high < other_low {
+                    AbstractBoolean::True
+                } else if low >= other_high {
+                    AbstractBoolean::False
+                } else {
+                    AbstractBoolean::Unknown
+                }
+            }
+        }
+    }
+
+    fn may_be_zero(&self) -> bool {
+        self.contains(0)
+    }
+
+    fn may_overflow_add(&self, other: &Self) -> bool {
+        Self::exceeds(self.corners(other, |left, right| left + right))
+    }
+
+    fn may_overflow_subtract(&self, other: &Self) -> bool {
+        Self::exceeds(self.corners(other, |left, right| left - right))
+    }
+
+    fn may_overflow_multiply(&self, other: &Self) -> bool {
+        Self::exceeds(self.corners(other, |left, right| left * right))
+    }
+
+    fn may_overflow_divide(&self, other: &Self) -> bool {
+        Self::exceeds(self.quotients(other))
+    }
+
+    fn may_overflow_negate(&self) -> bool {
+        Self::exceeds(self.corners(self, |value, _| -value))
+    }
+
+    fn assume_less_than(&self, other: &Self, inclusive: bool) -> Self {
+        match other {
+            Self::Empty => Self::Empty,
+            Self::Range { high, .. } => match (inclusive, high.checked_sub(1)) {
+                (true, _) => self.meet(&Self::new(i64::MIN, *high)),
+                (false, Some(high)) => self.meet(&Self::new(i64::MIN, high)),
+                (false, None) => Self::Empty,
+            },
+        }
+    }
+
+    fn assume_greater_than(&self, other: &Self, inclusive: bool) -> Self {
+        match other {
+            Self::Empty => Self::Empty,
+            Self::Range { low, .. } => match (inclusive, low.checked_add(1)) {
+                (true, _) => self.meet(&Self::new(*low, i64::MAX)),
+                (false, Some(low)) => self.meet(&Self::new(low, i64::MAX)),
+                (false, None) => Self::Empty,
+            },
+        }
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Empty => write!(f, "empty"),
+            Self::Range { low, high } => {
+                match *low {
+                    i64::MIN => write!(f, "(-inf, ")?,
+                    low => write!(f, "[{}, ", low)?,
+                }
+
+                match *high {
+                    i64::MAX => write!(f, "+inf)"),
+                    high => write!(f, "{}]", high),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use super::abstract_environment::AbstractEnvironment;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgramPoint<D> {
+    index: usize,
+    before: AbstractEnvironment<D>,
+    after: AbstractEnvironment<D>,
+}
+
+impl<D> ProgramPoint<D> {
+    pub fn new(
+        index: usize,
+        before: AbstractEnvironment<D>,
+        after: AbstractEnvironment<D>,
+    ) -> Self {
+        Self {
+            index,
+            before,
+            after,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn before(&self) -> &AbstractEnvironment<D> {
+        &self.before
+    }
+
+    pub fn after(&self) -> &AbstractEnvironment<D> {
+        &self.after
+    }
+}
@@ -0,0 +1,146 @@
+use super::{abstract_boolean::AbstractBoolean, abstract_domain::AbstractDomain};
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sign {
+    Negative,
+    Zero,
+    Positive,
+    Unknown,
+}
+
+impl AbstractDomain for Sign {
+    fn top() -> Self {
+        Self::Unknown
+    }
+
+    fn from_number(value: i64) -> Self {
+        match value {
+            ..0 => Self::Negative,
+            0 => Self::Zero,
+            1.. => Self::Positive,
+        }
+    }
+
+    fn join(&self, other: &Self) -> Self {
+        if self == other { *self } else { Self::Unknown }
+    }
+
+    fn meet(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Unknown, sign) | (sign, Self::Unknown) => *sign,
+            _ => *self,
+        }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Zero, sign) | (sign, Self::Zero) => *sign,
+            (Self::Positive, Self::Positive) => Self::Positive,
+            (Self::Negative, Self::Negative) => Self::Negative,
+            _ => Self::Unknown,
+        }
+    }
+
+    fn subtract(&self, other: &Self) -> Self {
+        self.add(&other.negate())
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Zero, _) | (_, Self::Zero) => Self::Zero,
+            (Self::Unknown, _) | (_, Self::Unknown) => Self::Unknown,
+            (left, right) if left == right => Self::Positive,
+            _ => Self::Negative,
+        }
+    }
+
+    fn divide(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Zero, _) => Self::Zero,
+            _ => Self::Unknown,
+        }
+    }
+
+    fn negate(&self) -> Self {
+        match self {
+            Self::Negative => Self::Positive,
+            Self::Positive => Self::Negative,
+            sign => *sign,
+        }
+    }
+
+    fn equal(&self, other: &Self) -> AbstractBoolean {
+        match (self, other) {
+            (Self::Zero, Self::Zero) => AbstractBoolean::True,
+            (Self::Unknown, _) | (_, Self::Unknown) => AbstractBoolean::Unknown,
+            (left, right) if left != right => AbstractBoolean::False,
+            _ => AbstractBoolean::Unknown,
+        }
+    }
+
+    fn less_than(&self, other: &Self) -> AbstractBoolean {
+        match (self, other) {
+            (Self::Negative, Self::Zero | Self::Positive) | (Self::Zero, Self::Positive) => {
+                AbstractBoolean::True
+            }
+            (Self::Zero | Self::Positive, Self::Negative)
+            | (Self::Positive, Self::Zero)
+            | (Self::Zero, Self::Zero) => AbstractBoolean::False,
+            _ => AbstractBoolean::Unknown,
+        }
+    }
+
+    fn may_be_zero(&self) -> bool {
+        matches!(self, Self::Zero | Self::Unknown)
+    }
+
+    fn may_overflow_add(&self, other: &Self) -> bool {
+        !matches!(
+            (self, other),
+            (Self::Zero, _)
+                | (_, Self::Zero)
+                | (Self::Positive, Self::Negative)
+                | (Self::Negative, Self::Positive)
+        )
+    }
+
+    fn may_overflow_subtract(&self, other: &Self) -> bool {
+        !matches!(
+            (self, other),
+            (_, Self::Zero)
+                | (Self::Positive, Self::Positive)
+                | (Self::Negative, Self::Negative)
+                | (Self::Zero, Self::Positive)
+        )
+    }
+
+    fn may_overflow_multiply(&self, other: &Self) -> bool {
+        !matches!((self, other), (Self::Zero, _) | (_, Self::Zero))
+    }
+
+    fn may_overflow_divide(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (
+                Self::Negative | Self::Unknown,
+                Self::Negative | Self::Unknown
+            )
+        )
+    }
+
+    fn may_overflow_negate(&self) -> bool {
+        matches!(self, Self::Negative | Self::Unknown)
+    }
+}
+
+impl Display for Sign {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Negative => write!(f, "negative"),
+            Self::Zero => write!(f, "zero"),
+            Self::Positive => write!(f, "positive"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
@@ -0,0 +1,453 @@
+pub mod c;
+pub mod javascript;
+pub mod python;
+pub mod ruby;
+
+use crate::{
+    environment::Environment, expression::Expression, statement::Statement,
+    transpile_error::TranspileError,
+};
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+
+pub trait Backend {
+    fn expression(&self, expression: &Expression) -> String;
+    fn program(
+        &self,
+        statement: &Statement,
+        environment: &Environment,
+    ) -> Result<String, TranspileError>;
+
+    fn operand(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::And(_, _)
+            | Expression::Or(_, _)
+            | Expression::Equal(_, _)
+            | Expression::NotEqual(_, _)
+            | Expression::LessThan(_, _)
+            | Expression::LessThanOrEqual(_, _)
+            | Expression::GreaterThan(_, _)
+            | Expression::GreaterThanOrEqual(_, _) => format!("({})", self.expression(expression)),
+            _ => self.expression(expression),
+        }
+    }
+}
+
+pub fn variables(statement: &Statement, environment: &Environment) -> BTreeSet<String> {
+    let mut variables = environment
+        .variables()
+        .map(|(name, _)| name.to_string())
+        .collect::<BTreeSet<_>>();
+    let mut statements = vec![statement];
+
+    while let Some(statement) = statements.pop() {
+        match statement {
+            Statement::Assign(name, _) => {
+                variables.insert(name.clone());
+            }
+            Statement::If(_, consequence, alternative) => {
+                statements.push(consequence);
+                statements.push(alternative);
+            }
+            Statement::Sequence(first, second) => {
+                statements.push(first);
+                statements.push(second);
+            }
+            Statement::While(_, body) => statements.push(body),
+            Statement::For(name, _, _, body) => {
+                variables.insert(name.clone());
+                statements.push(body);
+            }
+            Statement::Iteration(body, next) => {
+                statements.push(body);
+                statements.push(next);
+            }
+            Statement::Let(name, _, body) => variables.extend(
+                self::variables(body, &Environment::new())
+                    .into_iter()
+                    .filter(|variable| variable != name),
+            ),
+            Statement::Expression(_)
+            | Statement::Function(_, _, _)
+            | Statement::Return(_)
+            | Statement::Print(_)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Nothing => {}
+        }
+    }
+
+    variables
+}
+
+pub fn locals(parameters: &[String], body: &Statement) -> BTreeSet<String> {
+    variables(body, &Environment::new())
+        .into_iter()
+        .filter(|name| !parameters.contains(name))
+        .collect()
+}
+
+pub fn definitions(statement: &Statement) -> Vec<(&str, &[String], &Statement)> {
+    let mut definitions = Vec::new();
+    let mut statements = vec![statement];
+
+    while let Some(statement) = statements.pop() {
+        match statement {
+            Statement::Function(name, parameters, body) => {
+                definitions.push((name.as_str(), parameters.as_slice(), body.as_ref()))
+            }
+            Statement::If(_, consequence, alternative) => {
+                statements.push(alternative);
+                statements.push(consequence);
+            }
+            Statement::Sequence(first, second) => {
+                statements.push(second);
+                statements.push(first);
+            }
+            Statement::Iteration(body, next) => {
+                statements.push(next);
+                statements.push(body);
+            }
+            Statement::While(_, body)
+            | Statement::For(_, _, _, body)
+            | Statement::Let(_, _, body) => statements.push(body),
+            Statement::Expression(_)
+            | Statement::Assign(_, _)
+            | Statement::Return(_)
+            | Statement::Print(_)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Nothing => {}
+        }
+    }
+
+    definitions
+}
+
+pub fn intermediate(statement: &Statement) -> Option<&Statement> {
+    let mut statements = vec![statement];
+
+    while let Some(statement) = statements.pop() {
+        match statement {
+            Statement::Expression(expression)
+            | Statement::Assign(_, expression)
+            | Statement::Return(expression)
+            | Statement::Print(expression) => {
+                if invokes(expression) {
+                    return Some(statement);
+                }
+            }
+            Statement::If(condition, consequence, alternative) => {
+                if invokes(condition) {
+                    return Some(statement);
+                }
+
+                statements.push(alternative);
+                statements.push(consequence);
+            }
+            Statement::Sequence(first, second) => {
+                statements.push(second);
+                statements.push(first);
+            }
+            Statement::While(condition, body) => {
+                if invokes(condition) {
+                    return Some(statement);
+                }
+
+                statements.push(body);
+            }
+            Statement::For(_, start, end, body) => {
+                if invokes(start) || invokes(end) {
+                    return Some(statement);
+                }
+
+                statements.push(body);
+            }
+            Statement::Let(_, expression, body) => {
+                if invokes(expression) {
+                    return Some(statement);
+                }
+
+                statements.push(body);
+            }
+            Statement::Iteration(_, _) => return Some(statement),
+            Statement::Function(_, _, body) => statements.push(body),
+            Statement::Break | Statement::Continue | Statement::Nothing => {}
+        }
+    }
+
+    None
+}
+
+fn invokes(expression: &Expression) -> bool {
+    let mut expressions = vec![expression];
+
+    while let Some(expression) = expressions.pop() {
+        match expression {
+            Expression::Invocation(_, _, _) => return true,
+            Expression::Add(left, right)
+            | Expression::Subtract(left, right)
+            | Expression::Multiply(left, right)
+            | Expression::Divide(left, right)
+            | Expression::And(left, right)
+            | Expression::Or(left, right)
+            | Expression::Equal(left, right)
+            | Expression::NotEqual(left, right)
+            | Expression::LessThan(left, right)
+            | Expression::LessThanOrEqual(left, right)
+            | Expression::GreaterThan(left, right)
+            | Expression::GreaterThanOrEqual(left, right)
+            | Expression::Concatenate(left, right)
+            | Expression::Index(left, right)
+            | Expression::Append(left, right) => {
+                expressions.push(left);
+                expressions.push(right);
+            }
+            Expression::Negate(operand)
+            | Expression::Not(operand)
+            | Expression::Length(operand) => expressions.push(operand),
+            Expression::List(operands) | Expression::Call(_, operands) => {
+                expressions.extend(operands)
+            }
+            Expression::Number(_)
+            | Expression::Boolean(_)
+            | Expression::String(_)
+            | Expression::Variable(_)
+            | Expression::Input => {}
+        }
+    }
+
+    false
+}
+
+pub fn returns(statement: &Statement) -> bool {
+    let mut statements = vec![statement];
+
+    while let Some(statement) = statements.pop() {
+        match statement {
+            Statement::Return(_) => return true,
+            Statement::If(_, consequence, alternative) => {
+                statements.push(consequence);
+                statements.push(alternative);
+            }
+            Statement::Sequence(first, second) => {
+                statements.push(first);
+                statements.push(second);
+            }
+            Statement::Iteration(body, next) => {
+                statements.push(body);
+                statements.push(next);
+            }
+            Statement::While(_, body)
+            | Statement::For(_, _, _, body)
+            | Statement::Let(_, _, body) => statements.push(body),
+            Statement::Expression(_)
+            | Statement::Assign(_, _)
+            | Statement::Function(_, _, _)
+            | Statement::Print(_)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Nothing => {}
+        }
+    }
+
+    false
+}
+
+pub fn hoist(statement: &Statement, environment: &Environment) -> (Statement, BTreeSet<String>) {
+    let mut taken = variables(statement, environment);
+    let mut hidden = BTreeSet::new();
+    let mut statements = vec![statement];
+
+    while let Some(statement) = statements.pop() {
+        match statement {
+            Statement::If(_, consequence, alternative) => {
+                statements.push(consequence);
+                statements.push(alternative);
+            }
+            Statement::Sequence(first, second) => {
+                statements.push(first);
+                statements.push(second);
+            }
+            Statement::While(_, body) | Statement::For(_, _, _, body) => statements.push(body),
+            Statement::Iteration(body, next) => {
+                statements.push(body);
+                statements.push(next);
+            }
+            Statement::Let(name, _, body) => {
+                taken.insert(name.clone());
+                statements.push(body);
+            }
+            Statement::Function(_, parameters, body) => {
+                taken.extend(parameters.iter().cloned());
+                taken.extend(locals(parameters, body));
+                statements.push(body);
+            }
+            Statement::Expression(_)
+            | Statement::Assign(_, _)
+            | Statement::Return(_)
+            | Statement::Print(_)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Nothing => {}
+        }
+    }
+
+    let hoisted = hoist_statement(statement, &HashMap::new(), &mut taken, &mut hidden);
+    (hoisted, hidden)
+}
+
+fn hoist_statement(
+    statement: &Statement,
+    renames: &HashMap<String, String>,
+    taken: &mut BTreeSet<String>,
+    hidden: &mut BTreeSet<String>,
+) -> Statement {
+    match statement {
+        Statement::Expression(expression) => Statement::Expression(rename(expression, renames)),
+        Statement::Assign(name, expression) => Statement::Assign(
+            renames.get(name).unwrap_or(name).clone(),
+            rename(expression, renames),
+        ),
+        Statement::If(condition, consequence, alternative) => Statement::If(
+            rename(condition, renames),
+            Box::new(hoist_statement(consequence, renames, taken, hidden)),
+            Box::new(hoist_statement(alternative, renames, taken, hidden)),
+        ),
+        Statement::Sequence(_, _) => Statement::sequence(
+            statement
+                .spine()
+                .into_iter()
+                .map(|statement| hoist_statement(statement, renames, taken, hidden))
+                .collect(),
+        ),
+        Statement::While(condition, body) => Statement::While(
+            rename(condition, renames),
+            Box::new(hoist_statement(body, renames, taken, hidden)),
+        ),
+        Statement::For(name, start, end, body) => Statement::For(
+            renames.get(name).unwrap_or(name).clone(),
+            rename(start, renames),
+            rename(end, renames),
+            Box::new(hoist_statement(body, renames, taken, hidden)),
+        ),
+        Statement::Iteration(body, next) => Statement::Iteration(
+            Box::new(hoist_statement(body, renames, taken, hidden)),
+            Box::new(hoist_statement(next, renames, taken, hidden)),
+        ),
+        Statement::Let(name, expression, body) => {
+            let fresh = (1..)
+                .map(|index| format!("{}_{}", name, index))
+                .find(|fresh| !taken.contains(fresh))
+                .unwrap();
+            taken.insert(fresh.clone());
+            hidden.insert(fresh.clone());
+
+            let mut inner = renames.clone();
+            inner.insert(name.clone(), fresh.clone());
+
+            Statement::Sequence(
+                Arc::new(Statement::Assign(fresh, rename(expression, renames))),
+                Arc::new(hoist_statement(body, &inner, taken, hidden)),
+            )
+        }
+        Statement::Function(name, parameters, body) => Statement::Function(
+            name.clone(),
+            parameters.clone(),
+            Box::new(hoist_statement(body, &HashMap::new(), taken, hidden)),
+        ),
+        Statement::Return(expression) => Statement::Return(rename(expression, renames)),
+        Statement::Print(expression) => Statement::Print(rename(expression, renames)),
+        Statement::Break => Statement::Break,
+        Statement::Continue => Statement::Continue,
+        Statement::Nothing => Statement::Nothing,
+    }
+}
+
+pub fn rename(expression: &Expression, renames: &HashMap<String, String>) -> Expression {
+    let binary = |constructor: fn(Box<Expression>, Box<Expression>) -> Expression,
+                  left: &Expression,
+                  right: &Expression| {
+        constructor(
+            Box::new(rename(left, renames)),
+            Box::new(rename(right, renames)),
+        )
+    };
+
+    match expression {
+        Expression::Number(_)
+        | Expression::Boolean(_)
+        | Expression::String(_)
+        | Expression::Input
+        | Expression::Invocation(_, _, _) => expression.clone(),
+        Expression::List(elements) => Expression::List(
+            elements
+                .iter()
+                .map(|element| rename(element, renames))
+                .collect(),
+        ),
+        Expression::Variable(name) => {
+            Expression::Variable(renames.get(name).unwrap_or(name).clone())
+        }
+        Expression::Add(left, right) => binary(Expression::Add, left, right),
+        Expression::Subtract(left, right) => binary(Expression::Subtract, left, right),
+        Expression::Multiply(left, right) => binary(Expression::Multiply, left, right),
+        Expression::Divide(left, right) => binary(Expression::Divide, left, right),
+        Expression::Negate(operand) => Expression::Negate(Box::new(rename(operand, renames))),
+        Expression::Not(operand) => Expression::Not(Box::new(rename(operand, renames))),
+        Expression::And(left, right) => binary(Expression::And, left, right),
+        Expression::Or(left, right) => binary(Expression::Or, left, right),
+        Expression::Equal(left, right) => binary(Expression::Equal, left, right),
+        Expression::NotEqual(left, right) => binary(Expression::NotEqual, left, right),
+        Expression::LessThan(left, right) => binary(Expression::LessThan, left, right),
+        Expression::LessThanOrEqual(left, right) => {
+            binary(Expression::LessThanOrEqual, left, right)
+        }
+        Expression::GreaterThan(left, right) => binary(Expression::GreaterThan, left, right),
+        Expression::GreaterThanOrEqual(left, right) => {
+            binary(Expression::GreaterThanOrEqual, left, right)
+        }
+        Expression::Concatenate(left, right) => binary(Expression::Concatenate, left, right),
+        Expression::Length(operand) => Expression::Length(Box::new(rename(operand, renames))),
+        Expression::Index(list, index) => binary(Expression::Index, list, index),
+        Expression::Append(list, element) => binary(Expression::Append, list, element),
+        Expression::Call(name, arguments) => Expression::Call(
+            name.clone(),
+            arguments
+                .iter()
+                .map(|argument| rename(argument, renames))
+                .collect(),
+        ),
+    }
+}
+
+pub fn terminates(statement: &Statement) -> bool {
+    match statement {
+        Statement::Return(_) => true,
+        Statement::Sequence(_, _) => statement.spine().into_iter().any(terminates),
+        Statement::If(_, consequence, alternative) => {
+            terminates(consequence) && terminates(alternative)
+        }
+        Statement::Let(_, _, body) => terminates(body),
+        Statement::Expression(_)
+        | Statement::Assign(_, _)
+        | Statement::While(_, _)
+        | Statement::For(_, _, _, _)
+        | Statement::Iteration(_, _)
+        | Statement::Break
+        | Statement::Continue
+        | Statement::Function(_, _, _)
+        | Statement::Print(_)
+        | Statement::Nothing => false,
+    }
+}
+
+pub fn missing_return(name: &str) -> String {
+    format!("function '{}' finished without returning a value", name)
+}
+
+pub fn indent(depth: usize) -> String {
+    "    ".repeat(depth)
+}
@@ -0,0 +1,600 @@
+use super::{
+    Backend, definitions, hoist, indent, intermediate, locals, missing_return, returns, variables,
+};
+use crate::{
+    environment::Environment, expression::Expression, statement::Statement, target::Target,
+    transpile_error::TranspileError, type_checker::TypeChecker, type_context::TypeContext,
+    value_type::ValueType,
+};
+
+const PRELUDE: &str = r#"#include <limits.h>
+#include <stdbool.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+static inline long long fail(const char *message) {
+    fprintf(stderr, "%s\n", message);
+    exit(1);
+}
+
+static inline long long checked_add(long long left, long long right) {
+    long long result;
+    return __builtin_add_overflow(left, right, &result) ? fail("arithmetic overflow") : result;
+}
+
+static inline long long checked_subtract(long long left, long long right) {
+    long long result;
+    return __builtin_sub_overflow(left, right, &result) ? fail("arithmetic overflow") : result;
+}
+
+static inline long long checked_multiply(long long left, long long right) {
+    long long result;
+    return __builtin_mul_overflow(left, right, &result) ? fail("arithmetic overflow") : result;
+}
+
+static inline long long checked_divide(long long left, long long right) {
+    if (right == 0) return fail("division by zero");
+    if (left == LLONG_MIN && right == -1) return fail("arithmetic overflow");
+    return left / right;
+}
+
+static inline long long checked_negate(long long value) {
+    return value == LLONG_MIN ? fail("arithmetic overflow") : -value;
+}
+"#;
+
+const RESERVED: [&str; 55] = [
+    "EOF",
+    "LLONG_MAX",
+    "LLONG_MIN",
+    "NULL",
+    "_Bool",
+    "_Complex",
+    "_Imaginary",
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "checked_add",
+    "checked_divide",
+    "checked_multiply",
+    "checked_negate",
+    "checked_subtract",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "exit",
+    "extern",
+    "fail",
+    "false",
+    "float",
+    "for",
+    "fprintf",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "main",
+    "printf",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "stderr",
+    "struct",
+    "switch",
+    "true",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+];
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CBackend;
+
+impl CBackend {
+    fn identifier(&self, name: &str) -> String {
+        if RESERVED.contains(&name)
+            || name.starts_with("defined_")
+            || name.starts_with("function_")
+            || name.starts_with("operand_")
+        {
+            format!("{}_", name)
+        } else {
+            name.into()
+        }
+    }
+
+    fn call(&self, function: &str, left: &Expression, right: &Expression) -> String {
+        self.sequenced(&[left, right], Self::expression, |operands| {
+            format!("{}({})", function, operands.join(", "))
+        })
+    }
+
+    fn binary(&self, left: &Expression, operator: &str, right: &Expression) -> String {
+        format!(
+            "{} {} {}",
+            self.operand(left),
+            operator,
+            self.operand(right)
+        )
+    }
+
+    fn comparison(&self, left: &Expression, operator: &str, right: &Expression) -> String {
+        self.sequenced(&[left, right], Self::operand, |operands| {
+            format!("{} {} {}", operands[0], operator, operands[1])
+        })
+    }
+
+    fn sequenced(
+        &self,
+        operands: &[&Expression],
+        render: fn(&Self, &Expression) -> String,
+        build: impl Fn(&[String]) -> String,
+    ) -> String {
+        let effects = operands
+            .iter()
+            .filter(|operand| {
+                !matches!(
+                    operand,
+                    Expression::Number(_) | Expression::Boolean(_) | Expression::Variable(_)
+                )
+            })
+            .count();
+
+        if effects < 2 {
+            return build(
+                &operands
+                    .iter()
+                    .map(|operand| render(self, operand))
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        let declarations = operands
+            .iter()
+            .enumerate()
+            .map(|(index, operand)| {
+                format!(
+                    "__auto_type operand_{} = {}; ",
+                    index,
+                    self.expression(operand)
+                )
+            })
+            .collect::<String>();
+        let names = (0..operands.len())
+            .map(|index| format!("operand_{}", index))
+            .collect::<Vec<_>>();
+
+        format!("({{ {}{}; }})", declarations, build(&names))
+    }
+
+    fn declaration(&self, value_type: Option<ValueType>) -> (&'static str, &'static str) {
+        match value_type {
+            Some(ValueType::Boolean) => ("bool", "false"),
+            _ => ("long long", "0LL"),
+        }
+    }
+
+    fn functions(
+        &self,
+        statement: &Statement,
+        context: &TypeContext,
+        prototypes: &mut Vec<String>,
+        lines: &mut Vec<String>,
+    ) -> Result<(), TranspileError> {
+        for (name, parameters, body) in definitions(statement) {
+            let Some(signature) = context.signature(name) else {
+                return Err(TranspileError::Unsigned {
+                    target: Target::C,
+                    name: name.into(),
+                });
+            };
+            let mut local = context.local(name);
+            typed(body, &mut local)?;
+            local.settle();
+            let declarations = parameters
+                .iter()
+                .zip(signature.parameters())
+                .map(|(parameter, value_type)| {
+                    format!(
+                        "{} {}",
+                        self.declaration(Some(value_type.clone())).0,
+                        self.identifier(parameter)
+                    )
+                })
+                .collect::<Vec<_>>();
+            let header = format!(
+                "static {} function_{}({})",
+                self.declaration(signature.result()).0,
+                name,
+                if declarations.is_empty() {
+                    "void".into()
+                } else {
+                    declarations.join(", ")
+                }
+            );
+
+            self.functions(body, &local, prototypes, lines)?;
+            prototypes.push(format!("{};", header));
+            lines.push(format!("{} {{", header));
+
+            for name in locals(parameters, body) {
+                let (declaration, zero) = self.declaration(local.declared(&name));
+                lines.push(format!(
+                    "{}{} {} = {};",
+                    indent(1),
+                    declaration,
+                    self.identifier(&name),
+                    zero
+                ));
+            }
+
+            self.statement(body, 1, None, &local, lines);
+            lines.push(format!(
+                "{}return fail(\"{}\");",
+                indent(1),
+                missing_return(name)
+            ));
+            lines.push("}".into());
+            lines.push(String::new());
+        }
+
+        Ok(())
+    }
+
+    fn statement(
+        &self,
+        statement: &Statement,
+        depth: usize,
+        environment: Option<&Environment>,
+        context: &TypeContext,
+        lines: &mut Vec<String>,
+    ) {
+        match statement {
+            Statement::Expression(expression) => lines.push(format!(
+                "{}(void)({});",
+                indent(depth),
+                self.expression(expression)
+            )),
+            Statement::Assign(name, expression) => {
+                lines.push(format!(
+                    "{}{} = {};",
+                    indent(depth),
+                    self.identifier(name),
+                    self.expression(expression)
+                ));
+
+                if environment.is_some_and(|environment| environment.get(name).is_err()) {
+                    lines.push(format!("{}defined_{} = true;", indent(depth), name));
+                }
+            }
+            Statement::If(condition, consequence, alternative) => {
+                lines.push(format!(
+                    "{}if ({}) {{",
+                    indent(depth),
+                    self.expression(condition)
+                ));
+                self.statement(consequence, depth + 1, environment, context, lines);
+
+                if **alternative != Statement::Nothing {
+                    lines.push(format!("{}}} else {{", indent(depth)));
+                    self.statement(alternative, depth + 1, environment, context, lines);
+                }
+
+                lines.push(format!("{}}}", indent(depth)));
+            }
+            Statement::Sequence(_, _) => {
+                for statement in statement.spine() {
+                    self.statement(statement, depth, environment, context, lines);
+                }
+            }
+            Statement::While(condition, body) => {
+                lines.push(format!(
+                    "{}while ({}) {{",
+                    indent(depth),
+                    self.expression(condition)
+                ));
+                self.statement(body, depth + 1, environment, context, lines);
+                lines.push(format!("{}}}", indent(depth)));
+            }
+            Statement::For(name, start, end, body) => {
+                let counter = Box::new(Expression::Variable(name.clone()));
+
+                if environment.is_some_and(|environment| environment.get(name).is_err()) {
+                    lines.push(format!("{}defined_{} = true;", indent(depth), name));
+                }
+
+                lines.push(format!(
+                    "{}for ({} = {}; {}; {} = {}) {{",
+                    indent(depth),
+                    self.identifier(name),
+                    self.expression(start),
+                    self.expression(&Expression::LessThanOrEqual(
+                        counter.clone(),
+                        Box::new(end.clone())
+                    )),
+                    self.identifier(name),
+                    self.expression(&Expression::Add(counter, Box::new(Expression::Number(1))))
+                ));
+                self.statement(body, depth + 1, environment, context, lines);
+                lines.push(format!("{}}}", indent(depth)));
+            }
+            Statement::Iteration(_, _) => {
+                unreachable!("iterations are rejected before translation")
+            }
+            Statement::Let(_, _, _) => {
+                unreachable!("let statements are hoisted before translation")
+            }
+            Statement::Function(_, _, _) => {}
+            Statement::Return(expression) => match environment {
+                Some(_) => {
+                    lines.push(format!(
+                        "{}(void)({});",
+                        indent(depth),
+                        self.expression(expression)
+                    ));
+                    lines.push(format!("{}goto finish;", indent(depth)));
+                }
+                None => lines.push(format!(
+                    "{}return {};",
+                    indent(depth),
+                    self.expression(expression)
+                )),
+            },
+            Statement::Print(expression) => match expression.type_check(&mut context.clone()) {
+                Ok(ValueType::Boolean) => lines.push(format!(
+                    "{}puts({} ? \"true\" : \"false\");",
+                    indent(depth),
+                    self.operand(expression)
+                )),
+                _ => lines.push(format!(
+                    "{}printf(\"%lld\\n\", {});",
+                    indent(depth),
+                    self.expression(expression)
+                )),
+            },
+            Statement::Break => lines.push(format!("{}break;", indent(depth))),
+            Statement::Continue => lines.push(format!("{}continue;", indent(depth))),
+            Statement::Nothing => {}
+        }
+    }
+}
+
+impl Backend for CBackend {
+    fn expression(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Number(i64::MIN) => "LLONG_MIN".into(),
+            Expression::Number(value) => format!("{}LL", value),
+            Expression::Boolean(value) => value.to_string(),
+            Expression::String(_)
+            | Expression::List(_)
+            | Expression::Concatenate(_, _)
+            | Expression::Length(_)
+            | Expression::Index(_, _)
+            | Expression::Append(_, _)
+            | Expression::Input => {
+                unreachable!("strings, lists and input are rejected before emitting C")
+            }
+            Expression::Variable(name) => self.identifier(name),
+            Expression::Add(left, right) => self.call("checked_add", left, right),
+            Expression::Subtract(left, right) => self.call("checked_subtract", left, right),
+            Expression::Multiply(left, right) => self.call("checked_multiply", left, right),
+            Expression::Divide(left, right) => self.call("checked_divide", left, right),
+            Expression::Negate(expression) => {
+                format!("checked_negate({})", self.expression(expression))
+            }
+            Expression::Not(expression) => format!("!{}", self.operand(expression)),
+            Expression::And(left, right) => self.binary(left, "&&", right),
+            Expression::Or(left, right) => self.binary(left, "||", right),
+            Expression::Equal(left, right) => self.comparison(left, "==", right),
+            Expression::NotEqual(left, right) => self.comparison(left, "!=", right),
+            Expression::LessThan(left, right) => self.comparison(left, "<", right),
+            Expression::LessThanOrEqual(left, right) => self.comparison(left, "<=", right),
+            Expression::GreaterThan(left, right) => self.comparison(left, ">", right),
+            Expression::GreaterThanOrEqual(left, right) => self.comparison(left, ">=", right),
+            Expression::Call(name, arguments) => self.sequenced(
+                &arguments.iter().collect::<Vec<_>>(),
+                Self::expression,
+                |operands| format!("function_{}({})", name, operands.join(", ")),
+            ),
+            Expression::Invocation(_, _, _) => {
+                unreachable!("invocations are rejected before translation")
+            }
+        }
+    }
+
+    fn program(
+        &self,
+        statement: &Statement,
+        environment: &Environment,
+    ) -> Result<String, TranspileError> {
+        if let Some(statement) = intermediate(statement) {
+            return Err(TranspileError::Intermediate {
+                target: Target::C,
+                statement: Box::new(statement.clone()),
+            });
+        }
+
+        if let Some(expression) = unsupported(statement, environment) {
+            return Err(TranspileError::Unsupported {
+                target: Target::C,
+                expression: expression.clone(),
+            });
+        }
+
+        let (statement, hidden) = hoist(statement, environment);
+        let statement = &statement;
+        let variables = variables(statement, environment);
+        let mut context = TypeContext::from(environment);
+        typed(statement, &mut context)?;
+        context.settle();
+        let mut prototypes = Vec::new();
+        let mut functions = Vec::new();
+        self.functions(statement, &context, &mut prototypes, &mut functions)?;
+
+        let mut lines = vec![PRELUDE.to_string()];
+
+        if !prototypes.is_empty() {
+            lines.append(&mut prototypes);
+            lines.push(String::new());
+            lines.append(&mut functions);
+        }
+
+        lines.push("int main(void) {".into());
+
+        for name in &variables {
+            let (declaration, zero) = self.declaration(context.declared(name));
+
+            match environment.get(name) {
+                Ok(value) => lines.push(format!(
+                    "{}{} {} = {};",
+                    indent(1),
+                    declaration,
+                    self.identifier(name),
+                    self.expression(&value)
+                )),
+                Err(_) => {
+                    lines.push(format!(
+                        "{}{} {} = {};",
+                        indent(1),
+                        declaration,
+                        self.identifier(name),
+                        zero
+                    ));
+                    lines.push(format!("{}bool defined_{} = false;", indent(1), name));
+                }
+            }
+        }
+
+        self.statement(statement, 1, Some(environment), &context, &mut lines);
+
+        if returns(statement) {
+            lines.push("finish:".into());
+        }
+
+        for name in variables.difference(&hidden) {
+            let print = match context.declared(name) {
+                Some(ValueType::Boolean) => format!(
+                    "printf(\"{} = %s\\n\", {} ? \"true\" : \"false\");",
+                    name,
+                    self.identifier(name)
+                ),
+                _ => format!("printf(\"{} = %lld\\n\", {});", name, self.identifier(name)),
+            };
+
+            match environment.get(name) {
+                Ok(_) => lines.push(format!("{}{}", indent(1), print)),
+                Err(_) => lines.push(format!("{}if (defined_{}) {}", indent(1), name, print)),
+            }
+        }
+
+        lines.push(format!("{}return 0;", indent(1)));
+        lines.push("}".into());
+        Ok(lines.join("\n") + "\n")
+    }
+}
+
+fn typed(statement: &Statement, context: &mut TypeContext) -> Result<(), TranspileError> {
+    statement
+        .type_check(context)
+        .map_err(|errors| TranspileError::IllTyped {
+            target: Target::C,
+            errors,
+        })
+}
+
+fn unsupported<'a>(
+    statement: &'a Statement,
+    environment: &'a Environment,
+) -> Option<&'a Expression> {
+    let mut expressions = environment
+        .variables()
+        .map(|(_, value)| value)
+        .collect::<Vec<_>>();
+    let mut statements = vec![statement];
+
+    while let Some(statement) = statements.pop() {
+        match statement {
+            Statement::Expression(expression)
+            | Statement::Assign(_, expression)
+            | Statement::Return(expression)
+            | Statement::Print(expression) => expressions.push(expression),
+            Statement::If(condition, consequence, alternative) => {
+                expressions.push(condition);
+                statements.push(consequence);
+                statements.push(alternative);
+            }
+            Statement::Sequence(first, second) => {
+                statements.push(first);
+                statements.push(second);
+            }
+            Statement::While(condition, body) => {
+                expressions.push(condition);
+                statements.push(body);
+            }
+            Statement::For(_, start, end, body) => {
+                expressions.push(start);
+                expressions.push(end);
+                statements.push(body);
+            }
+            Statement::Iteration(body, next) => {
+                statements.push(body);
+                statements.push(next);
+            }
+            Statement::Let(_, expression, body) => {
+                expressions.push(expression);
+                statements.push(body);
+            }
+            Statement::Function(_, _, body) => statements.push(body),
+            Statement::Break | Statement::Continue | Statement::Nothing => {}
+        }
+    }
+
+    while let Some(expression) = expressions.pop() {
+        match expression {
+            Expression::String(_)
+            | Expression::List(_)
+            | Expression::Concatenate(_, _)
+            | Expression::Length(_)
+            | Expression::Index(_, _)
+            | Expression::Append(_, _)
+            | Expression::Input => return Some(expression),
+            Expression::Add(left, right)
+            | Expression::Subtract(left, right)
+            | Expression::Multiply(left, right)
+            | Expression::Divide(left, right)
+            | Expression::And(left, right)
+            | Expression::Or(left, right)
+            | Expression::Equal(left, right)
+            | Expression::NotEqual(left, right)
+            | Expression::LessThan(left, right)
+            | Expression::LessThanOrEqual(left, right)
+            | Expression::GreaterThan(left, right)
+            | Expression::GreaterThanOrEqual(left, right) => {
+                expressions.push(left);
+                expressions.push(right);
+            }
+            Expression::Negate(operand) | Expression::Not(operand) => expressions.push(operand),
+            Expression::Call(_, arguments) => expressions.extend(arguments),
+            Expression::Number(_)
+            | Expression::Boolean(_)
+            | Expression::Variable(_)
+            | Expression::Invocation(_, _, _) => {}
+        }
+    }
+
+    None
+}
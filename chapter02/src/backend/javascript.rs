@@ -0,0 +1,408 @@
+use super::{
+    Backend, definitions, hoist, indent, intermediate, locals, missing_return, returns, terminates,
+    variables,
+};
+use crate::{
+    environment::Environment, expression::Expression, statement::Statement, target::Target,
+    transpile_error::TranspileError,
+};
+
+const PRELUDE: &str = r#"const MIN = -(2n ** 63n);
+const MAX = 2n ** 63n - 1n;
+const checked = (value) => {
+    if (value < MIN || value > MAX) throw new RangeError("arithmetic overflow");
+    return value;
+};
+const divide = (left, right) => {
+    if (right === 0n) throw new RangeError("division by zero");
+    return checked(left / right);
+};
+const equal = (left, right) => {
+    if (!Array.isArray(left) || !Array.isArray(right)) return left === right;
+    return left.length === right.length && left.every((value, position) => equal(value, right[position]));
+};
+const length = (value) => BigInt(Array.isArray(value) ? value.length : [...value].length);
+const index = (list, position) => {
+    if (position < 0n || position >= BigInt(list.length)) throw new RangeError("index out of bounds");
+    return list[Number(position)];
+};
+const show = (value) => {
+    if (Array.isArray(value)) return `[${value.map(show).join(", ")}]`;
+    return typeof value === "string" ? JSON.stringify(value) : `${value}`;
+};
+const write = (value) => console.log(typeof value === "string" ? value : show(value));
+let pending;
+const input = () => {
+    pending ??= require("fs").readFileSync(0, "utf8").split("\n").reverse();
+    if (pending.length === 0 || (pending.length === 1 && pending[0] === "")) {
+        throw new Error("no more input to read");
+    }
+    return pending.pop();
+};
+"#;
+
+const RESERVED: [&str; 64] = [
+    "Array",
+    "BigInt",
+    "Error",
+    "JSON",
+    "MAX",
+    "MIN",
+    "Math",
+    "Number",
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "checked",
+    "class",
+    "console",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "divide",
+    "do",
+    "else",
+    "enum",
+    "equal",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "index",
+    "input",
+    "instanceof",
+    "interface",
+    "length",
+    "let",
+    "new",
+    "null",
+    "package",
+    "pending",
+    "private",
+    "protected",
+    "public",
+    "require",
+    "return",
+    "show",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "write",
+    "yield",
+];
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JavaScriptBackend;
+
+impl JavaScriptBackend {
+    fn identifier(&self, name: &str) -> String {
+        if RESERVED.contains(&name) || name.starts_with("function_") {
+            format!("{}_", name)
+        } else {
+            name.into()
+        }
+    }
+
+    fn function(&self, name: &str) -> String {
+        format!("function_{}", name)
+    }
+
+    fn binary(&self, left: &Expression, operator: &str, right: &Expression) -> String {
+        format!(
+            "{} {} {}",
+            self.operand(left),
+            operator,
+            self.operand(right)
+        )
+    }
+
+    fn declare(&self, names: &[String], depth: usize, lines: &mut Vec<String>) {
+        if !names.is_empty() {
+            lines.push(format!("{}let {};", indent(depth), names.join(", ")));
+        }
+    }
+
+    fn statement(
+        &self,
+        statement: &Statement,
+        depth: usize,
+        nested: bool,
+        lines: &mut Vec<String>,
+    ) {
+        match statement {
+            Statement::Expression(expression) => {
+                lines.push(format!("{}{};", indent(depth), self.expression(expression)))
+            }
+            Statement::Assign(name, expression) => lines.push(format!(
+                "{}{} = {};",
+                indent(depth),
+                self.identifier(name),
+                self.expression(expression)
+            )),
+            Statement::If(condition, consequence, alternative) => {
+                lines.push(format!(
+                    "{}if ({}) {{",
+                    indent(depth),
+                    self.expression(condition)
+                ));
+                self.statement(consequence, depth + 1, nested, lines);
+
+                if **alternative != Statement::Nothing {
+                    lines.push(format!("{}}} else {{", indent(depth)));
+                    self.statement(alternative, depth + 1, nested, lines);
+                }
+
+                lines.push(format!("{}}}", indent(depth)));
+            }
+            Statement::Sequence(_, _) => {
+                for statement in statement.spine() {
+                    self.statement(statement, depth, nested, lines);
+                }
+            }
+            Statement::While(condition, body) => {
+                lines.push(format!(
+                    "{}while ({}) {{",
+                    indent(depth),
+                    self.expression(condition)
+                ));
+                self.statement(body, depth + 1, nested, lines);
+                lines.push(format!("{}}}", indent(depth)));
+            }
+            Statement::For(name, start, end, body) => {
+                let counter = Box::new(Expression::Variable(name.clone()));
+
+                lines.push(format!(
+                    "{}for ({} = {}; {}; {} = {}) {{",
+                    indent(depth),
+                    self.identifier(name),
+                    self.expression(start),
+                    self.expression(&Expression::LessThanOrEqual(
+                        counter.clone(),
+                        Box::new(end.clone())
+                    )),
+                    self.identifier(name),
+                    self.expression(&Expression::Add(counter, Box::new(Expression::Number(1))))
+                ));
+                self.statement(body, depth + 1, nested, lines);
+                lines.push(format!("{}}}", indent(depth)));
+            }
+            Statement::Iteration(_, _) => {
+                unreachable!("iterations are rejected before translation")
+            }
+            Statement::Let(_, _, _) => {
+                unreachable!("let statements are hoisted before translation")
+            }
+            Statement::Function(name, parameters, body) => {
+                let names = locals(parameters, body)
+                    .iter()
+                    .map(|name| self.identifier(name))
+                    .chain(
+                        definitions(body)
+                            .into_iter()
+                            .map(|(name, _, _)| self.function(name)),
+                    )
+                    .collect::<Vec<_>>();
+                let arity = parameters.len();
+                let parameters = parameters
+                    .iter()
+                    .map(|parameter| self.identifier(parameter))
+                    .collect::<Vec<_>>();
+
+                lines.push(format!(
+                    "{}{} = function ({}) {{",
+                    indent(depth),
+                    self.function(name),
+                    parameters.join(", ")
+                ));
+                lines.push(format!(
+                    "{}if (arguments.length !== {}) throw new Error(`function '{}' expects {} arguments but was given ${{arguments.length}}`);",
+                    indent(depth + 1),
+                    arity,
+                    name,
+                    arity
+                ));
+                self.declare(&names, depth + 1, lines);
+                self.statement(body, depth + 1, true, lines);
+
+                if !terminates(body) {
+                    lines.push(format!(
+                        "{}throw new Error(\"{}\");",
+                        indent(depth + 1),
+                        missing_return(name)
+                    ));
+                }
+
+                lines.push(format!("{}}};", indent(depth)));
+            }
+            Statement::Return(expression) if nested => lines.push(format!(
+                "{}return {};",
+                indent(depth),
+                self.expression(expression)
+            )),
+            Statement::Return(expression) => {
+                lines.push(format!(
+                    "{}void ({});",
+                    indent(depth),
+                    self.expression(expression)
+                ));
+                lines.push(format!("{}break program;", indent(depth)));
+            }
+            Statement::Print(expression) => lines.push(format!(
+                "{}write({});",
+                indent(depth),
+                self.expression(expression)
+            )),
+            Statement::Break => lines.push(format!("{}break;", indent(depth))),
+            Statement::Continue => lines.push(format!("{}continue;", indent(depth))),
+            Statement::Nothing => {}
+        }
+    }
+}
+
+impl Backend for JavaScriptBackend {
+    fn expression(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Number(value) => format!("{}n", value),
+            Expression::Boolean(value) => value.to_string(),
+            Expression::String(_) => expression.to_string(),
+            Expression::List(elements) => format!(
+                "[{}]",
+                elements
+                    .iter()
+                    .map(|element| self.expression(element))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Expression::Variable(name) => self.identifier(name),
+            Expression::Add(left, right) => format!("checked({})", self.binary(left, "+", right)),
+            Expression::Subtract(left, right) => {
+                format!("checked({})", self.binary(left, "-", right))
+            }
+            Expression::Multiply(left, right) => {
+                format!("checked({})", self.binary(left, "*", right))
+            }
+            Expression::Divide(left, right) => format!(
+                "divide({}, {})",
+                self.expression(left),
+                self.expression(right)
+            ),
+            Expression::Negate(expression) => match **expression {
+                Expression::Number(_) => format!("checked(-({}))", self.expression(expression)),
+                _ => format!("checked(-{})", self.operand(expression)),
+            },
+            Expression::Not(expression) => format!("!{}", self.operand(expression)),
+            Expression::And(left, right) => self.binary(left, "&&", right),
+            Expression::Or(left, right) => self.binary(left, "||", right),
+            Expression::Equal(left, right) => format!(
+                "equal({}, {})",
+                self.expression(left),
+                self.expression(right)
+            ),
+            Expression::NotEqual(left, right) => format!(
+                "!equal({}, {})",
+                self.expression(left),
+                self.expression(right)
+            ),
+            Expression::LessThan(left, right) => self.binary(left, "<", right),
+            Expression::LessThanOrEqual(left, right) => self.binary(left, "<=", right),
+            Expression::GreaterThan(left, right) => self.binary(left, ">", right),
+            Expression::GreaterThanOrEqual(left, right) => self.binary(left, ">=", right),
+            Expression::Concatenate(left, right) => self.binary(left, "+", right),
+            Expression::Length(expression) => format!("length({})", self.expression(expression)),
+            Expression::Index(list, index) => format!(
+                "index({}, {})",
+                self.expression(list),
+                self.expression(index)
+            ),
+            Expression::Append(list, element) => format!(
+                "[...{}, {}]",
+                self.expression(list),
+                self.expression(element)
+            ),
+            Expression::Input => "input()".into(),
+            Expression::Call(name, arguments) => format!(
+                "{}({})",
+                self.function(name),
+                arguments
+                    .iter()
+                    .map(|argument| self.expression(argument))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Expression::Invocation(_, _, _) => {
+                unreachable!("invocations are rejected before translation")
+            }
+        }
+    }
+
+    fn program(
+        &self,
+        statement: &Statement,
+        environment: &Environment,
+    ) -> Result<String, TranspileError> {
+        if let Some(statement) = intermediate(statement) {
+            return Err(TranspileError::Intermediate {
+                target: Target::JavaScript,
+                statement: Box::new(statement.clone()),
+            });
+        }
+
+        let (statement, hidden) = hoist(statement, environment);
+        let statement = &statement;
+        let variables = variables(statement, environment);
+        let mut lines = vec![PRELUDE.to_string()];
+
+        for name in &variables {
+            match environment.get(name) {
+                Ok(value) => lines.push(format!(
+                    "let {} = {};",
+                    self.identifier(name),
+                    self.expression(&value)
+                )),
+                Err(_) => lines.push(format!("let {};", self.identifier(name))),
+            }
+        }
+
+        let names = definitions(statement)
+            .into_iter()
+            .map(|(name, _, _)| self.function(name))
+            .collect::<Vec<_>>();
+        self.declare(&names, 0, &mut lines);
+
+        if returns(statement) {
+            lines.push("program: {".into());
+            self.statement(statement, 1, false, &mut lines);
+            lines.push("}".into());
+        } else {
+            self.statement(statement, 0, false, &mut lines);
+        }
+
+        for name in variables.difference(&hidden) {
+            lines.push(format!(
+                "if ({} !== undefined) console.log(`{} = ${{show({})}}`);",
+                self.identifier(name),
+                name,
+                self.identifier(name)
+            ));
+        }
+
+        Ok(lines.join("\n") + "\n")
+    }
+}
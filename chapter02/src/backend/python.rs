@@ -0,0 +1,360 @@
+use super::{Backend, hoist, indent, intermediate, missing_return, returns, terminates, variables};
+use crate::{
+    desugar::Desugarer, environment::Environment, expression::Expression, statement::Statement,
+    target::Target, transpile_error::TranspileError,
+};
+
+const PRELUDE: &str = r#"MIN, MAX = -(2 ** 63), 2 ** 63 - 1
+
+
+def checked(value):
+    if not MIN <= value <= MAX:
+        raise OverflowError("arithmetic overflow")
+    return value
+
+
+def divide(left, right):
+    if right == 0:
+        raise ZeroDivisionError("division by zero")
+    quotient = abs(left) // abs(right)
+    return checked(quotient if (left < 0) == (right < 0) else -quotient)
+
+
+def index(items, position):
+    if not 0 <= position < len(items):
+        raise IndexError("index out of bounds")
+    return items[position]
+
+
+def show(value):
+    if isinstance(value, bool):
+        return str(value).lower()
+    if isinstance(value, str):
+        escaped = value.replace("\\", "\\\\").replace('"', '\\"')
+        return '"' + escaped.replace("\n", "\\n").replace("\t", "\\t") + '"'
+    if isinstance(value, list):
+        return "[" + ", ".join(show(item) for item in value) + "]"
+    return str(value)
+
+
+def write(value):
+    print(value if isinstance(value, str) else show(value))
+
+
+class Halt(Exception):
+    pass
+
+"#;
+
+const RESERVED: [&str; 52] = [
+    "False",
+    "Halt",
+    "MAX",
+    "MIN",
+    "None",
+    "RuntimeError",
+    "True",
+    "abs",
+    "and",
+    "as",
+    "assert",
+    "async",
+    "await",
+    "break",
+    "checked",
+    "class",
+    "continue",
+    "def",
+    "del",
+    "divide",
+    "elif",
+    "else",
+    "except",
+    "finally",
+    "for",
+    "from",
+    "global",
+    "if",
+    "import",
+    "in",
+    "index",
+    "input",
+    "is",
+    "isinstance",
+    "lambda",
+    "len",
+    "list",
+    "match",
+    "nonlocal",
+    "not",
+    "or",
+    "pass",
+    "print",
+    "raise",
+    "return",
+    "show",
+    "str",
+    "try",
+    "while",
+    "with",
+    "write",
+    "yield",
+];
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PythonBackend;
+
+impl PythonBackend {
+    fn identifier(&self, name: &str) -> String {
+        if RESERVED.contains(&name) || name.starts_with("function_") {
+            format!("{}_", name)
+        } else {
+            name.into()
+        }
+    }
+
+    fn function(&self, name: &str) -> String {
+        format!("function_{}", name)
+    }
+
+    fn binary(&self, left: &Expression, operator: &str, right: &Expression) -> String {
+        format!(
+            "{} {} {}",
+            self.operand(left),
+            operator,
+            self.operand(right)
+        )
+    }
+
+    fn block(&self, statement: &Statement, depth: usize, nested: bool, lines: &mut Vec<String>) {
+        let length = lines.len();
+
+        self.statement(statement, depth, nested, lines);
+
+        if lines.len() == length {
+            lines.push(format!("{}pass", indent(depth)));
+        }
+    }
+
+    fn statement(
+        &self,
+        statement: &Statement,
+        depth: usize,
+        nested: bool,
+        lines: &mut Vec<String>,
+    ) {
+        match statement {
+            Statement::Expression(expression) => {
+                lines.push(format!("{}{}", indent(depth), self.expression(expression)))
+            }
+            Statement::Assign(name, expression) => lines.push(format!(
+                "{}{} = {}",
+                indent(depth),
+                self.identifier(name),
+                self.expression(expression)
+            )),
+            Statement::If(condition, consequence, alternative) => {
+                lines.push(format!(
+                    "{}if {}:",
+                    indent(depth),
+                    self.expression(condition)
+                ));
+                self.block(consequence, depth + 1, nested, lines);
+
+                if **alternative != Statement::Nothing {
+                    lines.push(format!("{}else:", indent(depth)));
+                    self.block(alternative, depth + 1, nested, lines);
+                }
+            }
+            Statement::Sequence(_, _) => {
+                for statement in statement.spine() {
+                    self.statement(statement, depth, nested, lines);
+                }
+            }
+            Statement::While(condition, body) => {
+                lines.push(format!(
+                    "{}while {}:",
+                    indent(depth),
+                    self.expression(condition)
+                ));
+                self.block(body, depth + 1, nested, lines);
+            }
+            Statement::For(_, _, _, _) => {
+                unreachable!("for loops are desugared before translation")
+            }
+            Statement::Iteration(_, _) => {
+                unreachable!("iterations are rejected before translation")
+            }
+            Statement::Let(_, _, _) => {
+                unreachable!("let statements are hoisted before translation")
+            }
+            Statement::Function(name, parameters, body) => {
+                lines.push(format!(
+                    "{}def {}({}):",
+                    indent(depth),
+                    self.function(name),
+                    parameters
+                        .iter()
+                        .map(|parameter| self.identifier(parameter))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+                self.statement(body, depth + 1, true, lines);
+
+                if !terminates(body) {
+                    lines.push(format!(
+                        "{}raise RuntimeError(\"{}\")",
+                        indent(depth + 1),
+                        missing_return(name)
+                    ));
+                }
+            }
+            Statement::Return(expression) if nested => lines.push(format!(
+                "{}return {}",
+                indent(depth),
+                self.expression(expression)
+            )),
+            Statement::Return(expression) => {
+                lines.push(format!("{}{}", indent(depth), self.expression(expression)));
+                lines.push(format!("{}raise Halt", indent(depth)));
+            }
+            Statement::Print(expression) => lines.push(format!(
+                "{}write({})",
+                indent(depth),
+                self.expression(expression)
+            )),
+            Statement::Break => lines.push(format!("{}break", indent(depth))),
+            Statement::Continue => lines.push(format!("{}continue", indent(depth))),
+            Statement::Nothing => {}
+        }
+    }
+}
+
+impl Backend for PythonBackend {
+    fn operand(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Not(_)
+            | Expression::And(_, _)
+            | Expression::Or(_, _)
+            | Expression::Equal(_, _)
+            | Expression::NotEqual(_, _)
+            | Expression::LessThan(_, _)
+            | Expression::LessThanOrEqual(_, _)
+            | Expression::GreaterThan(_, _)
+            | Expression::GreaterThanOrEqual(_, _) => format!("({})", self.expression(expression)),
+            _ => self.expression(expression),
+        }
+    }
+
+    fn expression(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Number(value) => value.to_string(),
+            Expression::Boolean(true) => "True".into(),
+            Expression::Boolean(false) => "False".into(),
+            Expression::String(_) => expression.to_string(),
+            Expression::List(elements) => format!(
+                "[{}]",
+                elements
+                    .iter()
+                    .map(|element| self.expression(element))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Expression::Variable(name) => self.identifier(name),
+            Expression::Add(left, right) => format!("checked({})", self.binary(left, "+", right)),
+            Expression::Subtract(left, right) => {
+                format!("checked({})", self.binary(left, "-", right))
+            }
+            Expression::Multiply(left, right) => {
+                format!("checked({})", self.binary(left, "*", right))
+            }
+            Expression::Divide(left, right) => format!(
+                "divide({}, {})",
+                self.expression(left),
+                self.expression(right)
+            ),
+            Expression::Negate(expression) => format!("checked(-{})", self.operand(expression)),
+            Expression::Not(expression) => format!("not {}", self.operand(expression)),
+            Expression::And(left, right) => self.binary(left, "and", right),
+            Expression::Or(left, right) => self.binary(left, "or", right),
+            Expression::Equal(left, right) => self.binary(left, "==", right),
+            Expression::NotEqual(left, right) => self.binary(left, "!=", right),
+            Expression::LessThan(left, right) => self.binary(left, "<", right),
+            Expression::LessThanOrEqual(left, right) => self.binary(left, "<=", right),
+            Expression::GreaterThan(left, right) => self.binary(left, ">", right),
+            Expression::GreaterThanOrEqual(left, right) => self.binary(left, ">=", right),
+            Expression::Concatenate(left, right) => self.binary(left, "+", right),
+            Expression::Length(expression) => format!("len({})", self.expression(expression)),
+            Expression::Index(list, index) => format!(
+                "index({}, {})",
+                self.expression(list),
+                self.expression(index)
+            ),
+            Expression::Append(list, element) => {
+                format!("{} + [{}]", self.operand(list), self.expression(element))
+            }
+            Expression::Input => "input()".into(),
+            Expression::Call(name, arguments) => format!(
+                "{}({})",
+                self.function(name),
+                arguments
+                    .iter()
+                    .map(|argument| self.expression(argument))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Expression::Invocation(_, _, _) => {
+                unreachable!("invocations are rejected before translation")
+            }
+        }
+    }
+
+    fn program(
+        &self,
+        statement: &Statement,
+        environment: &Environment,
+    ) -> Result<String, TranspileError> {
+        if let Some(statement) = intermediate(statement) {
+            return Err(TranspileError::Intermediate {
+                target: Target::Python,
+                statement: Box::new(statement.clone()),
+            });
+        }
+
+        let (statement, hidden) = hoist(statement, environment);
+        let statement = &statement.desugar();
+        let variables = variables(statement, environment);
+        let mut lines = vec![PRELUDE.to_string()];
+
+        for name in &variables {
+            match environment.get(name) {
+                Ok(value) => lines.push(format!(
+                    "{} = {}",
+                    self.identifier(name),
+                    self.expression(&value)
+                )),
+                Err(_) => lines.push(format!("{} = None", self.identifier(name))),
+            }
+        }
+
+        if returns(statement) {
+            lines.push("try:".into());
+            self.block(statement, 1, false, &mut lines);
+            lines.push("except Halt:".into());
+            lines.push(format!("{}pass", indent(1)));
+        } else {
+            self.statement(statement, 0, false, &mut lines);
+        }
+
+        for name in variables.difference(&hidden) {
+            lines.push(format!(
+                "if {} is not None: print(f\"{} = {{show({})}}\")",
+                self.identifier(name),
+                name,
+                self.identifier(name)
+            ));
+        }
+
+        Ok(lines.join("\n") + "\n")
+    }
+}
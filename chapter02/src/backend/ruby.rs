@@ -0,0 +1,25 @@
+use super::Backend;
+use crate::{
+    environment::Environment,
+    expression::Expression,
+    statement::Statement,
+    transpile_error::TranspileError,
+    transpiler::{Transpiler, transpile_program},
+};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RubyBackend;
+
+impl Backend for RubyBackend {
+    fn expression(&self, expression: &Expression) -> String {
+        expression.transpile()
+    }
+
+    fn program(
+        &self,
+        statement: &Statement,
+        environment: &Environment,
+    ) -> Result<String, TranspileError> {
+        Ok(transpile_program(statement, environment))
+    }
+}
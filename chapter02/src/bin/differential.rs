@@ -0,0 +1,58 @@
+use anyhow::{Result, bail};
+use chapter02::differential::{property::Property, semantics::Semantics};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: differential [--seed <number>] [--cases <number>]";
+
+fn main() -> Result<ExitCode> {
+    let (seed, cases) = match parse_arguments(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("differential: {}\n{}", error, USAGE);
+            return Ok(ExitCode::from(2));
+        }
+    };
+
+    let semantics = Semantics::available();
+    let names = semantics
+        .iter()
+        .map(|semantics| semantics.to_string())
+        .collect::<Vec<_>>();
+
+    println!(
+        "checking {} programs from seed {} across {}",
+        cases,
+        seed,
+        names.join(", ")
+    );
+
+    match Property::new(semantics)
+        .with_seed(seed)
+        .with_cases(cases)
+        .check()?
+    {
+        Some(counterexample) => {
+            println!("{}", counterexample);
+            Ok(ExitCode::FAILURE)
+        }
+        None => {
+            println!("all semantics agree");
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn parse_arguments(mut arguments: impl Iterator<Item = String>) -> Result<(u64, usize)> {
+    let mut seed = 0;
+    let mut cases = 100;
+
+    while let Some(argument) = arguments.next() {
+        match (argument.as_str(), arguments.next()) {
+            ("--seed", Some(value)) => seed = value.parse()?,
+            ("--cases", Some(value)) => cases = value.parse()?,
+            (option, _) => bail!("unexpected argument '{}'", option),
+        }
+    }
+
+    Ok((seed, cases))
+}
@@ -0,0 +1,90 @@
+use anyhow::{Context, Result, bail};
+use chapter02::generator::{Generator, operator::Operator};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: generate [--seed <number>] [--count <number>] [--depth <number>] [--expression-depth <number>]
+                [--variables <number>] [--functions <number>] [--iterations <number>] [--weight <operator>=<number>]...";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("generate: {:#}\n{}", error, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(mut arguments: impl Iterator<Item = String>) -> Result<()> {
+    let mut seed = 0;
+    let mut count = 1;
+    let mut configure: Vec<Box<dyn Fn(Generator) -> Generator>> = Vec::new();
+
+    while let Some(argument) = arguments.next() {
+        let value = arguments
+            .next()
+            .with_context(|| format!("{} expects a value", argument))?;
+
+        match argument.as_str() {
+            "--seed" => seed = value.parse()?,
+            "--count" => count = value.parse()?,
+            "--depth" => {
+                let depth = value.parse()?;
+                configure.push(Box::new(move |generator| generator.with_depth(depth)));
+            }
+            "--expression-depth" => {
+                let depth = value.parse()?;
+                configure.push(Box::new(move |generator| {
+                    generator.with_expression_depth(depth)
+                }));
+            }
+            "--variables" => {
+                let variables = value.parse()?;
+                configure.push(Box::new(move |generator| {
+                    generator.with_variables(variables)
+                }));
+            }
+            "--functions" => {
+                let functions = value.parse()?;
+                configure.push(Box::new(move |generator| {
+                    generator.with_functions(functions)
+                }));
+            }
+            "--iterations" => {
+                let iterations = value.parse()?;
+                configure.push(Box::new(move |generator| {
+                    generator.with_iterations(iterations)
+                }));
+            }
+            "--weight" => {
+                let Some((name, weight)) = value.split_once('=') else {
+                    bail!("expected <operator>=<number> but got '{}'", value);
+                };
+                let Some(operator) = Operator::all()
+                    .into_iter()
+                    .find(|operator| operator.to_string() == name)
+                else {
+                    bail!("unknown operator '{}'", name);
+                };
+                let weight = weight.parse()?;
+                configure.push(Box::new(move |generator| {
+                    generator.with_weight(operator, weight)
+                }));
+            }
+            _ => bail!("unexpected argument '{}'", argument),
+        }
+    }
+
+    for case in 0..count {
+        let generator = configure
+            .iter()
+            .fold(Generator::new(seed + case), |generator, configure| {
+                configure(generator)
+            });
+
+        println!("{}", generator.clone().program());
+    }
+
+    Ok(())
+}
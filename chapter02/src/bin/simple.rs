@@ -0,0 +1,227 @@
+use anyhow::{Context, Result, anyhow, bail};
+use chapter02::{
+    abstract_interpretation::{analysis::Analysis, interval::Interval},
+    environment::Environment,
+    evaluator::Evaluator,
+    expression::Expression,
+    machine::Machine,
+    optimizer::Optimizer,
+    parser::{parse_expression, parse_statement},
+    statement::Statement,
+    target::Target,
+    type_checker::TypeChecker,
+    type_context::TypeContext,
+};
+use std::{fs, panic, process::ExitCode, thread};
+
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+const USAGE: &str = "\
+usage: simple [--small-step | --big-step | --transpile <target> | --check | --optimize] [--set name=value]... <file>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    SmallStep,
+    BigStep,
+    Transpile(Target),
+    Check,
+    Optimize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Options {
+    action: Action,
+    bindings: Vec<(String, Expression)>,
+    path: String,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_arguments(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("simple: {:#}\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let worker = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || run(&options));
+    let result = match worker {
+        Ok(worker) => worker
+            .join()
+            .unwrap_or_else(|payload| panic::resume_unwind(payload)),
+        Err(error) => Err(error.into()),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("simple: {:#}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_arguments(mut arguments: impl Iterator<Item = String>) -> Result<Options> {
+    let mut action = None;
+    let mut bindings = Vec::new();
+    let mut path = None;
+
+    while let Some(argument) = arguments.next() {
+        let selected = match argument.as_str() {
+            "--small-step" => Action::SmallStep,
+            "--big-step" => Action::BigStep,
+            "--check" => Action::Check,
+            "--optimize" => Action::Optimize,
+            "--transpile" => match arguments.next() {
+                Some(target) => Action::Transpile(target.parse()?),
+                None => bail!("--transpile expects a target"),
+            },
+            "--set" => {
+                match arguments.next() {
+                    Some(binding) => bindings.push(parse_binding(&binding)?),
+                    None => bail!("--set expects name=value"),
+                }
+                continue;
+            }
+            flag if flag.starts_with("--") => bail!("unknown option '{}'", flag),
+            _ => {
+                if path.replace(argument).is_some() {
+                    bail!("expected a single source file");
+                }
+                continue;
+            }
+        };
+
+        if action.replace(selected).is_some() {
+            bail!(
+                "choose only one of --small-step, --big-step, --transpile, --check and --optimize"
+            );
+        }
+    }
+
+    match path {
+        Some(path) => Ok(Options {
+            action: action.unwrap_or(Action::BigStep),
+            bindings,
+            path,
+        }),
+        None => bail!("missing source file"),
+    }
+}
+
+fn parse_binding(binding: &str) -> Result<(String, Expression)> {
+    let Some((name, source)) = binding.split_once('=') else {
+        bail!("expected name=value but got '{}'", binding);
+    };
+
+    let Expression::Variable(name) = parse_expression(name)? else {
+        bail!("'{}' is not a variable name", name);
+    };
+
+    let value = parse_expression(source)
+        .with_context(|| format!("invalid value for '{}'", name))?
+        .evaluate(&mut Environment::new())
+        .with_context(|| format!("invalid value for '{}'", name))?;
+
+    Ok((name, value))
+}
+
+fn run(options: &Options) -> Result<bool> {
+    let source = fs::read_to_string(&options.path)
+        .with_context(|| format!("cannot read '{}'", options.path))?;
+    let statement =
+        parse_statement(&source).map_err(|error| anyhow!("{}:{}", options.path, error))?;
+
+    let mut environment = Environment::new();
+
+    for (name, value) in &options.bindings {
+        environment.insert(name, value.clone());
+    }
+
+    match options.action {
+        Action::SmallStep => {
+            let mut machine = Machine::new(statement, environment);
+
+            Ok(report(machine.run()))
+        }
+        Action::BigStep => {
+            let result = statement.evaluate(&mut environment);
+
+            if result.is_ok() {
+                println!("{}", environment);
+            }
+
+            Ok(report(result))
+        }
+        Action::Transpile(target) => match target.backend().program(&statement, &environment) {
+            Ok(program) => {
+                print!("{}", program);
+
+                Ok(true)
+            }
+            Err(error) => {
+                eprintln!("{}: {}", options.path, error);
+
+                Ok(false)
+            }
+        },
+        Action::Check => Ok(check(&options.path, &statement, &environment)),
+        Action::Optimize => {
+            if !type_check(&options.path, &statement, &environment) {
+                return Ok(false);
+            }
+
+            let (optimized, rewrites) = statement.optimize();
+
+            println!("{}", optimized);
+            eprintln!("{} rewrites", rewrites);
+
+            Ok(true)
+        }
+    }
+}
+
+fn report<T>(result: std::result::Result<T, impl std::error::Error>) -> bool {
+    match result {
+        Ok(_) => true,
+        Err(error) => {
+            eprintln!("runtime error: {}", error);
+            false
+        }
+    }
+}
+
+fn type_check(path: &str, statement: &Statement, environment: &Environment) -> bool {
+    if let Err(errors) = statement.type_check(&mut TypeContext::from(environment)) {
+        for error in errors {
+            eprintln!("{}: type error: {}", path, error);
+        }
+
+        return false;
+    }
+
+    true
+}
+
+fn check(path: &str, statement: &Statement, environment: &Environment) -> bool {
+    if !type_check(path, statement, environment) {
+        return false;
+    }
+
+    let analysis = Analysis::<Interval>::summarize(statement, environment.into());
+
+    for expression in analysis.division_hazards() {
+        eprintln!("{}: warning: '{}' may divide by zero", path, expression);
+    }
+
+    for expression in analysis.overflow_hazards() {
+        eprintln!("{}: warning: '{}' may overflow", path, expression);
+    }
+
+    println!("{}: ok", path);
+
+    true
+}
@@ -0,0 +1,5 @@
+pub mod chunk;
+pub mod compiler;
+pub mod instruction;
+pub mod procedure;
+pub mod virtual_machine;
@@ -0,0 +1,152 @@
+use super::{compiler::Compiler, instruction::Instruction, procedure::Procedure};
+use crate::{expression::Expression, statement::Statement};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result},
+    ops::Range,
+    sync::Arc,
+};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Chunk {
+    instructions: Vec<Instruction>,
+    names: Vec<String>,
+    scoped: Vec<bool>,
+    slots: HashMap<String, usize>,
+    scopes: Vec<(String, usize)>,
+    origins: Vec<Option<Expression>>,
+    procedures: Vec<Arc<Procedure>>,
+}
+
+impl Chunk {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn compile(statement: &Statement) -> Self {
+        let mut chunk = Self::new();
+        statement.compile(&mut chunk);
+        chunk
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn procedures(&self) -> &[Arc<Procedure>] {
+        &self.procedures
+    }
+
+    pub fn origin(&self, offset: usize) -> Option<&Expression> {
+        self.origins.get(offset)?.as_ref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    pub fn emit(&mut self, instruction: Instruction) -> usize {
+        self.instructions.push(instruction);
+        self.origins.push(None);
+        self.instructions.len() - 1
+    }
+
+    pub fn emit_from(&mut self, instruction: Instruction, origin: &Expression) -> usize {
+        let offset = self.emit(instruction);
+        self.origins[offset] = Some(origin.clone());
+        offset
+    }
+
+    pub fn procedure(&mut self, procedure: Procedure) -> usize {
+        self.procedures.push(Arc::new(procedure));
+        self.procedures.len() - 1
+    }
+
+    pub fn patch(&mut self, offset: usize, target: usize) {
+        match &mut self.instructions[offset] {
+            Instruction::Jump(destination) | Instruction::JumpIfFalse(destination) => {
+                *destination = target
+            }
+            instruction => panic!("cannot patch '{}' at {:04}", instruction, offset),
+        }
+    }
+
+    pub fn resolve(&mut self, body: Range<usize>, continuation: usize, exit: usize) {
+        for instruction in &mut self.instructions[body] {
+            match instruction {
+                Instruction::Break => *instruction = Instruction::Jump(exit),
+                Instruction::Continue => *instruction = Instruction::Jump(continuation),
+                _ => {}
+            }
+        }
+    }
+
+    pub fn is_scoped(&self, slot: usize) -> bool {
+        self.scoped[slot]
+    }
+
+    pub fn slot(&mut self, name: &str) -> usize {
+        if let Some((_, slot)) = self.scopes.iter().rev().find(|(scope, _)| scope == name) {
+            return *slot;
+        }
+
+        match self.slots.get(name) {
+            Some(slot) => *slot,
+            None => {
+                self.names.push(name.into());
+                self.scoped.push(false);
+                self.slots.insert(name.into(), self.names.len() - 1);
+                self.names.len() - 1
+            }
+        }
+    }
+
+    pub fn enter(&mut self, name: &str) -> usize {
+        self.names.push(name.into());
+        self.scoped.push(true);
+        self.scopes.push((name.into(), self.names.len() - 1));
+        self.names.len() - 1
+    }
+
+    pub fn leave(&mut self) {
+        self.scopes.pop();
+    }
+
+    pub fn disassemble(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for Chunk {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for (offset, instruction) in self.instructions.iter().enumerate() {
+            match instruction {
+                Instruction::Load(slot) | Instruction::Store(slot) => {
+                    writeln!(f, "{:04} {} ({})", offset, instruction, self.names[*slot])?
+                }
+                Instruction::Define(index) | Instruction::Invoke(index) => writeln!(
+                    f,
+                    "{:04} {} ({})",
+                    offset,
+                    instruction,
+                    self.procedures[*index].name()
+                )?,
+                _ => writeln!(f, "{:04} {}", offset, instruction)?,
+            }
+        }
+
+        for procedure in &self.procedures {
+            write!(f, "\n{}", procedure)?;
+        }
+
+        Ok(())
+    }
+}
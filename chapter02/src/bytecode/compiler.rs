@@ -0,0 +1,237 @@
+use super::{chunk::Chunk, instruction::Instruction, procedure::Procedure};
+use crate::{expression::Expression, function::Function, statement::Statement};
+use std::sync::Arc;
+
+pub trait Compiler {
+    fn compile(&self, chunk: &mut Chunk);
+}
+
+impl Compiler for Expression {
+    fn compile(&self, chunk: &mut Chunk) {
+        match self {
+            Self::Number(_) | Self::Boolean(_) | Self::String(_) => {
+                chunk.emit(Instruction::Push(self.clone()));
+            }
+            Self::List(elements) => {
+                for element in elements {
+                    element.compile(chunk);
+                }
+
+                chunk.emit(Instruction::List(elements.len()));
+            }
+            Self::Variable(name) => {
+                let slot = chunk.slot(name);
+                chunk.emit(Instruction::Load(slot));
+            }
+            Self::Add(left, right) => binary(chunk, self, left, right, Instruction::Add),
+            Self::Subtract(left, right) => binary(chunk, self, left, right, Instruction::Subtract),
+            Self::Multiply(left, right) => binary(chunk, self, left, right, Instruction::Multiply),
+            Self::Divide(left, right) => binary(chunk, self, left, right, Instruction::Divide),
+            Self::Negate(expression) => {
+                expression.compile(chunk);
+                chunk.emit_from(Instruction::Negate, self);
+            }
+            Self::Not(expression) => {
+                expression.compile(chunk);
+                chunk.emit_from(Instruction::Not, self);
+            }
+            Self::And(left, right) => logic(chunk, self, left, right, false),
+            Self::Or(left, right) => logic(chunk, self, left, right, true),
+            Self::Equal(left, right) => binary(chunk, self, left, right, Instruction::Equal),
+            Self::NotEqual(left, right) => binary(chunk, self, left, right, Instruction::NotEqual),
+            Self::LessThan(left, right) => binary(chunk, self, left, right, Instruction::LessThan),
+            Self::LessThanOrEqual(left, right) => {
+                binary(chunk, self, left, right, Instruction::LessThanOrEqual)
+            }
+            Self::GreaterThan(left, right) => {
+                binary(chunk, self, left, right, Instruction::GreaterThan)
+            }
+            Self::GreaterThanOrEqual(left, right) => {
+                binary(chunk, self, left, right, Instruction::GreaterThanOrEqual)
+            }
+            Self::Concatenate(left, right) => {
+                binary(chunk, self, left, right, Instruction::Concatenate)
+            }
+            Self::Length(expression) => {
+                expression.compile(chunk);
+                chunk.emit_from(Instruction::Length, self);
+            }
+            Self::Index(list, index) => binary(chunk, self, list, index, Instruction::Index),
+            Self::Append(list, element) => binary(chunk, self, list, element, Instruction::Append),
+            Self::Input => {
+                chunk.emit(Instruction::Input);
+            }
+            Self::Call(name, arguments) => {
+                for argument in arguments {
+                    argument.compile(chunk);
+                }
+
+                chunk.emit_from(Instruction::Call(name.clone(), arguments.len()), self);
+            }
+            Self::Invocation(name, body, environment) => {
+                let body = environment
+                    .variables()
+                    .fold(*body.clone(), |body, (name, value)| {
+                        Statement::Sequence(
+                            Arc::new(Statement::Assign(name.into(), value.clone())),
+                            Arc::new(body),
+                        )
+                    });
+                let function = Arc::new(Function::new(Vec::new(), body));
+                let index = chunk.procedure(Procedure::compile(name, function));
+                chunk.emit_from(Instruction::Invoke(index), self);
+            }
+        }
+    }
+}
+
+impl Compiler for Statement {
+    fn compile(&self, chunk: &mut Chunk) {
+        let mut statements = vec![self];
+
+        while let Some(statement) = statements.pop() {
+            match statement {
+                Self::Expression(expression) => {
+                    expression.compile(chunk);
+                    chunk.emit(Instruction::Pop);
+                }
+                Self::Assign(name, expression) => {
+                    expression.compile(chunk);
+                    let slot = chunk.slot(name);
+                    chunk.emit(Instruction::Store(slot));
+                }
+                Self::If(condition, consequence, alternative) => {
+                    condition.compile(chunk);
+                    let skip = chunk.emit_from(Instruction::JumpIfFalse(0), condition);
+                    consequence.compile(chunk);
+
+                    if **alternative == Self::Nothing {
+                        chunk.patch(skip, chunk.len());
+                    } else {
+                        let exit = chunk.emit(Instruction::Jump(0));
+                        chunk.patch(skip, chunk.len());
+                        alternative.compile(chunk);
+                        chunk.patch(exit, chunk.len());
+                    }
+                }
+                Self::Sequence(first, second) => {
+                    statements.push(second);
+                    statements.push(first);
+                }
+                Self::While(condition, body) => {
+                    let start = chunk.emit(Instruction::Loop);
+                    condition.compile(chunk);
+                    let exit = chunk.emit_from(Instruction::JumpIfFalse(0), condition);
+                    let from = chunk.len();
+                    body.compile(chunk);
+                    let to = chunk.len();
+                    chunk.emit(Instruction::Jump(start));
+                    chunk.patch(exit, chunk.len());
+                    chunk.resolve(from..to, start, chunk.len());
+                }
+                Self::For(name, start, end, body) => {
+                    let counter = Box::new(Expression::Variable(name.clone()));
+                    let condition =
+                        Expression::LessThanOrEqual(counter.clone(), Box::new(end.clone()));
+                    let increment = Self::Assign(
+                        name.clone(),
+                        Expression::Add(counter, Box::new(Expression::Number(1))),
+                    );
+
+                    Self::Assign(name.clone(), start.clone()).compile(chunk);
+                    let start = chunk.emit(Instruction::Loop);
+                    condition.compile(chunk);
+                    let exit = chunk.emit_from(Instruction::JumpIfFalse(0), &condition);
+                    let from = chunk.len();
+                    body.compile(chunk);
+                    let to = chunk.len();
+                    increment.compile(chunk);
+                    chunk.emit(Instruction::Jump(start));
+                    chunk.patch(exit, chunk.len());
+                    chunk.resolve(from..to, to, chunk.len());
+                }
+                Self::Iteration(body, next) => {
+                    let from = chunk.len();
+                    body.compile(chunk);
+                    let to = chunk.len();
+                    next.compile(chunk);
+                    chunk.resolve(from..to, to, chunk.len());
+                }
+                Self::Let(name, expression, body) => {
+                    expression.compile(chunk);
+                    let slot = chunk.enter(name);
+                    chunk.emit(Instruction::Store(slot));
+                    body.compile(chunk);
+                    chunk.leave();
+                }
+                Self::Function(name, parameters, body) => {
+                    let function = Arc::new(Function::new(parameters.clone(), *body.clone()));
+                    let index = chunk.procedure(Procedure::compile(name, function));
+                    chunk.emit(Instruction::Define(index));
+                }
+                Self::Return(expression) => {
+                    expression.compile(chunk);
+                    chunk.emit(Instruction::Return);
+                }
+                Self::Print(expression) => {
+                    expression.compile(chunk);
+                    chunk.emit(Instruction::Print);
+                }
+                Self::Break => {
+                    chunk.emit(Instruction::Break);
+                }
+                Self::Continue => {
+                    chunk.emit(Instruction::Continue);
+                }
+                Self::Nothing => {}
+            }
+        }
+    }
+}
+
+fn binary(
+    chunk: &mut Chunk,
+    expression: &Expression,
+    left: &Expression,
+    right: &Expression,
+    instruction: Instruction,
+) {
+    left.compile(chunk);
+    right.compile(chunk);
+    chunk.emit_from(instruction, expression);
+}
+
+fn logic(
+    chunk: &mut Chunk,
+    expression: &Expression,
+    left: &Expression,
+    right: &Expression,
+    decisive: bool,
+) {
+    let mut exits = Vec::new();
+
+    left.compile(chunk);
+    let short = chunk.emit_from(Instruction::JumpIfFalse(0), expression);
+
+    if decisive {
+        chunk.emit(Instruction::Push(Expression::Boolean(true)));
+        exits.push(chunk.emit(Instruction::Jump(0)));
+        chunk.patch(short, chunk.len());
+    }
+
+    right.compile(chunk);
+    let other = chunk.emit_from(Instruction::JumpIfFalse(0), expression);
+    chunk.emit(Instruction::Push(Expression::Boolean(true)));
+    exits.push(chunk.emit(Instruction::Jump(0)));
+
+    if !decisive {
+        chunk.patch(short, chunk.len());
+    }
+
+    chunk.patch(other, chunk.len());
+    chunk.emit(Instruction::Push(Expression::Boolean(false)));
+
+    for exit in exits {
+        chunk.patch(exit, chunk.len());
+    }
+}
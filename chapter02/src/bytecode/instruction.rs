@@ -0,0 +1,77 @@
+use crate::expression::Expression;
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction {
+    Push(Expression),
+    Load(usize),
+    Store(usize),
+    Pop,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Negate,
+    Not,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    List(usize),
+    Concatenate,
+    Length,
+    Index,
+    Append,
+    Input,
+    Print,
+    Loop,
+    Jump(usize),
+    JumpIfFalse(usize),
+    Define(usize),
+    Call(String, usize),
+    Invoke(usize),
+    Return,
+    Break,
+    Continue,
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Push(value) => write!(f, "push {}", value),
+            Self::Load(slot) => write!(f, "load {}", slot),
+            Self::Store(slot) => write!(f, "store {}", slot),
+            Self::Pop => write!(f, "pop"),
+            Self::Add => write!(f, "add"),
+            Self::Subtract => write!(f, "subtract"),
+            Self::Multiply => write!(f, "multiply"),
+            Self::Divide => write!(f, "divide"),
+            Self::Negate => write!(f, "negate"),
+            Self::Not => write!(f, "not"),
+            Self::Equal => write!(f, "equal"),
+            Self::NotEqual => write!(f, "not_equal"),
+            Self::LessThan => write!(f, "less_than"),
+            Self::LessThanOrEqual => write!(f, "less_than_or_equal"),
+            Self::GreaterThan => write!(f, "greater_than"),
+            Self::GreaterThanOrEqual => write!(f, "greater_than_or_equal"),
+            Self::List(length) => write!(f, "list {}", length),
+            Self::Concatenate => write!(f, "concatenate"),
+            Self::Length => write!(f, "length"),
+            Self::Index => write!(f, "index"),
+            Self::Append => write!(f, "append"),
+            Self::Input => write!(f, "input"),
+            Self::Print => write!(f, "print"),
+            Self::Loop => write!(f, "loop"),
+            Self::Jump(target) => write!(f, "jump {:04}", target),
+            Self::JumpIfFalse(target) => write!(f, "jump_if_false {:04}", target),
+            Self::Define(index) => write!(f, "define {}", index),
+            Self::Call(name, arity) => write!(f, "call {} {}", name, arity),
+            Self::Invoke(index) => write!(f, "invoke {}", index),
+            Self::Return => write!(f, "return"),
+            Self::Break => write!(f, "break"),
+            Self::Continue => write!(f, "continue"),
+        }
+    }
+}
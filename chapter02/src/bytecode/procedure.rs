@@ -0,0 +1,59 @@
+use super::{chunk::Chunk, compiler::Compiler};
+use crate::function::Function;
+use std::{
+    fmt::{Display, Formatter, Result},
+    sync::Arc,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Procedure {
+    name: String,
+    function: Arc<Function>,
+    chunk: Chunk,
+}
+
+impl Procedure {
+    pub fn compile(name: &str, function: Arc<Function>) -> Self {
+        let mut chunk = Chunk::new();
+
+        for parameter in function.parameters() {
+            chunk.slot(parameter);
+        }
+
+        function.body().compile(&mut chunk);
+
+        Self {
+            name: name.into(),
+            function,
+            chunk,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn function(&self) -> &Arc<Function> {
+        &self.function
+    }
+
+    pub fn chunk(&self) -> &Chunk {
+        &self.chunk
+    }
+
+    pub fn arity(&self) -> usize {
+        self.function.parameters().len()
+    }
+}
+
+impl Display for Procedure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(
+            f,
+            "{}({}):",
+            self.name,
+            self.function.parameters().join(", ")
+        )?;
+        write!(f, "{}", self.chunk)
+    }
+}
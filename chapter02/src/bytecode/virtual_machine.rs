@@ -0,0 +1,383 @@
+use super::{chunk::Chunk, instruction::Instruction, procedure::Procedure};
+use crate::{
+    environment::Environment, expression::Expression, fuel::Fuel, runtime_error::RuntimeError,
+    statement::Statement,
+};
+use std::{collections::HashMap, sync::Arc};
+
+type Procedures = HashMap<String, Arc<Procedure>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualMachine {
+    chunk: Chunk,
+    fuel: Fuel,
+}
+
+impl VirtualMachine {
+    pub fn new(chunk: Chunk) -> Self {
+        Self::with_fuel(chunk, Fuel::default())
+    }
+
+    pub fn with_fuel(chunk: Chunk, fuel: Fuel) -> Self {
+        Self { chunk, fuel }
+    }
+
+    pub fn chunk(&self) -> &Chunk {
+        &self.chunk
+    }
+
+    pub fn run(&mut self, environment: &mut Environment) -> Result<(), RuntimeError> {
+        let mut slots = self
+            .chunk
+            .names()
+            .iter()
+            .enumerate()
+            .map(|(slot, name)| {
+                if self.chunk.is_scoped(slot) {
+                    None
+                } else {
+                    environment.get(name).ok()
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut procedures = Arc::new(
+            environment
+                .functions()
+                .map(|(name, function)| {
+                    let procedure = Procedure::compile(name, function.clone());
+                    (name.to_string(), Arc::new(procedure))
+                })
+                .collect::<Procedures>(),
+        );
+        let result = execute(
+            &self.chunk,
+            &mut self.fuel,
+            &mut slots,
+            &mut procedures,
+            environment,
+        );
+        store(&self.chunk, &slots, environment);
+
+        for (name, procedure) in procedures.iter() {
+            match environment.function(name) {
+                Ok(function) if Arc::ptr_eq(&function, procedure.function()) => {}
+                _ => environment.define(name, procedure.function().clone()),
+            }
+        }
+
+        result.map(|_| ())
+    }
+}
+
+struct Frame {
+    procedure: Option<Arc<Procedure>>,
+    counter: usize,
+    stack: Vec<Expression>,
+    slots: Vec<Option<Expression>>,
+    procedures: Arc<Procedures>,
+}
+
+impl Frame {
+    fn name(&self) -> &str {
+        self.procedure
+            .as_ref()
+            .map_or("", |procedure| procedure.name())
+    }
+}
+
+enum Transfer {
+    Call(Arc<Procedure>, Vec<Expression>),
+    Return(Option<Expression>),
+}
+
+fn execute(
+    chunk: &Chunk,
+    fuel: &mut Fuel,
+    slots: &mut Vec<Option<Expression>>,
+    procedures: &mut Arc<Procedures>,
+    environment: &Environment,
+) -> Result<Option<Expression>, RuntimeError> {
+    let isolated = environment.isolated();
+    let mut callers: Vec<Frame> = Vec::new();
+    let mut frame = Frame {
+        procedure: None,
+        counter: 0,
+        stack: Vec::new(),
+        slots: std::mem::take(slots),
+        procedures: procedures.clone(),
+    };
+
+    let result = loop {
+        let procedure = frame.procedure.clone();
+        let (code, environment) = match &procedure {
+            Some(procedure) => (procedure.chunk(), &isolated),
+            None => (chunk, environment),
+        };
+
+        match resume(code, &mut frame, fuel, environment) {
+            Ok(Transfer::Call(procedure, arguments)) => {
+                if let Err(error) =
+                    exhausted(fuel.enter(environment), code, &frame.slots, environment)
+                {
+                    break Err(error);
+                }
+
+                let mut local = arguments.into_iter().map(Some).collect::<Vec<_>>();
+                local.resize(procedure.chunk().names().len(), None);
+                let callee = Frame {
+                    procedure: Some(procedure),
+                    counter: 0,
+                    stack: Vec::new(),
+                    slots: local,
+                    procedures: frame.procedures.clone(),
+                };
+                callers.push(std::mem::replace(&mut frame, callee));
+            }
+            Ok(Transfer::Return(value)) => {
+                let Some(caller) = callers.pop() else {
+                    break Ok(value);
+                };
+                let callee = std::mem::replace(&mut frame, caller);
+                fuel.leave();
+
+                match value {
+                    Some(value) => frame.stack.push(value),
+                    None => break Err(RuntimeError::MissingReturn(callee.name().into())),
+                }
+            }
+            Err(error) => break Err(error),
+        }
+    };
+
+    let outermost = match callers.into_iter().next() {
+        Some(outermost) => outermost,
+        None => frame,
+    };
+    *slots = outermost.slots;
+    *procedures = outermost.procedures;
+
+    result
+}
+
+fn resume(
+    chunk: &Chunk,
+    frame: &mut Frame,
+    fuel: &mut Fuel,
+    environment: &Environment,
+) -> Result<Transfer, RuntimeError> {
+    let Frame {
+        counter,
+        stack,
+        slots,
+        procedures,
+        ..
+    } = frame;
+
+    while let Some(instruction) = chunk.instructions().get(*counter) {
+        let offset = *counter;
+        *counter += 1;
+
+        match instruction {
+            Instruction::Push(value) => stack.push(value.clone()),
+            Instruction::Load(slot) => match &slots[*slot] {
+                Some(value) => stack.push(value.clone()),
+                None => {
+                    return Err(RuntimeError::UnboundVariable(chunk.names()[*slot].clone()));
+                }
+            },
+            Instruction::Store(slot) => slots[*slot] = stack.pop(),
+            Instruction::Pop => {
+                stack.pop();
+            }
+            Instruction::Add => {
+                let origin = origin(chunk, offset);
+                let (left, right) = numbers(stack, origin)?;
+                let value = left
+                    .checked_add(right)
+                    .ok_or_else(|| RuntimeError::Overflow(origin.clone()))?;
+                stack.push(Expression::Number(value));
+            }
+            Instruction::Subtract => {
+                let origin = origin(chunk, offset);
+                let (left, right) = numbers(stack, origin)?;
+                let value = left
+                    .checked_sub(right)
+                    .ok_or_else(|| RuntimeError::Overflow(origin.clone()))?;
+                stack.push(Expression::Number(value));
+            }
+            Instruction::Multiply => {
+                let origin = origin(chunk, offset);
+                let (left, right) = numbers(stack, origin)?;
+                let value = left
+                    .checked_mul(right)
+                    .ok_or_else(|| RuntimeError::Overflow(origin.clone()))?;
+                stack.push(Expression::Number(value));
+            }
+            Instruction::Divide => {
+                let origin = origin(chunk, offset);
+                let (left, right) = numbers(stack, origin)?;
+
+                if right == 0 {
+                    return Err(RuntimeError::DivisionByZero(origin.clone()));
+                }
+
+                let value = left
+                    .checked_div(right)
+                    .ok_or_else(|| RuntimeError::Overflow(origin.clone()))?;
+                stack.push(Expression::Number(value));
+            }
+            Instruction::Negate => {
+                let origin = origin(chunk, offset);
+                let value = pop(stack)
+                    .unwrap_number(origin)?
+                    .checked_neg()
+                    .ok_or_else(|| RuntimeError::Overflow(origin.clone()))?;
+                stack.push(Expression::Number(value));
+            }
+            Instruction::Not => {
+                let value = pop(stack).unwrap_boolean(origin(chunk, offset))?;
+                stack.push(Expression::Boolean(!value));
+            }
+            Instruction::Equal => {
+                let right = pop(stack);
+                let left = pop(stack);
+                stack.push(Expression::Boolean(left == right));
+            }
+            Instruction::NotEqual => {
+                let right = pop(stack);
+                let left = pop(stack);
+                stack.push(Expression::Boolean(left != right));
+            }
+            Instruction::LessThan => {
+                let (left, right) = numbers(stack, origin(chunk, offset))?;
+                stack.push(Expression::Boolean(left < right));
+            }
+            Instruction::LessThanOrEqual => {
+                let (left, right) = numbers(stack, origin(chunk, offset))?;
+                stack.push(Expression::Boolean(left <= right));
+            }
+            Instruction::GreaterThan => {
+                let (left, right) = numbers(stack, origin(chunk, offset))?;
+                stack.push(Expression::Boolean(left > right));
+            }
+            Instruction::GreaterThanOrEqual => {
+                let (left, right) = numbers(stack, origin(chunk, offset))?;
+                stack.push(Expression::Boolean(left >= right));
+            }
+            Instruction::List(length) => {
+                let elements = stack.split_off(stack.len() - length);
+                stack.push(Expression::List(elements));
+            }
+            Instruction::Concatenate => {
+                let right = pop(stack);
+                let left = pop(stack);
+                stack.push(left.concatenate(&right, origin(chunk, offset))?);
+            }
+            Instruction::Length => {
+                let value = pop(stack).length(origin(chunk, offset))?;
+                stack.push(value);
+            }
+            Instruction::Index => {
+                let index = pop(stack);
+                let list = pop(stack);
+                stack.push(list.element(&index, origin(chunk, offset))?);
+            }
+            Instruction::Append => {
+                let element = pop(stack);
+                let list = pop(stack);
+                stack.push(list.append(&element, origin(chunk, offset))?);
+            }
+            Instruction::Input => stack.push(environment.input()?),
+            Instruction::Print => environment.print(&pop(stack)),
+            Instruction::Loop => exhausted(
+                fuel.burn_loop_iteration(environment),
+                chunk,
+                slots,
+                environment,
+            )?,
+            Instruction::Jump(target) => *counter = *target,
+            Instruction::JumpIfFalse(target) => {
+                if !pop(stack).unwrap_boolean(origin(chunk, offset))? {
+                    *counter = *target;
+                }
+            }
+            Instruction::Define(index) => {
+                let procedure = chunk.procedures()[*index].clone();
+                Arc::make_mut(procedures).insert(procedure.name().into(), procedure);
+            }
+            Instruction::Call(name, arity) => {
+                let procedure = match procedures.get(name) {
+                    Some(procedure) => procedure.clone(),
+                    None => return Err(RuntimeError::UndefinedFunction(name.clone())),
+                };
+
+                if procedure.arity() != *arity {
+                    return Err(RuntimeError::ArityMismatch {
+                        name: name.clone(),
+                        expected: procedure.arity(),
+                        actual: *arity,
+                    });
+                }
+
+                let arguments = stack.split_off(stack.len() - arity);
+                return Ok(Transfer::Call(procedure, arguments));
+            }
+            Instruction::Invoke(index) => {
+                return Ok(Transfer::Call(
+                    chunk.procedures()[*index].clone(),
+                    Vec::new(),
+                ));
+            }
+            Instruction::Return => return Ok(Transfer::Return(stack.pop())),
+            Instruction::Break => {
+                return Err(RuntimeError::OutsideLoop(Box::new(Statement::Break)));
+            }
+            Instruction::Continue => {
+                return Err(RuntimeError::OutsideLoop(Box::new(Statement::Continue)));
+            }
+        }
+    }
+
+    Ok(Transfer::Return(None))
+}
+
+fn origin(chunk: &Chunk, offset: usize) -> &Expression {
+    chunk.origin(offset).unwrap()
+}
+
+fn store(chunk: &Chunk, slots: &[Option<Expression>], environment: &mut Environment) {
+    for (slot, (name, value)) in chunk.names().iter().zip(slots).enumerate() {
+        if let Some(value) = value
+            && !chunk.is_scoped(slot)
+        {
+            environment.insert(name, value.clone());
+        }
+    }
+}
+
+fn exhausted(
+    result: Result<(), RuntimeError>,
+    chunk: &Chunk,
+    slots: &[Option<Expression>],
+    environment: &Environment,
+) -> Result<(), RuntimeError> {
+    result.map_err(|error| {
+        let mut environment = environment.clone();
+        store(chunk, slots, &mut environment);
+
+        match error {
+            RuntimeError::OutOfFuel { limit, .. } => RuntimeError::OutOfFuel { limit, environment },
+            error => error,
+        }
+    })
+}
+
+fn pop(stack: &mut Vec<Expression>) -> Expression {
+    stack.pop().unwrap()
+}
+
+fn numbers(stack: &mut Vec<Expression>, origin: &Expression) -> Result<(i64, i64), RuntimeError> {
+    let right = pop(stack);
+    let left = pop(stack);
+    Ok((left.unwrap_number(origin)?, right.unwrap_number(origin)?))
+}
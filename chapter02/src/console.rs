@@ -0,0 +1,53 @@
+use std::{collections::VecDeque, fmt::Debug, io::BufRead};
+
+pub trait Console: Debug + Send {
+    fn write(&mut self, line: &str);
+    fn read(&mut self) -> Option<String>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StandardConsole;
+
+impl Console for StandardConsole {
+    fn write(&mut self, line: &str) {
+        println!("{}", line);
+    }
+
+    fn read(&mut self) -> Option<String> {
+        let mut line = String::new();
+
+        match std::io::stdin().lock().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end_matches(['\n', '\r']).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScriptedConsole {
+    input: VecDeque<String>,
+    output: Vec<String>,
+}
+
+impl ScriptedConsole {
+    pub fn new(input: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            input: input.into_iter().map(Into::into).collect(),
+            output: Vec::new(),
+        }
+    }
+
+    pub fn output(&self) -> &[String] {
+        &self.output
+    }
+}
+
+impl Console for ScriptedConsole {
+    fn write(&mut self, line: &str) {
+        self.output.push(line.into());
+    }
+
+    fn read(&mut self) -> Option<String> {
+        self.input.pop_front()
+    }
+}
@@ -0,0 +1,495 @@
+use crate::{
+    environment::Environment, expression::Expression, fuel::Fuel, function::Function,
+    runtime_error::RuntimeError, statement::Statement,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::{Rc, Weak},
+    sync::Arc,
+};
+
+pub type Meaning<T> = Box<dyn Fn(&mut Environment, &mut Fuel) -> Result<T, RuntimeError>>;
+
+type Catalog = RefCell<HashMap<String, Vec<(Arc<Function>, Rc<Meaning<Statement>>)>>>;
+
+pub trait Denotation {
+    type Output;
+
+    fn denote(&self) -> Meaning<Self::Output>;
+}
+
+impl Denotation for Expression {
+    type Output = Expression;
+
+    fn denote(&self) -> Meaning<Expression> {
+        with_catalog(|catalog| value(self, catalog))
+    }
+}
+
+impl Denotation for Statement {
+    type Output = Statement;
+
+    fn denote(&self) -> Meaning<Statement> {
+        with_catalog(|catalog| completion(meaning(self, catalog)))
+    }
+}
+
+fn with_catalog<T: 'static>(denote: impl FnOnce(&Weak<Catalog>) -> Meaning<T>) -> Meaning<T> {
+    let catalog = Rc::new(Catalog::default());
+    let meaning = denote(&Rc::downgrade(&catalog));
+
+    Box::new(move |environment, fuel| {
+        let _catalog = &catalog;
+        meaning(environment, fuel)
+    })
+}
+
+fn completion(meaning: Meaning<Statement>) -> Meaning<Statement> {
+    Box::new(move |environment, fuel| match meaning(environment, fuel)? {
+        statement @ (Statement::Break | Statement::Continue) => {
+            Err(RuntimeError::OutsideLoop(Box::new(statement)))
+        }
+        statement => Ok(statement),
+    })
+}
+
+fn body(catalog: &Weak<Catalog>, name: &str, function: &Arc<Function>) -> Rc<Meaning<Statement>> {
+    let catalog = catalog
+        .upgrade()
+        .expect("meanings never outlive their catalog");
+
+    if let Some((_, body)) = catalog.borrow().get(name).and_then(|bodies| {
+        bodies
+            .iter()
+            .find(|(defined, _)| Arc::ptr_eq(defined, function))
+    }) {
+        return body.clone();
+    }
+
+    let body = Rc::new(completion(meaning(
+        function.body(),
+        &Rc::downgrade(&catalog),
+    )));
+    catalog
+        .borrow_mut()
+        .entry(name.into())
+        .or_default()
+        .push((function.clone(), body.clone()));
+
+    body
+}
+
+fn value(expression: &Expression, catalog: &Weak<Catalog>) -> Meaning<Expression> {
+    match expression {
+        Expression::Number(value) => {
+            let value = *value;
+            Box::new(move |_, _| Ok(Expression::Number(value)))
+        }
+        Expression::Boolean(value) => {
+            let value = *value;
+            Box::new(move |_, _| Ok(Expression::Boolean(value)))
+        }
+        Expression::String(value) => {
+            let value = value.clone();
+            Box::new(move |_, _| Ok(Expression::String(value.clone())))
+        }
+        Expression::List(elements) => {
+            let elements = elements
+                .iter()
+                .map(|element| value(element, catalog))
+                .collect::<Vec<_>>();
+            Box::new(move |environment, fuel| {
+                let elements = elements
+                    .iter()
+                    .map(|element| element(environment, fuel))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Expression::List(elements))
+            })
+        }
+        Expression::Variable(name) => {
+            let name = name.clone();
+            Box::new(move |environment, _| environment.get(&name))
+        }
+        Expression::Input => Box::new(|environment, _| environment.input()),
+        Expression::Add(left, right) => {
+            arithmetic(expression, left, right, i64::checked_add, catalog)
+        }
+        Expression::Subtract(left, right) => {
+            arithmetic(expression, left, right, i64::checked_sub, catalog)
+        }
+        Expression::Multiply(left, right) => {
+            arithmetic(expression, left, right, i64::checked_mul, catalog)
+        }
+        Expression::Divide(left, right) => {
+            let left = value(left, catalog);
+            let right = value(right, catalog);
+            let expression = expression.clone();
+            Box::new(move |environment, fuel| {
+                let dividend = left(environment, fuel)?.unwrap_number(&expression)?;
+
+                match right(environment, fuel)?.unwrap_number(&expression)? {
+                    0 => Err(RuntimeError::DivisionByZero(expression.clone())),
+                    divisor => match dividend.checked_div(divisor) {
+                        Some(value) => Ok(Expression::Number(value)),
+                        None => Err(RuntimeError::Overflow(expression.clone())),
+                    },
+                }
+            })
+        }
+        Expression::Negate(operand) => {
+            let operand = value(operand, catalog);
+            let expression = expression.clone();
+            Box::new(move |environment, fuel| {
+                match operand(environment, fuel)?
+                    .unwrap_number(&expression)?
+                    .checked_neg()
+                {
+                    Some(value) => Ok(Expression::Number(value)),
+                    None => Err(RuntimeError::Overflow(expression.clone())),
+                }
+            })
+        }
+        Expression::Not(operand) => {
+            let operand = value(operand, catalog);
+            let expression = expression.clone();
+            Box::new(move |environment, fuel| {
+                let value = operand(environment, fuel)?.unwrap_boolean(&expression)?;
+                Ok(Expression::Boolean(!value))
+            })
+        }
+        Expression::And(left, right) => logic(expression, left, right, false, catalog),
+        Expression::Or(left, right) => logic(expression, left, right, true, catalog),
+        Expression::Equal(left, right) => {
+            let left = value(left, catalog);
+            let right = value(right, catalog);
+            Box::new(move |environment, fuel| {
+                Ok(Expression::Boolean(
+                    left(environment, fuel)? == right(environment, fuel)?,
+                ))
+            })
+        }
+        Expression::NotEqual(left, right) => {
+            let left = value(left, catalog);
+            let right = value(right, catalog);
+            Box::new(move |environment, fuel| {
+                Ok(Expression::Boolean(
+                    left(environment, fuel)? != right(environment, fuel)?,
+                ))
+            })
+        }
+        Expression::LessThan(left, right) => {
+            comparison(expression, left, right, |left, right| left < right, catalog)
+        }
+        Expression::LessThanOrEqual(left, right) => comparison(
+            expression,
+            left,
+            right,
+            |left, right| left <= right,
+            catalog,
+        ),
+        Expression::GreaterThan(left, right) => {
+            comparison(expression, left, right, |left, right| left > right, catalog)
+        }
+        Expression::GreaterThanOrEqual(left, right) => comparison(
+            expression,
+            left,
+            right,
+            |left, right| left >= right,
+            catalog,
+        ),
+        Expression::Concatenate(left, right) => {
+            combination(expression, left, right, Expression::concatenate, catalog)
+        }
+        Expression::Length(operand) => {
+            let operand = value(operand, catalog);
+            let expression = expression.clone();
+            Box::new(move |environment, fuel| operand(environment, fuel)?.length(&expression))
+        }
+        Expression::Index(list, index) => {
+            combination(expression, list, index, Expression::element, catalog)
+        }
+        Expression::Append(list, element) => {
+            combination(expression, list, element, Expression::append, catalog)
+        }
+        Expression::Call(name, arguments) => {
+            let name = name.clone();
+            let arguments = arguments
+                .iter()
+                .map(|argument| value(argument, catalog))
+                .collect::<Vec<_>>();
+            let catalog = catalog.clone();
+            Box::new(move |environment, fuel| {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| argument(environment, fuel))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let (function, mut local) = environment.call(&name, arguments)?;
+                let body = body(&catalog, &name, &function);
+
+                fuel.enter(&local)?;
+                let completion = body(&mut local, fuel)?;
+                fuel.leave();
+
+                match &completion {
+                    Statement::Return(value) => Ok(value.clone()),
+                    _ => Err(RuntimeError::MissingReturn(name.clone())),
+                }
+            })
+        }
+        Expression::Invocation(name, body, local) => {
+            let name = name.clone();
+            let body = completion(meaning(body, catalog));
+            let local = local.clone();
+            Box::new(move |_, fuel| {
+                fuel.enter(&local)?;
+                let completion = body(&mut local.clone(), fuel)?;
+                fuel.leave();
+
+                match &completion {
+                    Statement::Return(value) => Ok(value.clone()),
+                    _ => Err(RuntimeError::MissingReturn(name.clone())),
+                }
+            })
+        }
+    }
+}
+
+fn meaning(statement: &Statement, catalog: &Weak<Catalog>) -> Meaning<Statement> {
+    match statement {
+        Statement::Expression(expression) => {
+            let expression = value(expression, catalog);
+            Box::new(move |environment, fuel| {
+                expression(environment, fuel).map(|_| Statement::Nothing)
+            })
+        }
+        Statement::Assign(name, expression) => {
+            let name = name.clone();
+            let expression = value(expression, catalog);
+            Box::new(move |environment, fuel| {
+                let value = expression(environment, fuel)?;
+                environment.insert(&name, value);
+                Ok(Statement::Nothing)
+            })
+        }
+        Statement::If(condition, consequence, alternative) => {
+            let source = condition.clone();
+            let condition = value(condition, catalog);
+            let consequence = meaning(consequence, catalog);
+            let alternative = meaning(alternative, catalog);
+            Box::new(move |environment, fuel| {
+                if condition(environment, fuel)?.unwrap_boolean(&source)? {
+                    consequence(environment, fuel)
+                } else {
+                    alternative(environment, fuel)
+                }
+            })
+        }
+        Statement::Sequence(_, _) => {
+            let mut statements = Vec::new();
+            let mut pending = vec![statement];
+
+            while let Some(statement) = pending.pop() {
+                match statement {
+                    Statement::Sequence(first, second) => {
+                        pending.push(second);
+                        pending.push(first);
+                    }
+                    statement => statements.push(meaning(statement, catalog)),
+                }
+            }
+
+            Box::new(move |environment, fuel| {
+                for statement in &statements {
+                    match statement(environment, fuel)? {
+                        Statement::Nothing => {}
+                        completion => return Ok(completion),
+                    }
+                }
+
+                Ok(Statement::Nothing)
+            })
+        }
+        Statement::While(condition, body) => {
+            let source = condition.clone();
+            let condition = value(condition, catalog);
+            let body = meaning(body, catalog);
+            Box::new(move |environment, fuel| {
+                loop {
+                    fuel.burn_loop_iteration(environment)?;
+
+                    if !condition(environment, fuel)?.unwrap_boolean(&source)? {
+                        break;
+                    }
+
+                    match body(environment, fuel)? {
+                        Statement::Break => break,
+                        completion @ Statement::Return(_) => return Ok(completion),
+                        _ => {}
+                    }
+                }
+
+                Ok(Statement::Nothing)
+            })
+        }
+        Statement::For(name, start, end, body) => {
+            let name = name.clone();
+            let counter = Box::new(Expression::Variable(name.clone()));
+            let source = Expression::LessThanOrEqual(counter.clone(), Box::new(end.clone()));
+            let condition = value(&source, catalog);
+            let increment = value(
+                &Expression::Add(counter, Box::new(Expression::Number(1))),
+                catalog,
+            );
+            let start = value(start, catalog);
+            let body = meaning(body, catalog);
+            Box::new(move |environment, fuel| {
+                let start = start(environment, fuel)?;
+                environment.insert(&name, start);
+
+                loop {
+                    fuel.burn_loop_iteration(environment)?;
+
+                    if !condition(environment, fuel)?.unwrap_boolean(&source)? {
+                        break;
+                    }
+
+                    match body(environment, fuel)? {
+                        Statement::Break => break,
+                        completion @ Statement::Return(_) => return Ok(completion),
+                        _ => {}
+                    }
+
+                    let next = increment(environment, fuel)?;
+                    environment.insert(&name, next);
+                }
+
+                Ok(Statement::Nothing)
+            })
+        }
+        Statement::Iteration(body, next) => {
+            let body = meaning(body, catalog);
+            let next = meaning(next, catalog);
+            Box::new(move |environment, fuel| match body(environment, fuel)? {
+                Statement::Break => Ok(Statement::Nothing),
+                completion @ Statement::Return(_) => Ok(completion),
+                _ => next(environment, fuel),
+            })
+        }
+        Statement::Let(name, expression, body) => {
+            let name = name.clone();
+            let expression = value(expression, catalog);
+            let body = meaning(body, catalog);
+            Box::new(move |environment, fuel| {
+                let value = expression(environment, fuel)?;
+                environment.enter(&name, value);
+                let result = body(environment, fuel);
+                environment.leave(&name);
+                result
+            })
+        }
+        Statement::Function(name, parameters, body) => {
+            let name = name.clone();
+            let function = Arc::new(Function::new(parameters.clone(), *body.clone()));
+            Box::new(move |environment, _| {
+                environment.define(&name, function.clone());
+                Ok(Statement::Nothing)
+            })
+        }
+        Statement::Return(expression) => {
+            let expression = value(expression, catalog);
+            Box::new(move |environment, fuel| expression(environment, fuel).map(Statement::Return))
+        }
+        Statement::Print(expression) => {
+            let expression = value(expression, catalog);
+            Box::new(move |environment, fuel| {
+                let value = expression(environment, fuel)?;
+                environment.print(&value);
+                Ok(Statement::Nothing)
+            })
+        }
+        statement @ (Statement::Break | Statement::Continue | Statement::Nothing) => {
+            let statement = statement.clone();
+            Box::new(move |_, _| Ok(statement.clone()))
+        }
+    }
+}
+
+fn arithmetic(
+    expression: &Expression,
+    left: &Expression,
+    right: &Expression,
+    operate: fn(i64, i64) -> Option<i64>,
+    catalog: &Weak<Catalog>,
+) -> Meaning<Expression> {
+    let left = value(left, catalog);
+    let right = value(right, catalog);
+    let expression = expression.clone();
+
+    Box::new(move |environment, fuel| {
+        let left = left(environment, fuel)?.unwrap_number(&expression)?;
+        let right = right(environment, fuel)?.unwrap_number(&expression)?;
+
+        match operate(left, right) {
+            Some(value) => Ok(Expression::Number(value)),
+            None => Err(RuntimeError::Overflow(expression.clone())),
+        }
+    })
+}
+
+fn logic(
+    expression: &Expression,
+    left: &Expression,
+    right: &Expression,
+    decisive: bool,
+    catalog: &Weak<Catalog>,
+) -> Meaning<Expression> {
+    let left = value(left, catalog);
+    let right = value(right, catalog);
+    let expression = expression.clone();
+
+    Box::new(move |environment, fuel| {
+        if left(environment, fuel)?.unwrap_boolean(&expression)? == decisive {
+            return Ok(Expression::Boolean(decisive));
+        }
+
+        let right = right(environment, fuel)?.unwrap_boolean(&expression)?;
+        Ok(Expression::Boolean(right))
+    })
+}
+
+fn comparison(
+    expression: &Expression,
+    left: &Expression,
+    right: &Expression,
+    operate: fn(i64, i64) -> bool,
+    catalog: &Weak<Catalog>,
+) -> Meaning<Expression> {
+    let left = value(left, catalog);
+    let right = value(right, catalog);
+    let expression = expression.clone();
+
+    Box::new(move |environment, fuel| {
+        let left = left(environment, fuel)?.unwrap_number(&expression)?;
+        let right = right(environment, fuel)?.unwrap_number(&expression)?;
+        Ok(Expression::Boolean(operate(left, right)))
+    })
+}
+
+fn combination(
+    expression: &Expression,
+    left: &Expression,
+    right: &Expression,
+    operate: fn(&Expression, &Expression, &Expression) -> Result<Expression, RuntimeError>,
+    catalog: &Weak<Catalog>,
+) -> Meaning<Expression> {
+    let left = value(left, catalog);
+    let right = value(right, catalog);
+    let expression = expression.clone();
+
+    Box::new(move |environment, fuel| {
+        let left = left(environment, fuel)?;
+        let right = right(environment, fuel)?;
+        operate(&left, &right, &expression)
+    })
+}
@@ -0,0 +1,154 @@
+use crate::{backend::rename, expression::Expression, statement::Statement};
+use std::{collections::HashMap, sync::Arc};
+
+pub trait Desugarer {
+    fn desugar(&self) -> Self;
+}
+
+impl Desugarer for Statement {
+    fn desugar(&self) -> Self {
+        match self {
+            Self::If(condition, consequence, alternative) => Self::If(
+                condition.clone(),
+                Box::new(consequence.desugar()),
+                Box::new(alternative.desugar()),
+            ),
+            Self::Sequence(_, _) => {
+                Self::sequence(self.spine().into_iter().map(Self::desugar).collect())
+            }
+            Self::While(condition, body) => {
+                Self::While(condition.clone(), Box::new(body.desugar()))
+            }
+            Self::For(name, start, end, body) => {
+                let counter = Box::new(Expression::Variable(name.clone()));
+                let increment = Self::Assign(
+                    name.clone(),
+                    Expression::Add(counter.clone(), Box::new(Expression::Number(1))),
+                );
+                let body = advance(&body.desugar(), name, &increment);
+
+                Self::Sequence(
+                    Arc::new(Self::Assign(name.clone(), start.clone())),
+                    Arc::new(Self::While(
+                        Expression::LessThanOrEqual(counter, Box::new(end.clone())),
+                        Box::new(Self::Sequence(Arc::new(body), Arc::new(increment))),
+                    )),
+                )
+            }
+            Self::Iteration(body, next) => {
+                Self::Iteration(Box::new(body.desugar()), Box::new(next.desugar()))
+            }
+            Self::Let(name, expression, body) => {
+                Self::Let(name.clone(), expression.clone(), Box::new(body.desugar()))
+            }
+            Self::Function(name, parameters, body) => {
+                Self::Function(name.clone(), parameters.clone(), Box::new(body.desugar()))
+            }
+            Self::Expression(_)
+            | Self::Assign(_, _)
+            | Self::Break
+            | Self::Continue
+            | Self::Return(_)
+            | Self::Print(_)
+            | Self::Nothing => self.clone(),
+        }
+    }
+}
+
+fn advance(statement: &Statement, counter: &str, increment: &Statement) -> Statement {
+    match statement {
+        Statement::Continue => {
+            Statement::Sequence(Arc::new(increment.clone()), Arc::new(Statement::Continue))
+        }
+        Statement::If(condition, consequence, alternative) => Statement::If(
+            condition.clone(),
+            Box::new(advance(consequence, counter, increment)),
+            Box::new(advance(alternative, counter, increment)),
+        ),
+        Statement::Sequence(_, _) => Statement::sequence(
+            statement
+                .spine()
+                .into_iter()
+                .map(|statement| advance(statement, counter, increment))
+                .collect(),
+        ),
+        Statement::Let(name, expression, body) if name == counter => {
+            let text = body.to_string();
+            let fresh = (1..)
+                .map(|index| format!("{}_{}", name, index))
+                .find(|fresh| !text.contains(fresh.as_str()))
+                .unwrap();
+            let body = substitute(body, name, &fresh);
+
+            Statement::Let(
+                fresh,
+                expression.clone(),
+                Box::new(advance(&body, counter, increment)),
+            )
+        }
+        Statement::Let(name, expression, body) => Statement::Let(
+            name.clone(),
+            expression.clone(),
+            Box::new(advance(body, counter, increment)),
+        ),
+        statement => statement.clone(),
+    }
+}
+
+fn substitute(statement: &Statement, from: &str, to: &str) -> Statement {
+    let renames = HashMap::from([(from.to_string(), to.to_string())]);
+    let target = |name: &String| {
+        if name == from {
+            to.into()
+        } else {
+            name.clone()
+        }
+    };
+
+    match statement {
+        Statement::Expression(expression) => Statement::Expression(rename(expression, &renames)),
+        Statement::Assign(name, expression) => {
+            Statement::Assign(target(name), rename(expression, &renames))
+        }
+        Statement::If(condition, consequence, alternative) => Statement::If(
+            rename(condition, &renames),
+            Box::new(substitute(consequence, from, to)),
+            Box::new(substitute(alternative, from, to)),
+        ),
+        Statement::Sequence(_, _) => Statement::sequence(
+            statement
+                .spine()
+                .into_iter()
+                .map(|statement| substitute(statement, from, to))
+                .collect(),
+        ),
+        Statement::While(condition, body) => Statement::While(
+            rename(condition, &renames),
+            Box::new(substitute(body, from, to)),
+        ),
+        Statement::For(name, start, end, body) => Statement::For(
+            target(name),
+            rename(start, &renames),
+            rename(end, &renames),
+            Box::new(substitute(body, from, to)),
+        ),
+        Statement::Iteration(body, next) => Statement::Iteration(
+            Box::new(substitute(body, from, to)),
+            Box::new(substitute(next, from, to)),
+        ),
+        Statement::Let(name, expression, body) if name == from => {
+            Statement::Let(name.clone(), rename(expression, &renames), body.clone())
+        }
+        Statement::Let(name, expression, body) => Statement::Let(
+            name.clone(),
+            rename(expression, &renames),
+            Box::new(substitute(body, from, to)),
+        ),
+        Statement::Return(expression) => Statement::Return(rename(expression, &renames)),
+        Statement::Print(expression) => Statement::Print(rename(expression, &renames)),
+        Statement::Function(_, _, _)
+        | Statement::Break
+        | Statement::Continue
+        | Statement::Nothing => statement.clone(),
+    }
+}
@@ -0,0 +1,7 @@
+pub mod counterexample;
+pub mod discrepancy;
+pub mod harness;
+pub mod outcome;
+pub mod property;
+pub mod semantics;
+pub mod shrink;
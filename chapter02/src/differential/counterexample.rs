@@ -0,0 +1,55 @@
+use super::discrepancy::Discrepancy;
+use crate::statement::Statement;
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counterexample {
+    seed: u64,
+    original: Statement,
+    shrunk: Statement,
+    discrepancy: Discrepancy,
+}
+
+impl Counterexample {
+    pub fn new(
+        seed: u64,
+        original: Statement,
+        shrunk: Statement,
+        discrepancy: Discrepancy,
+    ) -> Self {
+        Self {
+            seed,
+            original,
+            shrunk,
+            discrepancy,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn original(&self) -> &Statement {
+        &self.original
+    }
+
+    pub fn shrunk(&self) -> &Statement {
+        &self.shrunk
+    }
+
+    pub fn discrepancy(&self) -> &Discrepancy {
+        &self.discrepancy
+    }
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "seed {}: '{}' shrinks to '{}', where {}",
+            self.seed, self.original, self.shrunk, self.discrepancy
+        )
+    }
+}
+
+impl std::error::Error for Counterexample {}
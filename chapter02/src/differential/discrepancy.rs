@@ -0,0 +1,46 @@
+use super::{outcome::Outcome, semantics::Semantics};
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discrepancy {
+    semantics: Semantics,
+    expected: Outcome,
+    actual: Outcome,
+}
+
+impl Discrepancy {
+    pub fn new(semantics: Semantics, expected: Outcome, actual: Outcome) -> Self {
+        Self {
+            semantics,
+            expected,
+            actual,
+        }
+    }
+
+    pub fn semantics(&self) -> Semantics {
+        self.semantics
+    }
+
+    pub fn expected(&self) -> &Outcome {
+        &self.expected
+    }
+
+    pub fn actual(&self) -> &Outcome {
+        &self.actual
+    }
+}
+
+impl Display for Discrepancy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{} gives {} but {} gives {}",
+            Semantics::BigStep,
+            self.expected,
+            self.semantics,
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for Discrepancy {}
@@ -0,0 +1,38 @@
+use super::{discrepancy::Discrepancy, outcome::Outcome, semantics::Semantics};
+use crate::{
+    environment::Environment, fuel::Fuel, statement::Statement, transpile_error::TranspileError,
+};
+use anyhow::Result;
+
+const MAX_LOOP_ITERATIONS: usize = 10_000;
+
+pub fn compare(
+    statement: &Statement,
+    environment: &Environment,
+    input: &[String],
+    semantics: &[Semantics],
+) -> Result<Option<Discrepancy>> {
+    let fuel = Fuel::default().with_max_loop_iterations(MAX_LOOP_ITERATIONS);
+    let expected = Semantics::BigStep.run_with_input(statement, environment, fuel, input)?;
+
+    if let Outcome::Exhausted(_) = expected {
+        return Ok(None);
+    }
+
+    for semantics in semantics {
+        if *semantics == Semantics::BigStep {
+            continue;
+        }
+
+        let actual = match semantics.run_with_input(statement, environment, fuel, input) {
+            Err(error) if error.is::<TranspileError>() => continue,
+            result => result?,
+        };
+
+        if !actual.agrees_with(&expected) {
+            return Ok(Some(Discrepancy::new(*semantics, expected, actual)));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,125 @@
+use crate::{
+    environment::Environment, evaluator::Evaluator, expression::Expression, limit::Limit,
+    parser::parse_expression,
+};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result},
+};
+
+const FAILURES: [&str; 8] = [
+    "division by zero",
+    "arithmetic overflow",
+    "out of bounds",
+    "finished without returning a value",
+    "no more input to read",
+    "is not defined",
+    "arguments but was given",
+    "used outside of a loop",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Finished {
+        output: Vec<String>,
+        environment: Environment,
+    },
+    Transcript(String),
+    Failed(String),
+    Exhausted(Limit),
+}
+
+impl Outcome {
+    pub fn agrees_with(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Failed(message), Self::Failed(other_message)) => {
+                match (failure(message), failure(other_message)) {
+                    (Some(kind), Some(other_kind)) => kind == other_kind,
+                    _ => message == other_message,
+                }
+            }
+            (Self::Exhausted(limit), Self::Exhausted(other_limit)) => limit == other_limit,
+            (
+                Self::Finished {
+                    output,
+                    environment,
+                },
+                Self::Finished {
+                    output: other_output,
+                    environment: other_environment,
+                },
+            ) => output == other_output && variables(environment) == variables(other_environment),
+            (Self::Transcript(transcript), Self::Finished { .. }) => other.matches(transcript),
+            (Self::Finished { .. }, Self::Transcript(transcript)) => self.matches(transcript),
+            (Self::Transcript(transcript), Self::Transcript(other_transcript)) => {
+                transcript == other_transcript
+            }
+            _ => false,
+        }
+    }
+
+    fn matches(&self, transcript: &str) -> bool {
+        let Self::Finished {
+            output,
+            environment,
+        } = self
+        else {
+            return false;
+        };
+
+        let printed = output
+            .iter()
+            .map(|line| format!("{}\n", line))
+            .collect::<String>();
+
+        let Some(dump) = transcript.strip_prefix(&printed) else {
+            return false;
+        };
+
+        let mut actual = HashMap::new();
+
+        for line in dump.lines() {
+            let Some((name, source)) = line.split_once(" = ") else {
+                return false;
+            };
+
+            match parse_expression(source).map(|value| value.evaluate(&mut Environment::new())) {
+                Ok(Ok(value)) => actual.insert(name.to_string(), value),
+                _ => return false,
+            };
+        }
+
+        actual == variables(environment)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Finished {
+                output,
+                environment,
+            } if output.is_empty() => write!(f, "{}", environment),
+            Self::Finished {
+                output,
+                environment,
+            } => write!(f, "{} after printing {:?}", environment, output),
+            Self::Transcript(transcript) => write!(f, "{:?}", transcript),
+            Self::Failed(message) => write!(f, "error '{}'", message),
+            Self::Exhausted(limit) => write!(f, "out of fuel for {}", limit),
+        }
+    }
+}
+
+pub fn failure(message: &str) -> Option<&'static str> {
+    FAILURES
+        .into_iter()
+        .find(|failure| message.contains(failure))
+}
+
+fn variables(environment: &Environment) -> HashMap<String, Expression> {
+    environment
+        .variables()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
@@ -0,0 +1,69 @@
+use super::{
+    counterexample::Counterexample, harness::compare, semantics::Semantics, shrink::shrink,
+};
+use crate::{
+    environment::Environment, generator::Generator, type_checker::TypeChecker,
+    type_context::TypeContext,
+};
+use anyhow::Result;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    semantics: Vec<Semantics>,
+    seed: u64,
+    cases: usize,
+}
+
+impl Property {
+    pub fn new(semantics: Vec<Semantics>) -> Self {
+        Self {
+            semantics,
+            seed: 0,
+            cases: 100,
+        }
+    }
+
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    pub fn with_cases(self, cases: usize) -> Self {
+        Self { cases, ..self }
+    }
+
+    pub fn check(&self) -> Result<Option<Counterexample>> {
+        let environment = Environment::new();
+
+        for case in 0..self.cases {
+            let seed = self.seed.wrapping_add(case as u64);
+            let mut generator = Generator::new(seed);
+            let original = generator.program();
+            let input = generator.input();
+
+            let Some(discrepancy) = compare(&original, &environment, input, &self.semantics)?
+            else {
+                continue;
+            };
+
+            let semantics = [discrepancy.semantics()];
+            let shrunk = shrink(&original, |candidate| {
+                candidate
+                    .type_check(&mut TypeContext::from(&environment))
+                    .is_ok()
+                    && compare(candidate, &environment, input, &semantics)
+                        .is_ok_and(|found| found.is_some())
+            });
+            let discrepancy =
+                compare(&shrunk, &environment, input, &semantics)?.unwrap_or(discrepancy);
+
+            return Ok(Some(Counterexample::new(
+                seed,
+                original,
+                shrunk,
+                discrepancy,
+            )));
+        }
+
+        Ok(None)
+    }
+}
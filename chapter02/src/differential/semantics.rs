@@ -0,0 +1,312 @@
+use super::outcome::{Outcome, failure};
+use crate::{
+    bytecode::{chunk::Chunk, virtual_machine::VirtualMachine},
+    console::ScriptedConsole,
+    denotation::Denotation,
+    environment::Environment,
+    evaluator::Evaluator,
+    fuel::Fuel,
+    limit::Limit,
+    machine::Machine,
+    optimizer::Optimizer,
+    runtime_error::RuntimeError,
+    statement::Statement,
+    target::Target,
+};
+use anyhow::{Context, Result};
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    io::{self, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+static COMPILATIONS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Semantics {
+    SmallStep,
+    BigStep,
+    Denotational,
+    Bytecode,
+    Optimized,
+    Transpiled(Target),
+}
+
+impl Semantics {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::SmallStep,
+            Self::BigStep,
+            Self::Denotational,
+            Self::Bytecode,
+            Self::Optimized,
+            Self::Transpiled(Target::Ruby),
+            Self::Transpiled(Target::JavaScript),
+            Self::Transpiled(Target::Python),
+            Self::Transpiled(Target::C),
+        ]
+    }
+
+    pub fn available() -> Vec<Self> {
+        Self::all()
+            .into_iter()
+            .filter(|semantics| semantics.is_available())
+            .collect()
+    }
+
+    pub fn is_available(&self) -> bool {
+        match self {
+            Self::Transpiled(target) => {
+                let program = match target {
+                    Target::Ruby => "ruby",
+                    Target::JavaScript => "node",
+                    Target::Python => "python3",
+                    Target::C => "cc",
+                };
+
+                Command::new(program)
+                    .arg("--version")
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status()
+                    .is_ok_and(|status| status.success())
+            }
+            _ => true,
+        }
+    }
+
+    pub fn run(&self, statement: &Statement, environment: &Environment) -> Result<Outcome> {
+        self.run_with_fuel(statement, environment, Fuel::default())
+    }
+
+    pub fn run_with_fuel(
+        &self,
+        statement: &Statement,
+        environment: &Environment,
+        fuel: Fuel,
+    ) -> Result<Outcome> {
+        self.run_with_input(statement, environment, fuel, &[])
+    }
+
+    pub fn run_with_input(
+        &self,
+        statement: &Statement,
+        environment: &Environment,
+        fuel: Fuel,
+        input: &[String],
+    ) -> Result<Outcome> {
+        let console = Arc::new(Mutex::new(ScriptedConsole::new(input.iter().cloned())));
+        let mut environment = environment.clone();
+        environment.attach(console.clone());
+
+        let result = match self {
+            Self::SmallStep => {
+                let mut machine = Machine::with_fuel(statement.clone(), environment, fuel);
+                let result = machine.run_with_observer(&mut |_: &_| {});
+                environment = machine.environment().clone();
+                result
+            }
+            Self::BigStep => statement
+                .evaluate_with_fuel(&mut environment, &mut fuel.clone())
+                .map(|_| ()),
+            Self::Denotational => {
+                statement.denote()(&mut environment, &mut fuel.clone()).map(|_| ())
+            }
+            Self::Bytecode => {
+                VirtualMachine::with_fuel(Chunk::compile(statement), fuel).run(&mut environment)
+            }
+            Self::Optimized => {
+                let (optimized, _) = statement.optimize();
+                optimized
+                    .evaluate_with_fuel(&mut environment, &mut fuel.clone())
+                    .map(|_| ())
+            }
+            Self::Transpiled(target) => {
+                let program = target.backend().program(statement, &environment)?;
+                return transpiled(*target, &program, input);
+            }
+        };
+
+        let output = console.lock().unwrap().output().to_vec();
+
+        Ok(match result {
+            Ok(()) => Outcome::Finished {
+                output,
+                environment,
+            },
+            Err(RuntimeError::OutOfFuel { limit, .. }) => Outcome::Exhausted(limit),
+            Err(error) => Outcome::Failed(error.to_string()),
+        })
+    }
+}
+
+impl Display for Semantics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SmallStep => write!(f, "small-step"),
+            Self::BigStep => write!(f, "big-step"),
+            Self::Denotational => write!(f, "denotational"),
+            Self::Bytecode => write!(f, "bytecode"),
+            Self::Optimized => write!(f, "optimized"),
+            Self::Transpiled(target) => write!(f, "{}", target),
+        }
+    }
+}
+
+fn transpiled(target: Target, program: &str, input: &[String]) -> Result<Outcome> {
+    let base = scratch();
+    let output = match target {
+        Target::Ruby => interpret("ruby", &base.with_extension("rb"), program, input),
+        Target::JavaScript => interpret("node", &base.with_extension("js"), program, input),
+        Target::Python => interpret("python3", &base.with_extension("py"), program, input),
+        Target::C => compile(&base, program, input),
+    }?;
+
+    let Some(output) = output else {
+        return Ok(Outcome::Exhausted(Limit::WallClock));
+    };
+
+    if output.status.success() {
+        Ok(Outcome::Transcript(String::from_utf8(output.stdout)?))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let lines = stderr
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let message = lines
+            .iter()
+            .rev()
+            .find(|line| failure(line).is_some())
+            .or_else(|| lines.iter().rev().find(|line| line.contains("Error")))
+            .or(lines.last())
+            .unwrap_or(&"");
+
+        Ok(Outcome::Failed(message.to_string()))
+    }
+}
+
+fn scratch() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "simple-{}-{}",
+        std::process::id(),
+        COMPILATIONS.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+fn interpret(
+    interpreter: &str,
+    source: &Path,
+    program: &str,
+    input: &[String],
+) -> Result<Option<Output>> {
+    fs::write(source, program)?;
+
+    let mut command = Command::new(interpreter);
+    command.arg(source);
+    let output =
+        execute(&mut command, input).with_context(|| format!("failed to start {}", interpreter));
+
+    let _ = fs::remove_file(source);
+
+    output
+}
+
+fn compile(base: &Path, program: &str, input: &[String]) -> Result<Option<Output>> {
+    let source = base.with_extension("c");
+    fs::write(&source, program)?;
+
+    let compiled = Command::new("cc")
+        .arg("-o")
+        .arg(base)
+        .arg(&source)
+        .output()
+        .context("failed to start cc");
+    let output = match compiled {
+        Ok(compiled) if compiled.status.success() => {
+            execute(&mut Command::new(base), input).context("failed to run the compiled program")
+        }
+        Ok(compiled) => Err(anyhow::anyhow!(
+            "cc failed: {}",
+            String::from_utf8_lossy(&compiled.stderr).trim()
+        )),
+        Err(error) => Err(error),
+    };
+
+    let _ = fs::remove_file(&source);
+    let _ = fs::remove_file(base);
+
+    output
+}
+
+fn execute(command: &mut Command, input: &[String]) -> Result<Option<Output>> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .context("failed to open the program's standard input")?;
+    let script = input
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+
+    match stdin.write_all(script.as_bytes()) {
+        Err(error) if error.kind() != ErrorKind::BrokenPipe => return Err(error.into()),
+        _ => drop(stdin),
+    }
+
+    let stdout = collect(child.stdout.take())?;
+    let stderr = collect(child.stderr.take())?;
+    let deadline = Instant::now() + TIMEOUT;
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+
+        thread::sleep(Duration::from_millis(1));
+    };
+
+    let stdout = stdout.join().unwrap()?;
+    let stderr = stderr.join().unwrap()?;
+
+    Ok(status.map(|status| Output {
+        status,
+        stdout,
+        stderr,
+    }))
+}
+
+fn collect(
+    pipe: Option<impl Read + Send + 'static>,
+) -> Result<thread::JoinHandle<io::Result<Vec<u8>>>> {
+    let mut pipe = pipe.context("failed to open the program's output")?;
+
+    Ok(thread::spawn(move || {
+        let mut bytes = Vec::new();
+        pipe.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }))
+}
@@ -0,0 +1,257 @@
+use crate::{expression::Expression, statement::Statement};
+use std::sync::Arc;
+
+pub fn shrink(statement: &Statement, mut reproduces: impl FnMut(&Statement) -> bool) -> Statement {
+    let mut current = statement.clone();
+
+    'search: loop {
+        for candidate in statements(&current) {
+            if reproduces(&candidate) {
+                current = candidate;
+                continue 'search;
+            }
+        }
+
+        return current;
+    }
+}
+
+pub fn statements(statement: &Statement) -> Vec<Statement> {
+    let mut candidates = match statement {
+        Statement::Nothing => return Vec::new(),
+        _ => vec![Statement::Nothing],
+    };
+
+    match statement {
+        Statement::Expression(expression) => {
+            for expression in expressions(expression) {
+                candidates.push(Statement::Expression(expression));
+            }
+        }
+        Statement::Assign(name, expression) => {
+            for expression in expressions(expression) {
+                candidates.push(Statement::Assign(name.clone(), expression));
+            }
+        }
+        Statement::If(condition, consequence, alternative) => {
+            candidates.push(*consequence.clone());
+            candidates.push(*alternative.clone());
+
+            for condition in expressions(condition) {
+                candidates.push(Statement::If(
+                    condition,
+                    consequence.clone(),
+                    alternative.clone(),
+                ));
+            }
+
+            for consequence in statements(consequence) {
+                candidates.push(Statement::If(
+                    condition.clone(),
+                    Box::new(consequence),
+                    alternative.clone(),
+                ));
+            }
+
+            for alternative in statements(alternative) {
+                candidates.push(Statement::If(
+                    condition.clone(),
+                    consequence.clone(),
+                    Box::new(alternative),
+                ));
+            }
+        }
+        Statement::Sequence(first, second) => {
+            candidates.push((**first).clone());
+            candidates.push((**second).clone());
+
+            for first in statements(first) {
+                candidates.push(Statement::Sequence(Arc::new(first), second.clone()));
+            }
+
+            for second in statements(second) {
+                candidates.push(Statement::Sequence(first.clone(), Arc::new(second)));
+            }
+        }
+        Statement::While(condition, body) => {
+            candidates.push(*body.clone());
+
+            for condition in expressions(condition) {
+                candidates.push(Statement::While(condition, body.clone()));
+            }
+
+            for body in statements(body) {
+                candidates.push(Statement::While(condition.clone(), Box::new(body)));
+            }
+        }
+        Statement::For(name, start, end, body) => {
+            candidates.push(*body.clone());
+
+            for start in expressions(start) {
+                candidates.push(Statement::For(
+                    name.clone(),
+                    start,
+                    end.clone(),
+                    body.clone(),
+                ));
+            }
+
+            for end in expressions(end) {
+                candidates.push(Statement::For(
+                    name.clone(),
+                    start.clone(),
+                    end,
+                    body.clone(),
+                ));
+            }
+
+            for body in statements(body) {
+                candidates.push(Statement::For(
+                    name.clone(),
+                    start.clone(),
+                    end.clone(),
+                    Box::new(body),
+                ));
+            }
+        }
+        Statement::Let(name, expression, body) => {
+            for expression in expressions(expression) {
+                candidates.push(Statement::Let(name.clone(), expression, body.clone()));
+            }
+
+            for body in statements(body) {
+                candidates.push(Statement::Let(
+                    name.clone(),
+                    expression.clone(),
+                    Box::new(body),
+                ));
+            }
+        }
+        Statement::Function(name, parameters, body) => {
+            for body in statements(body) {
+                candidates.push(Statement::Function(
+                    name.clone(),
+                    parameters.clone(),
+                    Box::new(body),
+                ));
+            }
+        }
+        Statement::Return(expression) => {
+            for expression in expressions(expression) {
+                candidates.push(Statement::Return(expression));
+            }
+        }
+        Statement::Print(expression) => {
+            for expression in expressions(expression) {
+                candidates.push(Statement::Print(expression));
+            }
+        }
+        Statement::Iteration(_, _)
+        | Statement::Break
+        | Statement::Continue
+        | Statement::Nothing => {}
+    }
+
+    candidates
+}
+
+pub fn expressions(expression: &Expression) -> Vec<Expression> {
+    match expression {
+        Expression::Number(0) => Vec::new(),
+        Expression::Number(value) if value.unsigned_abs() <= 1 => vec![Expression::Number(0)],
+        Expression::Number(value) => vec![Expression::Number(0), Expression::Number(value / 2)],
+        Expression::Boolean(true) => vec![Expression::Boolean(false)],
+        Expression::Boolean(false) => Vec::new(),
+        Expression::String(value) => match value.chars().count() {
+            0 => Vec::new(),
+            length => vec![
+                Expression::String(String::new()),
+                Expression::String(value.chars().take(length / 2).collect()),
+            ],
+        },
+        Expression::List(elements) => {
+            let mut candidates = Vec::new();
+
+            for index in 0..elements.len() {
+                let mut fewer = elements.clone();
+                fewer.remove(index);
+                candidates.push(Expression::List(fewer));
+            }
+
+            for (index, element) in elements.iter().enumerate() {
+                for element in expressions(element) {
+                    let mut smaller = elements.clone();
+                    smaller[index] = element;
+                    candidates.push(Expression::List(smaller));
+                }
+            }
+
+            candidates
+        }
+        Expression::Variable(_) => vec![Expression::Number(0), Expression::Boolean(false)],
+        Expression::Input | Expression::Invocation(_, _, _) => Vec::new(),
+        Expression::Add(left, right) => binary(left, right, Expression::Add),
+        Expression::Subtract(left, right) => binary(left, right, Expression::Subtract),
+        Expression::Multiply(left, right) => binary(left, right, Expression::Multiply),
+        Expression::Divide(left, right) => binary(left, right, Expression::Divide),
+        Expression::Negate(operand) => unary(operand, Expression::Negate),
+        Expression::Not(operand) => unary(operand, Expression::Not),
+        Expression::And(left, right) => binary(left, right, Expression::And),
+        Expression::Or(left, right) => binary(left, right, Expression::Or),
+        Expression::Equal(left, right) => binary(left, right, Expression::Equal),
+        Expression::NotEqual(left, right) => binary(left, right, Expression::NotEqual),
+        Expression::LessThan(left, right) => binary(left, right, Expression::LessThan),
+        Expression::LessThanOrEqual(left, right) => {
+            binary(left, right, Expression::LessThanOrEqual)
+        }
+        Expression::GreaterThan(left, right) => binary(left, right, Expression::GreaterThan),
+        Expression::GreaterThanOrEqual(left, right) => {
+            binary(left, right, Expression::GreaterThanOrEqual)
+        }
+        Expression::Concatenate(left, right) => binary(left, right, Expression::Concatenate),
+        Expression::Length(operand) => unary(operand, Expression::Length),
+        Expression::Index(list, index) => binary(list, index, Expression::Index),
+        Expression::Append(list, element) => binary(list, element, Expression::Append),
+        Expression::Call(name, arguments) => {
+            let mut candidates = Vec::new();
+
+            for (index, argument) in arguments.iter().enumerate() {
+                for argument in expressions(argument) {
+                    let mut smaller = arguments.clone();
+                    smaller[index] = argument;
+                    candidates.push(Expression::Call(name.clone(), smaller));
+                }
+            }
+
+            candidates
+        }
+    }
+}
+
+fn unary(operand: &Expression, rebuild: fn(Box<Expression>) -> Expression) -> Vec<Expression> {
+    let mut candidates = vec![operand.clone()];
+
+    for operand in expressions(operand) {
+        candidates.push(rebuild(Box::new(operand)));
+    }
+
+    candidates
+}
+
+fn binary(
+    left: &Expression,
+    right: &Expression,
+    rebuild: fn(Box<Expression>, Box<Expression>) -> Expression,
+) -> Vec<Expression> {
+    let mut candidates = vec![left.clone(), right.clone()];
+
+    for left in expressions(left) {
+        candidates.push(rebuild(Box::new(left), Box::new(right.clone())));
+    }
+
+    for right in expressions(right) {
+        candidates.push(rebuild(Box::new(left.clone()), Box::new(right)));
+    }
+
+    candidates
+}
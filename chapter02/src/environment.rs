@@ -1,31 +1,173 @@
-use crate::expression::Expression;
+use crate::{
+    console::{Console, StandardConsole},
+    expression::Expression,
+    function::Function,
+    runtime_error::RuntimeError,
+};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::{Display, Formatter, Result},
+    sync::{Arc, Mutex},
 };
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct Environment {
-    variables: HashMap<String, Expression>,
+    frames: Vec<Arc<HashMap<String, Expression>>>,
+    functions: HashMap<String, Arc<Function>>,
+    console: Arc<Mutex<dyn Console>>,
 }
 
 impl Environment {
     pub fn new() -> Self {
+        Self::with_console(Arc::new(Mutex::new(StandardConsole)))
+    }
+
+    pub fn with_console(console: Arc<Mutex<dyn Console>>) -> Self {
         Self {
-            variables: HashMap::new(),
+            frames: vec![Arc::default()],
+            functions: HashMap::new(),
+            console,
+        }
+    }
+
+    pub fn console(&self) -> Arc<Mutex<dyn Console>> {
+        self.console.clone()
+    }
+
+    pub fn attach(&mut self, console: Arc<Mutex<dyn Console>>) {
+        self.console = console;
+    }
+
+    pub fn isolated(&self) -> Self {
+        Self::with_console(self.console.clone())
+    }
+
+    pub fn print(&self, value: &Expression) {
+        let line = match value {
+            Expression::String(text) => text.clone(),
+            value => value.to_string(),
+        };
+
+        self.console.lock().unwrap().write(&line);
+    }
+
+    pub fn input(&self) -> std::result::Result<Expression, RuntimeError> {
+        match self.console.lock().unwrap().read() {
+            Some(line) => Ok(Expression::String(line)),
+            None => Err(RuntimeError::EndOfInput),
         }
     }
 
     pub fn insert(&mut self, key: &str, value: Expression) {
-        self.variables.insert(key.into(), value);
+        let frame = match self
+            .frames
+            .iter()
+            .rposition(|frame| frame.contains_key(key))
+        {
+            Some(index) => &mut self.frames[index],
+            None => &mut self.frames[0],
+        };
+
+        Arc::make_mut(frame).insert(key.into(), value);
+    }
+
+    pub fn get(&self, key: &str) -> std::result::Result<Expression, RuntimeError> {
+        match self.frames.iter().rev().find_map(|frame| frame.get(key)) {
+            Some(value) => Ok(value.clone()),
+            None => Err(RuntimeError::UnboundVariable(key.into())),
+        }
+    }
+
+    pub fn variables(&self) -> impl Iterator<Item = (&str, &Expression)> {
+        let mut seen = HashSet::new();
+
+        self.frames
+            .iter()
+            .rev()
+            .flat_map(|frame| frame.iter())
+            .filter(move |(key, _)| seen.insert(key.as_str()))
+            .map(|(key, value)| (key.as_str(), value))
+    }
+
+    pub fn enter(&mut self, key: &str, value: Expression) {
+        self.frames
+            .push(Arc::new(HashMap::from([(key.into(), value)])));
     }
 
-    pub fn get(&self, key: &str) -> Expression {
-        match self.variables.get(key) {
-            Some(value) => value.clone(),
-            None => panic!("key '{}' is not found", key),
+    pub fn leave(&mut self, key: &str) -> Option<Expression> {
+        if self.frames.len() > 1 {
+            Arc::unwrap_or_clone(self.frames.pop()?).remove(key)
+        } else {
+            None
         }
     }
+
+    pub fn depth(&self) -> usize {
+        self.frames.len() - 1
+    }
+
+    pub fn unwind(&mut self, depth: usize) {
+        self.frames.truncate(depth + 1);
+    }
+
+    pub fn define(&mut self, name: &str, function: impl Into<Arc<Function>>) {
+        self.functions.insert(name.into(), function.into());
+    }
+
+    pub fn function(&self, name: &str) -> std::result::Result<Arc<Function>, RuntimeError> {
+        match self.functions.get(name) {
+            Some(function) => Ok(function.clone()),
+            None => Err(RuntimeError::UndefinedFunction(name.into())),
+        }
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = (&str, &Arc<Function>)> {
+        self.functions
+            .iter()
+            .map(|(key, function)| (key.as_str(), function))
+    }
+
+    pub fn call(
+        &self,
+        name: &str,
+        arguments: Vec<Expression>,
+    ) -> std::result::Result<(Arc<Function>, Self), RuntimeError> {
+        let function = self.function(name)?;
+
+        if function.parameters().len() != arguments.len() {
+            return Err(RuntimeError::ArityMismatch {
+                name: name.into(),
+                expected: function.parameters().len(),
+                actual: arguments.len(),
+            });
+        }
+
+        let variables = function
+            .parameters()
+            .iter()
+            .cloned()
+            .zip(arguments)
+            .collect();
+        let local = Self {
+            frames: vec![Arc::new(variables)],
+            functions: self.functions.clone(),
+            console: self.console.clone(),
+        };
+
+        Ok((function, local))
+    }
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for Environment {
+    fn eq(&self, other: &Self) -> bool {
+        self.frames == other.frames && self.functions == other.functions
+    }
 }
 
 impl Display for Environment {
@@ -33,8 +175,7 @@ impl Display for Environment {
         write!(
             f,
             "{{ {} }}",
-            self.variables
-                .iter()
+            self.variables()
                 .map(|(k, v)| format!("{} = {}", k, v))
                 .collect::<Vec<_>>()
                 .join(", ")
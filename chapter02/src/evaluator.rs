@@ -1,117 +1,495 @@
-use crate::{environment::Environment, expression::Expression, statement::Statement};
+use crate::{
+    environment::Environment, expression::Expression, fuel::Fuel, function::Function,
+    runtime_error::RuntimeError, statement::Statement,
+};
+use std::sync::Arc;
 
-pub trait Evaluator {
-    fn evaluate(&self, environment: &mut Environment) -> Self;
+pub trait Evaluator: Sized {
+    fn evaluate(&self, environment: &mut Environment) -> Result<Self, RuntimeError> {
+        self.evaluate_with_fuel(environment, &mut Fuel::default())
+    }
+
+    fn evaluate_with_fuel(
+        &self,
+        environment: &mut Environment,
+        fuel: &mut Fuel,
+    ) -> Result<Self, RuntimeError>;
 }
 
 impl Evaluator for Expression {
-    fn evaluate(&self, environment: &mut Environment) -> Self {
-        match self {
-            expression @ Self::Number(_) => expression.clone(),
-            expression @ Self::Boolean(_) => expression.clone(),
-            Self::Variable(value) => environment.get(value),
-            Self::Add(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Number(left.unwrap_number() + right.unwrap_number())
-            }
-            Self::Subtract(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Number(left.unwrap_number() - right.unwrap_number())
-            }
-            Self::Multiply(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Number(left.unwrap_number() * right.unwrap_number())
-            }
-            Self::Divide(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Number(left.unwrap_number() / right.unwrap_number())
-            }
-            Self::Not(expression) => {
-                let expression = expression.evaluate(environment);
-                Self::Boolean(!expression.unwrap_boolean())
-            }
-            Self::And(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Boolean(left.unwrap_boolean() && right.unwrap_boolean())
-            }
-            Self::Or(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Boolean(left.unwrap_boolean() || right.unwrap_boolean())
-            }
-            Self::Equal(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Boolean(left == right)
-            }
-            Self::NotEqual(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Boolean(left != right)
-            }
-            Self::LessThan(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Boolean(left.unwrap_number() < right.unwrap_number())
-            }
-            Self::LessThanOrEqual(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Boolean(left.unwrap_number() <= right.unwrap_number())
-            }
-            Self::GreaterThan(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Boolean(left.unwrap_number() > right.unwrap_number())
-            }
-            Self::GreaterThanOrEqual(left, right) => {
-                let left = left.evaluate(environment);
-                let right = right.evaluate(environment);
-                Self::Boolean(left.unwrap_number() >= right.unwrap_number())
-            }
+    fn evaluate_with_fuel(
+        &self,
+        environment: &mut Environment,
+        fuel: &mut Fuel,
+    ) -> Result<Self, RuntimeError> {
+        match &execute(vec![Frame::Yield, Frame::Compute(self)], environment, fuel)? {
+            Statement::Expression(value) => Ok(value.clone()),
+            evaluated => unreachable!("'{}' did not yield a value", evaluated),
         }
     }
 }
 
+enum Frame<'a> {
+    Evaluate(&'a Statement),
+    Compute(&'a Expression),
+    Decide(&'a Expression),
+    Apply(&'a Expression),
+    Complete(&'a Statement),
+    Test(&'a Statement),
+    Yield,
+    Discard,
+    Leave(&'a str),
+    Iterate(&'a Statement),
+}
+
+enum Call<'a> {
+    Function(&'a str, Arc<Function>, Environment),
+    Invocation(&'a str, &'a Statement, Environment),
+}
+
+struct Activation<'a> {
+    frames: Vec<Frame<'a>>,
+    values: Vec<Expression>,
+    evaluated: Statement,
+}
+
 impl Evaluator for Statement {
-    fn evaluate(&self, environment: &mut Environment) -> Self {
-        match self {
-            Self::Expression(expression) => Self::Expression(expression.evaluate(environment)),
-            Self::Assign(name, expression) => {
-                let expression = expression.evaluate(environment);
-                environment.insert(name, expression);
-                Self::Nothing
-            }
-            Self::If(condition, consequence, alternative) => {
-                let condition = condition.evaluate(environment);
-
-                if condition.unwrap_boolean() {
-                    consequence.evaluate(environment)
-                } else {
-                    alternative.evaluate(environment)
+    fn evaluate_with_fuel(
+        &self,
+        environment: &mut Environment,
+        fuel: &mut Fuel,
+    ) -> Result<Self, RuntimeError> {
+        let depth = environment.depth();
+        let evaluated =
+            execute(vec![Frame::Evaluate(self)], environment, fuel).and_then(|evaluated| {
+                match evaluated {
+                    Statement::Break | Statement::Continue => {
+                        Err(RuntimeError::OutsideLoop(Box::new(evaluated)))
+                    }
+                    evaluated => Ok(evaluated),
+                }
+            });
+
+        if evaluated.is_err() {
+            environment.unwind(depth);
+        }
+
+        evaluated
+    }
+}
+
+fn execute(
+    frames: Vec<Frame<'_>>,
+    environment: &mut Environment,
+    fuel: &mut Fuel,
+) -> Result<Statement, RuntimeError> {
+    let mut activation = Activation {
+        frames,
+        values: Vec::new(),
+        evaluated: Statement::Nothing,
+    };
+
+    while let Some(call) = activation.run(environment, fuel)? {
+        let value = match call {
+            Call::Function(name, function, mut local) => {
+                invoke(name, function.body(), &mut local, fuel)?
+            }
+            Call::Invocation(name, body, mut local) => invoke(name, body, &mut local, fuel)?,
+        };
+        activation.values.push(value);
+    }
+
+    Ok(activation.evaluated)
+}
+
+impl<'a> Activation<'a> {
+    fn run(
+        &mut self,
+        environment: &mut Environment,
+        fuel: &mut Fuel,
+    ) -> Result<Option<Call<'a>>, RuntimeError> {
+        while let Some(frame) = self.frames.pop() {
+            match frame {
+                Frame::Evaluate(statement) => self.evaluate(statement, environment, fuel)?,
+                Frame::Compute(expression) => {
+                    if let Some(call) = self.compute(expression, environment)? {
+                        return Ok(Some(call));
+                    }
+                }
+                Frame::Decide(expression) => self.decide(expression)?,
+                Frame::Apply(Expression::Call(name, arguments)) => {
+                    let arguments = self.values.split_off(self.values.len() - arguments.len());
+                    let (function, local) = environment.call(name, arguments)?;
+                    return Ok(Some(Call::Function(name, function, local)));
+                }
+                Frame::Apply(expression) => {
+                    let value = apply(expression, &mut self.values)?;
+                    self.values.push(value);
+                }
+                Frame::Complete(statement) => self.complete(statement, environment, fuel)?,
+                Frame::Test(statement) => self.test(statement, environment)?,
+                Frame::Yield => self.evaluated = Statement::Expression(pop(&mut self.values)),
+                Frame::Discard => self.evaluated = Statement::Nothing,
+                Frame::Leave(name) => {
+                    environment.leave(name);
+                    self.evaluated = Statement::Nothing;
                 }
+                Frame::Iterate(statement) => self.repeat(statement, environment, fuel)?,
             }
-            Self::Sequence(first, second) => {
-                first.evaluate(environment);
-                second.evaluate(environment);
-                Self::Nothing
+        }
+
+        Ok(None)
+    }
+
+    fn evaluate(
+        &mut self,
+        statement: &'a Statement,
+        environment: &mut Environment,
+        fuel: &mut Fuel,
+    ) -> Result<(), RuntimeError> {
+        match statement {
+            Statement::Expression(expression) => {
+                self.frames.push(Frame::Yield);
+                self.frames.push(Frame::Compute(expression));
+            }
+            Statement::Assign(_, expression)
+            | Statement::If(expression, _, _)
+            | Statement::For(_, expression, _, _)
+            | Statement::Let(_, expression, _)
+            | Statement::Return(expression)
+            | Statement::Print(expression) => {
+                self.frames.push(Frame::Complete(statement));
+                self.frames.push(Frame::Compute(expression));
+            }
+            Statement::Sequence(first, second) => {
+                self.frames.push(Frame::Discard);
+                self.frames.push(Frame::Evaluate(second));
+                self.frames.push(Frame::Evaluate(first));
+            }
+            Statement::While(_, _) => {
+                self.iterate(statement, environment, fuel)?;
+                self.evaluated = Statement::Nothing;
+            }
+            Statement::Iteration(body, _) => {
+                self.frames.push(Frame::Iterate(statement));
+                self.frames.push(Frame::Evaluate(body));
+            }
+            Statement::Break | Statement::Continue => {
+                self.evaluated = statement.clone();
+
+                while let Some(frame) = self.frames.pop() {
+                    match frame {
+                        Frame::Leave(name) => {
+                            environment.leave(name);
+                        }
+                        Frame::Iterate(looping) => {
+                            if let Statement::Continue = statement {
+                                self.frames.push(Frame::Iterate(looping));
+                            }
+
+                            self.evaluated = Statement::Nothing;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Statement::Function(name, parameters, body) => {
+                environment.define(name, Function::new(parameters.clone(), *body.clone()));
+                self.evaluated = Statement::Nothing;
             }
-            statement @ Self::While(condition, body) => {
-                let condition = condition.evaluate(environment);
+            Statement::Nothing => self.evaluated = Statement::Nothing,
+        }
+
+        Ok(())
+    }
+
+    fn compute(
+        &mut self,
+        expression: &'a Expression,
+        environment: &mut Environment,
+    ) -> Result<Option<Call<'a>>, RuntimeError> {
+        match expression {
+            Expression::Number(_) | Expression::Boolean(_) | Expression::String(_) => {
+                self.values.push(expression.clone());
+            }
+            Expression::Variable(name) => self.values.push(environment.get(name)?),
+            Expression::Input => self.values.push(environment.input()?),
+            Expression::List(operands) | Expression::Call(_, operands) => {
+                self.frames.push(Frame::Apply(expression));
+                self.frames
+                    .extend(operands.iter().rev().map(Frame::Compute));
+            }
+            Expression::Negate(operand)
+            | Expression::Not(operand)
+            | Expression::Length(operand) => {
+                self.frames.push(Frame::Apply(expression));
+                self.frames.push(Frame::Compute(operand));
+            }
+            Expression::And(left, _) | Expression::Or(left, _) => {
+                self.frames.push(Frame::Decide(expression));
+                self.frames.push(Frame::Compute(left));
+            }
+            Expression::Add(left, right)
+            | Expression::Subtract(left, right)
+            | Expression::Multiply(left, right)
+            | Expression::Divide(left, right)
+            | Expression::Equal(left, right)
+            | Expression::NotEqual(left, right)
+            | Expression::LessThan(left, right)
+            | Expression::LessThanOrEqual(left, right)
+            | Expression::GreaterThan(left, right)
+            | Expression::GreaterThanOrEqual(left, right)
+            | Expression::Concatenate(left, right)
+            | Expression::Index(left, right)
+            | Expression::Append(left, right) => {
+                self.frames.push(Frame::Apply(expression));
+                self.frames.push(Frame::Compute(right));
+                self.frames.push(Frame::Compute(left));
+            }
+            Expression::Invocation(name, body, local) => {
+                return Ok(Some(Call::Invocation(name, body, local.as_ref().clone())));
+            }
+        }
+
+        Ok(None)
+    }
 
-                if condition.unwrap_boolean() {
-                    body.evaluate(environment);
-                    statement.evaluate(environment)
+    fn decide(&mut self, expression: &'a Expression) -> Result<(), RuntimeError> {
+        let decisive = matches!(expression, Expression::Or(_, _));
+
+        if pop(&mut self.values).unwrap_boolean(expression)? == decisive {
+            self.values.push(Expression::Boolean(decisive));
+        } else if let Expression::And(_, right) | Expression::Or(_, right) = expression {
+            self.frames.push(Frame::Apply(expression));
+            self.frames.push(Frame::Compute(right));
+        }
+
+        Ok(())
+    }
+
+    fn complete(
+        &mut self,
+        statement: &'a Statement,
+        environment: &mut Environment,
+        fuel: &mut Fuel,
+    ) -> Result<(), RuntimeError> {
+        let value = pop(&mut self.values);
+        self.evaluated = Statement::Nothing;
+
+        match statement {
+            Statement::Assign(name, _) => environment.insert(name, value),
+            Statement::If(condition, consequence, alternative) => {
+                if value.unwrap_boolean(condition)? {
+                    self.frames.push(Frame::Evaluate(consequence));
                 } else {
-                    Self::Nothing
+                    self.frames.push(Frame::Evaluate(alternative));
                 }
             }
-            statement @ Self::Nothing => statement.clone(),
+            Statement::For(name, _, _, _) => {
+                environment.insert(name, value);
+                self.iterate(statement, environment, fuel)?;
+            }
+            Statement::Let(name, _, body) => {
+                environment.enter(name, value);
+                self.frames.push(Frame::Leave(name));
+                self.frames.push(Frame::Evaluate(body));
+            }
+            Statement::Return(_) => {
+                for frame in self.frames.drain(..).rev() {
+                    if let Frame::Leave(name) = frame {
+                        environment.leave(name);
+                    }
+                }
+
+                self.evaluated = Statement::Return(value);
+            }
+            Statement::Print(_) => environment.print(&value),
+            _ => unreachable!("'{}' has no expression to complete", statement),
+        }
+
+        Ok(())
+    }
+
+    fn iterate(
+        &mut self,
+        statement: &'a Statement,
+        environment: &mut Environment,
+        fuel: &mut Fuel,
+    ) -> Result<(), RuntimeError> {
+        fuel.burn_loop_iteration(environment)?;
+
+        match statement {
+            Statement::While(condition, _) | Statement::For(_, _, condition, _) => {
+                self.frames.push(Frame::Test(statement));
+                self.frames.push(Frame::Compute(condition));
+            }
+            _ => unreachable!("only loops are iterated"),
         }
+
+        Ok(())
+    }
+
+    fn test(
+        &mut self,
+        statement: &'a Statement,
+        environment: &mut Environment,
+    ) -> Result<(), RuntimeError> {
+        let value = pop(&mut self.values);
+        self.evaluated = Statement::Nothing;
+
+        let (repeat, body) = match statement {
+            Statement::While(condition, body) => (value.unwrap_boolean(condition)?, body),
+            Statement::For(name, _, end, body) => {
+                let bound = bound(name, end);
+                let counter = environment.get(name)?.unwrap_number(&bound)?;
+                (counter <= value.unwrap_number(&bound)?, body)
+            }
+            _ => unreachable!("only loops are tested"),
+        };
+
+        if repeat {
+            self.frames.push(Frame::Iterate(statement));
+            self.frames.push(Frame::Evaluate(body));
+        }
+
+        Ok(())
+    }
+
+    fn repeat(
+        &mut self,
+        statement: &'a Statement,
+        environment: &mut Environment,
+        fuel: &mut Fuel,
+    ) -> Result<(), RuntimeError> {
+        self.evaluated = Statement::Nothing;
+
+        match statement {
+            Statement::While(_, _) => self.iterate(statement, environment, fuel),
+            Statement::For(name, _, _, _) => {
+                let next = environment
+                    .get(name)?
+                    .unwrap_number(&next(name))?
+                    .checked_add(1)
+                    .ok_or_else(|| RuntimeError::Overflow(next(name)))?;
+                environment.insert(name, Expression::Number(next));
+                self.iterate(statement, environment, fuel)
+            }
+            Statement::Iteration(_, next) => {
+                self.frames.push(Frame::Evaluate(next));
+                Ok(())
+            }
+            _ => unreachable!("only loops are iterated"),
+        }
+    }
+}
+
+fn apply(
+    expression: &Expression,
+    values: &mut Vec<Expression>,
+) -> Result<Expression, RuntimeError> {
+    let applied = match expression {
+        Expression::List(elements) => {
+            Expression::List(values.split_off(values.len() - elements.len()))
+        }
+        Expression::Negate(_) => Expression::Number(
+            pop(values)
+                .unwrap_number(expression)?
+                .checked_neg()
+                .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+        ),
+        Expression::Not(_) => Expression::Boolean(!pop(values).unwrap_boolean(expression)?),
+        Expression::Length(_) => pop(values).length(expression)?,
+        Expression::And(_, _) | Expression::Or(_, _) => {
+            Expression::Boolean(pop(values).unwrap_boolean(expression)?)
+        }
+        _ => {
+            let right = pop(values);
+            let left = pop(values);
+
+            match expression {
+                Expression::Add(_, _) => Expression::Number(
+                    left.unwrap_number(expression)?
+                        .checked_add(right.unwrap_number(expression)?)
+                        .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+                ),
+                Expression::Subtract(_, _) => Expression::Number(
+                    left.unwrap_number(expression)?
+                        .checked_sub(right.unwrap_number(expression)?)
+                        .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+                ),
+                Expression::Multiply(_, _) => Expression::Number(
+                    left.unwrap_number(expression)?
+                        .checked_mul(right.unwrap_number(expression)?)
+                        .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+                ),
+                Expression::Divide(_, _) => {
+                    let dividend = left.unwrap_number(expression)?;
+
+                    match right.unwrap_number(expression)? {
+                        0 => return Err(RuntimeError::DivisionByZero(expression.clone())),
+                        divisor => Expression::Number(
+                            dividend
+                                .checked_div(divisor)
+                                .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+                        ),
+                    }
+                }
+                Expression::Equal(_, _) => Expression::Boolean(left == right),
+                Expression::NotEqual(_, _) => Expression::Boolean(left != right),
+                Expression::LessThan(_, _) => Expression::Boolean(
+                    left.unwrap_number(expression)? < right.unwrap_number(expression)?,
+                ),
+                Expression::LessThanOrEqual(_, _) => Expression::Boolean(
+                    left.unwrap_number(expression)? <= right.unwrap_number(expression)?,
+                ),
+                Expression::GreaterThan(_, _) => Expression::Boolean(
+                    left.unwrap_number(expression)? > right.unwrap_number(expression)?,
+                ),
+                Expression::GreaterThanOrEqual(_, _) => Expression::Boolean(
+                    left.unwrap_number(expression)? >= right.unwrap_number(expression)?,
+                ),
+                Expression::Concatenate(_, _) => left.concatenate(&right, expression)?,
+                Expression::Index(_, _) => left.element(&right, expression)?,
+                Expression::Append(_, _) => left.append(&right, expression)?,
+                _ => unreachable!("'{}' has no operands to apply", expression),
+            }
+        }
+    };
+
+    Ok(applied)
+}
+
+fn bound(name: &str, end: &Expression) -> Expression {
+    Expression::LessThanOrEqual(
+        Box::new(Expression::Variable(name.into())),
+        Box::new(end.clone()),
+    )
+}
+
+fn next(name: &str) -> Expression {
+    Expression::Add(
+        Box::new(Expression::Variable(name.into())),
+        Box::new(Expression::Number(1)),
+    )
+}
+
+fn pop(values: &mut Vec<Expression>) -> Expression {
+    values.pop().unwrap()
+}
+
+fn invoke(
+    name: &str,
+    body: &Statement,
+    local: &mut Environment,
+    fuel: &mut Fuel,
+) -> Result<Expression, RuntimeError> {
+    fuel.enter(local)?;
+    let evaluated = body.evaluate_with_fuel(local, fuel)?;
+    fuel.leave();
+
+    match &evaluated {
+        Statement::Return(value) => Ok(value.clone()),
+        _ => Err(RuntimeError::MissingReturn(name.into())),
     }
 }
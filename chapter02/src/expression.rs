@@ -1,14 +1,21 @@
+use crate::{
+    environment::Environment, runtime_error::RuntimeError, statement::Statement,
+    value_type::ValueType,
+};
 use std::fmt::{Display, Formatter, Result};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expression {
-    Number(usize),
+    Number(i64),
     Boolean(bool),
+    String(String),
+    List(Vec<Expression>),
     Variable(String),
     Add(Box<Expression>, Box<Expression>),
     Subtract(Box<Expression>, Box<Expression>),
     Multiply(Box<Expression>, Box<Expression>),
     Divide(Box<Expression>, Box<Expression>),
+    Negate(Box<Expression>),
     Not(Box<Expression>),
     And(Box<Expression>, Box<Expression>),
     Or(Box<Expression>, Box<Expression>),
@@ -18,6 +25,13 @@ pub enum Expression {
     LessThanOrEqual(Box<Expression>, Box<Expression>),
     GreaterThan(Box<Expression>, Box<Expression>),
     GreaterThanOrEqual(Box<Expression>, Box<Expression>),
+    Concatenate(Box<Expression>, Box<Expression>),
+    Length(Box<Expression>),
+    Index(Box<Expression>, Box<Expression>),
+    Append(Box<Expression>, Box<Expression>),
+    Input,
+    Call(String, Vec<Expression>),
+    Invocation(String, Box<Statement>, Box<Environment>),
 }
 
 impl Display for Expression {
@@ -25,36 +39,234 @@ impl Display for Expression {
         match self {
             Self::Number(value) => write!(f, "{}", value),
             Self::Boolean(value) => write!(f, "{}", value),
+            Self::String(value) => {
+                write!(f, "\"")?;
+
+                for character in value.chars() {
+                    match character {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        character => write!(f, "{}", character)?,
+                    }
+                }
+
+                write!(f, "\"")
+            }
+            Self::List(elements) => write!(
+                f,
+                "[{}]",
+                elements
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
             Self::Variable(value) => write!(f, "{}", value),
-            Self::Add(left, right) => write!(f, "{} + {}", left, right),
-            Self::Subtract(left, right) => write!(f, "{} - {}", left, right),
-            Self::Multiply(left, right) => write!(f, "{} * {}", left, right),
-            Self::Divide(left, right) => write!(f, "{} / {}", left, right),
-            Self::Not(expression) => write!(f, "!{}", expression),
-            Self::And(left, right) => write!(f, "{} && {}", left, right),
-            Self::Or(left, right) => write!(f, "{} || {}", left, right),
-            Self::Equal(left, right) => write!(f, "{} == {}", left, right),
-            Self::NotEqual(left, right) => write!(f, "{} != {}", left, right),
-            Self::LessThan(left, right) => write!(f, "{} < {}", left, right),
-            Self::LessThanOrEqual(left, right) => write!(f, "{} <= {}", left, right),
-            Self::GreaterThan(left, right) => write!(f, "{} > {}", left, right),
-            Self::GreaterThanOrEqual(left, right) => write!(f, "{} >= {}", left, right),
+            Self::Add(left, right) => self.fmt_binary(f, left, "+", right),
+            Self::Subtract(left, right) => self.fmt_binary(f, left, "-", right),
+            Self::Multiply(left, right) => self.fmt_binary(f, left, "*", right),
+            Self::Divide(left, right) => self.fmt_binary(f, left, "/", right),
+            Self::Negate(expression) => match **expression {
+                Self::Number(_) => write!(f, "-({})", expression),
+                _ => {
+                    write!(f, "-")?;
+                    self.fmt_operand(f, expression, false)
+                }
+            },
+            Self::Not(expression) => {
+                write!(f, "!")?;
+                self.fmt_operand(f, expression, false)
+            }
+            Self::And(left, right) => self.fmt_binary(f, left, "&&", right),
+            Self::Or(left, right) => self.fmt_binary(f, left, "||", right),
+            Self::Equal(left, right) => self.fmt_binary(f, left, "==", right),
+            Self::NotEqual(left, right) => self.fmt_binary(f, left, "!=", right),
+            Self::LessThan(left, right) => self.fmt_binary(f, left, "<", right),
+            Self::LessThanOrEqual(left, right) => self.fmt_binary(f, left, "<=", right),
+            Self::GreaterThan(left, right) => self.fmt_binary(f, left, ">", right),
+            Self::GreaterThanOrEqual(left, right) => self.fmt_binary(f, left, ">=", right),
+            Self::Concatenate(left, right) => self.fmt_binary(f, left, "++", right),
+            Self::Length(expression) => write!(f, "length({})", expression),
+            Self::Index(list, index) => {
+                self.fmt_operand(f, list, false)?;
+                write!(f, "[{}]", index)
+            }
+            Self::Append(list, element) => write!(f, "append({}, {})", list, element),
+            Self::Input => write!(f, "input()"),
+            Self::Call(name, arguments) => write!(
+                f,
+                "{}({})",
+                name,
+                arguments
+                    .iter()
+                    .map(|argument| argument.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Invocation(name, body, environment) => {
+                write!(f, "call {} {{ {} }} with {}", name, body, environment)
+            }
         }
     }
 }
 
 impl Expression {
-    pub fn unwrap_number(&self) -> usize {
+    pub fn precedence(&self) -> usize {
+        match self {
+            Self::Or(_, _) => 1,
+            Self::And(_, _) => 2,
+            Self::Equal(_, _) | Self::NotEqual(_, _) => 3,
+            Self::LessThan(_, _)
+            | Self::LessThanOrEqual(_, _)
+            | Self::GreaterThan(_, _)
+            | Self::GreaterThanOrEqual(_, _) => 4,
+            Self::Add(_, _) | Self::Subtract(_, _) | Self::Concatenate(_, _) => 5,
+            Self::Multiply(_, _) | Self::Divide(_, _) => 6,
+            Self::Negate(_) | Self::Not(_) => 7,
+            Self::Number(_)
+            | Self::Boolean(_)
+            | Self::String(_)
+            | Self::List(_)
+            | Self::Variable(_)
+            | Self::Length(_)
+            | Self::Index(_, _)
+            | Self::Append(_, _)
+            | Self::Input
+            | Self::Call(_, _)
+            | Self::Invocation(_, _, _) => 8,
+        }
+    }
+
+    fn fmt_binary(
+        &self,
+        f: &mut Formatter<'_>,
+        left: &Expression,
+        operator: &str,
+        right: &Expression,
+    ) -> Result {
+        self.fmt_operand(f, left, false)?;
+        write!(f, " {} ", operator)?;
+        self.fmt_operand(f, right, true)
+    }
+
+    fn fmt_operand(&self, f: &mut Formatter<'_>, operand: &Expression, right: bool) -> Result {
+        let precedence = operand.precedence();
+
+        if precedence < self.precedence() || right && precedence == self.precedence() {
+            write!(f, "({})", operand)
+        } else {
+            write!(f, "{}", operand)
+        }
+    }
+
+    pub fn value_type(&self) -> Option<ValueType> {
+        match self {
+            Self::Number(_) => Some(ValueType::Number),
+            Self::Boolean(_) => Some(ValueType::Boolean),
+            Self::String(_) => Some(ValueType::String),
+            Self::List(elements) => elements
+                .iter()
+                .try_fold(ValueType::List(None), |list, element| {
+                    list.unify(&ValueType::list(element.value_type()?))
+                }),
+            _ => None,
+        }
+    }
+
+    pub fn unwrap_number(&self, within: &Expression) -> std::result::Result<i64, RuntimeError> {
+        match self {
+            Self::Number(value) => Ok(*value),
+            _ => Err(RuntimeError::TypeMismatch {
+                expected: ValueType::Number,
+                actual: self.clone(),
+                expression: within.clone(),
+            }),
+        }
+    }
+
+    pub fn unwrap_boolean(&self, within: &Expression) -> std::result::Result<bool, RuntimeError> {
         match self {
-            Self::Number(value) => *value,
-            _ => panic!("expression '{}' is not a number", self),
+            Self::Boolean(value) => Ok(*value),
+            _ => Err(RuntimeError::TypeMismatch {
+                expected: ValueType::Boolean,
+                actual: self.clone(),
+                expression: within.clone(),
+            }),
         }
     }
 
-    pub fn unwrap_boolean(&self) -> bool {
+    pub fn unwrap_string(&self, within: &Expression) -> std::result::Result<&str, RuntimeError> {
         match self {
-            Self::Boolean(value) => *value,
-            _ => panic!("expression '{}' is not a boolean", self),
+            Self::String(value) => Ok(value),
+            _ => Err(RuntimeError::TypeMismatch {
+                expected: ValueType::String,
+                actual: self.clone(),
+                expression: within.clone(),
+            }),
         }
     }
+
+    pub fn unwrap_list(
+        &self,
+        within: &Expression,
+    ) -> std::result::Result<&[Expression], RuntimeError> {
+        match self {
+            Self::List(elements) => Ok(elements),
+            _ => Err(RuntimeError::TypeMismatch {
+                expected: ValueType::List(None),
+                actual: self.clone(),
+                expression: within.clone(),
+            }),
+        }
+    }
+
+    pub fn concatenate(
+        &self,
+        other: &Expression,
+        within: &Expression,
+    ) -> std::result::Result<Expression, RuntimeError> {
+        Ok(Self::String(
+            [self.unwrap_string(within)?, other.unwrap_string(within)?].concat(),
+        ))
+    }
+
+    pub fn length(&self, within: &Expression) -> std::result::Result<Expression, RuntimeError> {
+        let length = match self {
+            Self::String(value) => value.chars().count(),
+            _ => self.unwrap_list(within)?.len(),
+        };
+
+        Ok(Self::Number(length as i64))
+    }
+
+    pub fn element(
+        &self,
+        index: &Expression,
+        within: &Expression,
+    ) -> std::result::Result<Expression, RuntimeError> {
+        let elements = self.unwrap_list(within)?;
+        let index = index.unwrap_number(within)?;
+
+        usize::try_from(index)
+            .ok()
+            .and_then(|position| elements.get(position))
+            .cloned()
+            .ok_or_else(|| RuntimeError::IndexOutOfBounds {
+                index,
+                length: elements.len(),
+                expression: within.clone(),
+            })
+    }
+
+    pub fn append(
+        &self,
+        element: &Expression,
+        within: &Expression,
+    ) -> std::result::Result<Expression, RuntimeError> {
+        let mut elements = self.unwrap_list(within)?.to_vec();
+        elements.push(element.clone());
+        Ok(Self::List(elements))
+    }
 }
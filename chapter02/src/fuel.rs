@@ -0,0 +1,91 @@
+use crate::{environment::Environment, limit::Limit, runtime_error::RuntimeError};
+
+const MAX_RECURSION_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fuel {
+    reductions: Option<usize>,
+    loop_iterations: Option<usize>,
+    recursion_depth: Option<usize>,
+}
+
+impl Fuel {
+    pub fn unlimited() -> Self {
+        Self {
+            reductions: None,
+            loop_iterations: None,
+            recursion_depth: None,
+        }
+    }
+
+    pub fn with_max_reductions(self, reductions: usize) -> Self {
+        Self {
+            reductions: Some(reductions),
+            ..self
+        }
+    }
+
+    pub fn with_max_loop_iterations(self, loop_iterations: usize) -> Self {
+        Self {
+            loop_iterations: Some(loop_iterations),
+            ..self
+        }
+    }
+
+    pub fn with_max_recursion_depth(self, recursion_depth: usize) -> Self {
+        Self {
+            recursion_depth: Some(recursion_depth),
+            ..self
+        }
+    }
+
+    pub fn burn_reduction(&mut self, environment: &Environment) -> Result<(), RuntimeError> {
+        Self::burn(&mut self.reductions, Limit::Reductions, environment)
+    }
+
+    pub fn burn_loop_iteration(&mut self, environment: &Environment) -> Result<(), RuntimeError> {
+        Self::burn(
+            &mut self.loop_iterations,
+            Limit::LoopIterations,
+            environment,
+        )
+    }
+
+    pub fn enter(&mut self, environment: &Environment) -> Result<(), RuntimeError> {
+        Self::burn(
+            &mut self.recursion_depth,
+            Limit::RecursionDepth,
+            environment,
+        )
+    }
+
+    pub fn leave(&mut self) {
+        if let Some(remaining) = &mut self.recursion_depth {
+            *remaining += 1;
+        }
+    }
+
+    fn burn(
+        tank: &mut Option<usize>,
+        limit: Limit,
+        environment: &Environment,
+    ) -> Result<(), RuntimeError> {
+        match tank {
+            Some(0) => Err(RuntimeError::OutOfFuel {
+                limit,
+                environment: environment.clone(),
+            }),
+            Some(remaining) => {
+                *remaining -= 1;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for Fuel {
+    fn default() -> Self {
+        Self::unlimited().with_max_recursion_depth(MAX_RECURSION_DEPTH)
+    }
+}
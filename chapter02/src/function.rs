@@ -0,0 +1,33 @@
+use crate::statement::Statement;
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    parameters: Vec<String>,
+    body: Statement,
+}
+
+impl Function {
+    pub fn new(parameters: Vec<String>, body: Statement) -> Self {
+        Self { parameters, body }
+    }
+
+    pub fn parameters(&self) -> &[String] {
+        &self.parameters
+    }
+
+    pub fn body(&self) -> &Statement {
+        &self.body
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "function({}) {{ {} }}",
+            self.parameters.join(", "),
+            self.body
+        )
+    }
+}
@@ -0,0 +1,462 @@
+pub mod operator;
+
+use crate::{expression::Expression, random::Random, statement::Statement, value_type::ValueType};
+use operator::Operator;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generator {
+    random: Random,
+    depth: usize,
+    expression_depth: usize,
+    variables: usize,
+    functions: usize,
+    iterations: i64,
+    weights: Vec<(Operator, usize)>,
+    pool: Vec<(String, ValueType)>,
+    scope: Vec<(String, ValueType)>,
+    signatures: Vec<(String, usize)>,
+    loops: usize,
+    fresh: usize,
+    structured: bool,
+    reading: bool,
+    input: Vec<String>,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            random: Random::new(seed),
+            depth: 3,
+            expression_depth: 2,
+            variables: 5,
+            functions: 1,
+            iterations: 3,
+            weights: Operator::all()
+                .into_iter()
+                .map(|operator| (operator, 1))
+                .collect(),
+            pool: Vec::new(),
+            scope: Vec::new(),
+            signatures: Vec::new(),
+            loops: 0,
+            fresh: 0,
+            structured: false,
+            reading: false,
+            input: Vec::new(),
+        }
+    }
+
+    pub fn with_depth(self, depth: usize) -> Self {
+        Self { depth, ..self }
+    }
+
+    pub fn with_expression_depth(self, expression_depth: usize) -> Self {
+        Self {
+            expression_depth,
+            ..self
+        }
+    }
+
+    pub fn with_variables(self, variables: usize) -> Self {
+        Self {
+            variables: variables.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_functions(self, functions: usize) -> Self {
+        Self { functions, ..self }
+    }
+
+    pub fn with_iterations(self, iterations: i64) -> Self {
+        Self {
+            iterations: iterations.max(0),
+            ..self
+        }
+    }
+
+    pub fn with_weight(mut self, operator: Operator, weight: usize) -> Self {
+        for (candidate, current) in &mut self.weights {
+            if *candidate == operator {
+                *current = weight;
+            }
+        }
+
+        self
+    }
+
+    pub fn weight(&self, operator: Operator) -> usize {
+        self.weights
+            .iter()
+            .find(|(candidate, _)| *candidate == operator)
+            .map_or(0, |(_, weight)| *weight)
+    }
+
+    pub fn input(&self) -> &[String] {
+        &self.input
+    }
+
+    pub fn program(&mut self) -> Statement {
+        self.signatures.clear();
+        self.loops = 0;
+        self.fresh = 0;
+        self.structured = false;
+        self.reading = false;
+        self.input.clear();
+
+        let mut statements = Vec::new();
+
+        for index in 0..self.functions {
+            let name = format!("f{}", index);
+            let parameters = (0..self.random.below(2) + 1)
+                .map(|parameter| format!("a{}", parameter))
+                .collect::<Vec<_>>();
+
+            self.scope = parameters
+                .iter()
+                .map(|parameter| (parameter.clone(), ValueType::Number))
+                .collect();
+            let body = self.expression(&ValueType::Number, self.expression_depth);
+            self.signatures.push((name.clone(), parameters.len()));
+
+            statements.push(Statement::Function(
+                name,
+                parameters,
+                Box::new(Statement::Return(body)),
+            ));
+        }
+
+        self.structured = self.random.chance(50);
+        self.reading = true;
+
+        let types = self.types();
+        self.pool = (0..self.variables)
+            .map(|index| (format!("v{}", index), types[index % types.len()].clone()))
+            .collect();
+        self.scope = Vec::new();
+
+        for (name, value_type) in self.pool.clone() {
+            let value = self.literal(&value_type);
+            statements.push(Statement::Assign(name.clone(), value));
+            self.scope.push((name, value_type));
+        }
+
+        statements.push(self.block(self.depth));
+
+        sequence(statements)
+    }
+
+    fn types(&self) -> Vec<ValueType> {
+        let mut types = vec![ValueType::Number, ValueType::Boolean];
+
+        if !self.structured {
+            return types;
+        }
+
+        if self.weight(Operator::Input) > 0
+            || self.weight(Operator::Concatenate) > 0
+            || self.weight(Operator::Length) > 0
+        {
+            types.push(ValueType::String);
+        }
+
+        if self.weight(Operator::Append) > 0 || self.weight(Operator::Index) > 0 {
+            types.push(ValueType::list(ValueType::Number));
+        }
+
+        types
+    }
+
+    fn block(&mut self, depth: usize) -> Statement {
+        let length = self.random.below(3) + 1;
+        let statements = (0..length).map(|_| self.statement(depth)).collect();
+
+        sequence(statements)
+    }
+
+    fn statement(&mut self, depth: usize) -> Statement {
+        let mut choices = vec![(0, 3), (1, 1)];
+
+        if depth > 0 {
+            choices.extend([(2, 1), (3, 1), (4, 1), (5, 1)]);
+        }
+
+        if self.loops > 0 {
+            choices.push((6, 1));
+        }
+
+        match *self.weighted(&choices) {
+            0 => {
+                let (name, value_type) = self.random.choose(&self.pool).clone();
+                Statement::Assign(name, self.expression(&value_type, self.expression_depth))
+            }
+            1 => {
+                let value_type = self.random.choose(&self.types()).clone();
+                Statement::Print(self.expression(&value_type, self.expression_depth))
+            }
+            2 => Statement::If(
+                self.expression(&ValueType::Boolean, self.expression_depth),
+                Box::new(self.block(depth - 1)),
+                Box::new(if self.random.chance(50) {
+                    self.block(depth - 1)
+                } else {
+                    Statement::Nothing
+                }),
+            ),
+            3 => {
+                let counter = self.fresh("i");
+                let variable = Box::new(Expression::Variable(counter.clone()));
+                let bound = Expression::Number(self.random.between(0, self.iterations));
+                let body = self.scoped(&counter, ValueType::Number, true, depth - 1);
+
+                Statement::Sequence(
+                    Arc::new(Statement::Assign(counter.clone(), Expression::Number(0))),
+                    Arc::new(Statement::While(
+                        Expression::LessThan(variable.clone(), Box::new(bound)),
+                        Box::new(Statement::Sequence(
+                            Arc::new(Statement::Assign(
+                                counter,
+                                Expression::Add(variable, Box::new(Expression::Number(1))),
+                            )),
+                            Arc::new(body),
+                        )),
+                    )),
+                )
+            }
+            4 => {
+                let counter = self.fresh("i");
+                let end = Expression::Number(self.random.between(0, self.iterations));
+                let body = self.scoped(&counter, ValueType::Number, true, depth - 1);
+
+                Statement::For(counter, Expression::Number(1), end, Box::new(body))
+            }
+            5 => {
+                let name = self.fresh("l");
+                let value_type = self.random.choose(&self.types()).clone();
+                let value = self.expression(&value_type, self.expression_depth);
+                let body = self.scoped(&name, value_type, false, depth - 1);
+
+                Statement::Let(name, value, Box::new(body))
+            }
+            _ => Statement::If(
+                self.expression(&ValueType::Boolean, self.expression_depth),
+                Box::new(if self.random.chance(50) {
+                    Statement::Break
+                } else {
+                    Statement::Continue
+                }),
+                Box::new(Statement::Nothing),
+            ),
+        }
+    }
+
+    fn scoped(
+        &mut self,
+        name: &str,
+        value_type: ValueType,
+        looping: bool,
+        depth: usize,
+    ) -> Statement {
+        self.scope.push((name.into(), value_type));
+        self.loops += usize::from(looping);
+
+        let body = self.block(depth);
+
+        self.loops -= usize::from(looping);
+        self.scope.pop();
+
+        body
+    }
+
+    fn expression(&mut self, value_type: &ValueType, depth: usize) -> Expression {
+        let operators = self
+            .weights
+            .iter()
+            .filter(|(operator, weight)| *weight > 0 && self.produces(*operator, value_type))
+            .copied()
+            .collect::<Vec<_>>();
+
+        if depth == 0 || operators.is_empty() || self.random.chance(25) {
+            return self.leaf(value_type);
+        }
+
+        let operator = *self.weighted(&operators);
+        let depth = depth - 1;
+
+        match operator {
+            Operator::Add => self.binary(Expression::Add, &ValueType::Number, depth),
+            Operator::Subtract => self.binary(Expression::Subtract, &ValueType::Number, depth),
+            Operator::Multiply => self.binary(Expression::Multiply, &ValueType::Number, depth),
+            Operator::Divide => self.binary(Expression::Divide, &ValueType::Number, depth),
+            Operator::Negate => {
+                Expression::Negate(Box::new(self.expression(&ValueType::Number, depth)))
+            }
+            Operator::Not => Expression::Not(Box::new(self.expression(&ValueType::Boolean, depth))),
+            Operator::And => self.binary(Expression::And, &ValueType::Boolean, depth),
+            Operator::Or => self.binary(Expression::Or, &ValueType::Boolean, depth),
+            Operator::Equal => {
+                let operand = self.random.choose(&self.types()).clone();
+                self.binary(Expression::Equal, &operand, depth)
+            }
+            Operator::NotEqual => {
+                let operand = self.random.choose(&self.types()).clone();
+                self.binary(Expression::NotEqual, &operand, depth)
+            }
+            Operator::LessThan => self.binary(Expression::LessThan, &ValueType::Number, depth),
+            Operator::LessThanOrEqual => {
+                self.binary(Expression::LessThanOrEqual, &ValueType::Number, depth)
+            }
+            Operator::GreaterThan => {
+                self.binary(Expression::GreaterThan, &ValueType::Number, depth)
+            }
+            Operator::GreaterThanOrEqual => {
+                self.binary(Expression::GreaterThanOrEqual, &ValueType::Number, depth)
+            }
+            Operator::Concatenate => {
+                self.binary(Expression::Concatenate, &ValueType::String, depth)
+            }
+            Operator::Length => {
+                let operand = if self.random.chance(50) {
+                    ValueType::String
+                } else {
+                    ValueType::list(ValueType::Number)
+                };
+                Expression::Length(Box::new(self.expression(&operand, depth)))
+            }
+            Operator::Index => Expression::Index(
+                Box::new(self.expression(&ValueType::list(ValueType::Number), depth)),
+                Box::new(Expression::Number(self.random.between(0, 2))),
+            ),
+            Operator::Append => Expression::Append(
+                Box::new(self.expression(&ValueType::list(ValueType::Number), depth)),
+                Box::new(self.expression(&ValueType::Number, depth)),
+            ),
+            Operator::Input => {
+                let Expression::String(line) = self.literal(&ValueType::String) else {
+                    unreachable!("string literals are strings")
+                };
+                self.input.push(line);
+                Expression::Input
+            }
+            Operator::Call => {
+                let (name, arity) = self.random.choose(&self.signatures).clone();
+                let arguments = (0..arity)
+                    .map(|_| self.expression(&ValueType::Number, depth))
+                    .collect();
+                Expression::Call(name, arguments)
+            }
+        }
+    }
+
+    fn produces(&self, operator: Operator, value_type: &ValueType) -> bool {
+        match operator {
+            Operator::Add
+            | Operator::Subtract
+            | Operator::Multiply
+            | Operator::Divide
+            | Operator::Negate => *value_type == ValueType::Number,
+            Operator::Length | Operator::Index => {
+                *value_type == ValueType::Number && self.structured
+            }
+            Operator::Call => *value_type == ValueType::Number && !self.signatures.is_empty(),
+            Operator::Not
+            | Operator::And
+            | Operator::Or
+            | Operator::Equal
+            | Operator::NotEqual
+            | Operator::LessThan
+            | Operator::LessThanOrEqual
+            | Operator::GreaterThan
+            | Operator::GreaterThanOrEqual => *value_type == ValueType::Boolean,
+            Operator::Concatenate => *value_type == ValueType::String,
+            Operator::Append => matches!(value_type, ValueType::List(_)),
+            Operator::Input => *value_type == ValueType::String && self.reading && self.loops == 0,
+        }
+    }
+
+    fn binary(
+        &mut self,
+        build: fn(Box<Expression>, Box<Expression>) -> Expression,
+        operand: &ValueType,
+        depth: usize,
+    ) -> Expression {
+        let left = self.expression(operand, depth);
+        let right = self.expression(operand, depth);
+        build(Box::new(left), Box::new(right))
+    }
+
+    fn leaf(&mut self, value_type: &ValueType) -> Expression {
+        if self.loops > 0 && matches!(value_type, ValueType::String | ValueType::List(_)) {
+            return self.literal(value_type);
+        }
+
+        let names = self
+            .scope
+            .iter()
+            .filter(|(_, candidate)| candidate == value_type)
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+
+        if names.is_empty() || self.random.chance(40) {
+            self.literal(value_type)
+        } else {
+            Expression::Variable(self.random.choose(&names).clone())
+        }
+    }
+
+    fn literal(&mut self, value_type: &ValueType) -> Expression {
+        match value_type {
+            ValueType::Number if self.random.chance(5) => {
+                Expression::Number(*self.random.choose(&[i64::MAX, i64::MIN, i64::MAX / 2]))
+            }
+            ValueType::Number => Expression::Number(self.random.between(-10, 10)),
+            ValueType::Boolean => Expression::Boolean(self.random.chance(50)),
+            ValueType::String => {
+                let length = self.random.below(4);
+                Expression::String(
+                    (0..length)
+                        .map(|_| *self.random.choose(&['a', 'b', ' ', '"', '#']))
+                        .collect(),
+                )
+            }
+            ValueType::List(_) => {
+                let length = self.random.below(3) + 1;
+                Expression::List(
+                    (0..length)
+                        .map(|_| Expression::Number(self.random.between(-10, 10)))
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    fn weighted<'a, T>(&mut self, choices: &'a [(T, usize)]) -> &'a T {
+        let total = choices.iter().map(|(_, weight)| weight).sum::<usize>();
+        let mut target = self.random.below(total);
+
+        for (choice, weight) in choices {
+            if target < *weight {
+                return choice;
+            }
+
+            target -= weight;
+        }
+
+        unreachable!("weights always cover the chosen target")
+    }
+
+    fn fresh(&mut self, prefix: &str) -> String {
+        let name = format!("{}{}", prefix, self.fresh);
+        self.fresh += 1;
+        name
+    }
+}
+
+fn sequence(statements: Vec<Statement>) -> Statement {
+    statements
+        .into_iter()
+        .rev()
+        .reduce(|rest, statement| Statement::Sequence(Arc::new(statement), Arc::new(rest)))
+        .unwrap_or(Statement::Nothing)
+}
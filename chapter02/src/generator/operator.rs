@@ -0,0 +1,79 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Negate,
+    Not,
+    And,
+    Or,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    Concatenate,
+    Length,
+    Index,
+    Append,
+    Input,
+    Call,
+}
+
+impl Operator {
+    pub fn all() -> [Self; 20] {
+        [
+            Self::Add,
+            Self::Subtract,
+            Self::Multiply,
+            Self::Divide,
+            Self::Negate,
+            Self::Not,
+            Self::And,
+            Self::Or,
+            Self::Equal,
+            Self::NotEqual,
+            Self::LessThan,
+            Self::LessThanOrEqual,
+            Self::GreaterThan,
+            Self::GreaterThanOrEqual,
+            Self::Concatenate,
+            Self::Length,
+            Self::Index,
+            Self::Append,
+            Self::Input,
+            Self::Call,
+        ]
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Add => write!(f, "+"),
+            Self::Subtract => write!(f, "-"),
+            Self::Multiply => write!(f, "*"),
+            Self::Divide => write!(f, "/"),
+            Self::Negate => write!(f, "negate"),
+            Self::Not => write!(f, "!"),
+            Self::And => write!(f, "&&"),
+            Self::Or => write!(f, "||"),
+            Self::Equal => write!(f, "=="),
+            Self::NotEqual => write!(f, "!="),
+            Self::LessThan => write!(f, "<"),
+            Self::LessThanOrEqual => write!(f, "<="),
+            Self::GreaterThan => write!(f, ">"),
+            Self::GreaterThanOrEqual => write!(f, ">="),
+            Self::Concatenate => write!(f, "++"),
+            Self::Length => write!(f, "length"),
+            Self::Index => write!(f, "index"),
+            Self::Append => write!(f, "append"),
+            Self::Input => write!(f, "input"),
+            Self::Call => write!(f, "call"),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+pub mod abstract_interpretation;
+pub mod backend;
+pub mod bytecode;
+pub mod console;
+pub mod denotation;
+pub mod desugar;
+pub mod differential;
+pub mod environment;
+pub mod evaluator;
+pub mod expression;
+pub mod fuel;
+pub mod function;
+pub mod generator;
+pub mod limit;
+pub mod machine;
+pub mod observer;
+pub mod optimizer;
+pub mod parser;
+pub mod printer;
+pub mod random;
+pub mod redex;
+pub mod reducer;
+pub mod repl;
+pub mod runtime_error;
+pub mod signature;
+pub mod statement;
+pub mod step;
+pub mod target;
+pub mod transpile_error;
+pub mod transpiler;
+pub mod type_checker;
+pub mod type_context;
+pub mod type_error;
+pub mod value_type;
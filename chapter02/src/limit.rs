@@ -0,0 +1,20 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Limit {
+    Reductions,
+    LoopIterations,
+    RecursionDepth,
+    WallClock,
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Reductions => write!(f, "reductions"),
+            Self::LoopIterations => write!(f, "loop iterations"),
+            Self::RecursionDepth => write!(f, "recursion depth"),
+            Self::WallClock => write!(f, "wall-clock time"),
+        }
+    }
+}
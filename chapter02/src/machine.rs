@@ -1,25 +1,134 @@
-use crate::{environment::Environment, reducer::Reducer, statement::Statement};
+use crate::{
+    environment::Environment,
+    expression::Expression,
+    fuel::Fuel,
+    observer::{Observer, StdoutObserver},
+    redex::Redex,
+    reducer::Reducer,
+    runtime_error::RuntimeError,
+    statement::Statement,
+    step::Step,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Machine {
     statement: Statement,
     environment: Environment,
+    fuel: Fuel,
 }
 
 impl Machine {
     pub fn new(statement: Statement, environment: Environment) -> Self {
+        Self::with_fuel(statement, environment, Fuel::default())
+    }
+
+    pub fn with_fuel(statement: Statement, environment: Environment, fuel: Fuel) -> Self {
         Self {
             statement,
             environment,
+            fuel,
+        }
+    }
+
+    pub fn statement(&self) -> &Statement {
+        &self.statement
+    }
+
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    pub fn steps(&mut self) -> Steps<'_> {
+        Steps {
+            machine: self,
+            index: 0,
+            failed: false,
         }
     }
 
-    pub fn run(&mut self) {
-        while self.statement.is_reducible() {
-            println!("{}, {}", self.statement, self.environment);
-            self.statement = self.statement.reduce(&mut self.environment);
+    pub fn run(&mut self) -> Result<(), RuntimeError> {
+        self.run_with_observer(&mut StdoutObserver)
+    }
+
+    pub fn run_with_observer(&mut self, observer: &mut impl Observer) -> Result<(), RuntimeError> {
+        observer.start(&self.statement, &self.environment);
+
+        for step in self.steps() {
+            observer.step(&step?);
         }
 
-        println!("{}, {}", self.statement, self.environment);
+        Ok(())
+    }
+
+    fn burn_fuel(&mut self, redex: &Redex) -> Result<(), RuntimeError> {
+        self.fuel.burn_reduction(&self.environment)?;
+
+        match redex {
+            Redex::Statement(Statement::While(_, _) | Statement::For(_, _, _, _)) => {
+                self.fuel.burn_loop_iteration(&self.environment)?;
+            }
+            Redex::Expression(Expression::Call(_, _)) => self.fuel.enter(&self.environment)?,
+            Redex::Expression(Expression::Invocation(_, _, _)) => self.fuel.leave(),
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct Steps<'a> {
+    machine: &'a mut Machine,
+    index: usize,
+    failed: bool,
+}
+
+impl Iterator for Steps<'_> {
+    type Item = Result<Step, RuntimeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        if !self.machine.statement.is_reducible() {
+            return match self.machine.statement {
+                Statement::Break | Statement::Continue => {
+                    self.failed = true;
+                    Some(Err(RuntimeError::OutsideLoop(Box::new(
+                        self.machine.statement.clone(),
+                    ))))
+                }
+                _ => None,
+            };
+        }
+
+        let before = self.machine.statement.clone();
+        let redex = before.redex()?;
+
+        match self
+            .machine
+            .burn_fuel(&redex)
+            .and_then(|_| before.reduce(&mut self.machine.environment))
+        {
+            Ok(after) => {
+                self.machine.statement = after.clone();
+
+                let step = Step::new(
+                    self.index,
+                    before,
+                    after,
+                    self.machine.environment.clone(),
+                    redex,
+                );
+                self.index += 1;
+
+                Some(Ok(step))
+            }
+            Err(error) => {
+                self.failed = true;
+                Some(Err(error))
+            }
+        }
     }
 }
@@ -1,82 +1,83 @@
-use environment::Environment;
-use evaluator::Evaluator;
-use expression::Expression;
-use machine::Machine;
-use statement::Statement;
-use transpiler::Transpiler;
+use anyhow::Result;
+use chapter02::{
+    observer::StdoutObserver,
+    parser::ParseError,
+    repl::{command::Command, session::Session},
+};
+use std::io::{BufRead, Write, stdin, stdout};
 
-mod environment;
-mod evaluator;
-mod expression;
-mod machine;
-mod reducer;
-mod statement;
-mod transpiler;
+const HELP: &str = "\
+<statement>          run a statement against the current environment
+:small               switch to small-step execution with the machine
+:big                 switch to big-step execution with the evaluator
+:env                 show the current environment
+:transpile [target]  show the last input translated to ruby, javascript, python or c
+:reset               forget every variable, function and the last input
+:help                show this message
+:quit                leave the repl";
 
-pub fn main() {
-    {
-        let statement = Statement::While(
-            Expression::LessThan(
-                Box::new(Expression::Variable("x".into())),
-                Box::new(Expression::Number(5)),
-            ),
-            Box::new(Statement::Assign(
-                "x".into(),
-                Expression::Multiply(
-                    Box::new(Expression::Variable("x".into())),
-                    Box::new(Expression::Number(3)),
-                ),
-            )),
-        );
+fn main() -> Result<()> {
+    let mut session = Session::new();
+    let mut source = String::new();
 
-        let mut environment = Environment::new();
-        environment.insert("x", Expression::Number(1));
+    println!("SIMPLE ({}), type :help for commands", session.mode());
 
-        Machine::new(statement, environment).run();
-    }
-
-    {
-        let statement = Statement::While(
-            Expression::LessThan(
-                Box::new(Expression::Variable("x".into())),
-                Box::new(Expression::Number(5)),
-            ),
-            Box::new(Statement::Assign(
-                "x".into(),
-                Expression::Multiply(
-                    Box::new(Expression::Variable("x".into())),
-                    Box::new(Expression::Number(3)),
-                ),
-            )),
-        );
+    loop {
+        print!("{} ", if source.is_empty() { ">>" } else { ".." });
+        stdout().flush()?;
 
-        let mut environment = Environment::new();
-        environment.insert("x", Expression::Number(1));
+        let mut line = String::new();
 
-        println!("{}, {}", statement, environment);
+        if stdin().lock().read_line(&mut line)? == 0 {
+            break;
+        }
 
-        statement.evaluate(&mut environment);
+        source.push_str(&line);
 
-        println!("{}", environment);
-    }
+        if source.trim().is_empty() {
+            source.clear();
+            continue;
+        }
 
-    {
-        let statement = Statement::While(
-            Expression::LessThan(
-                Box::new(Expression::Variable("x".into())),
-                Box::new(Expression::Number(5)),
-            ),
-            Box::new(Statement::Assign(
-                "x".into(),
-                Expression::Multiply(
-                    Box::new(Expression::Variable("x".into())),
-                    Box::new(Expression::Number(3)),
-                ),
-            )),
-        );
+        let command = match source.parse::<Command>() {
+            Ok(command) => command,
+            Err(error) => match error.downcast_ref::<ParseError>() {
+                Some(parse_error) if parse_error.is_incomplete() && !line.trim().is_empty() => {
+                    continue;
+                }
+                _ => {
+                    println!("error: {}", error);
+                    source.clear();
+                    continue;
+                }
+            },
+        };
+        source.clear();
 
-        let transpiled = statement.transpile();
-
-        println!("{}", transpiled);
+        match command {
+            Command::Execute(statement) => {
+                if let Err(error) = session.execute(statement, &mut StdoutObserver) {
+                    println!("error: {}", error);
+                }
+            }
+            Command::Switch(mode) => {
+                session.switch(mode);
+                println!("switched to {} execution", mode);
+            }
+            Command::Environment => println!("{}", session.environment()),
+            Command::Transpile(target) => match session.transpile(target) {
+                Some(Ok(program)) => print!("{}", program),
+                Some(Err(error)) => println!("error: {}", error),
+                None => println!("nothing to transpile yet"),
+            },
+            Command::Reset => {
+                session.reset();
+                println!("environment cleared");
+            }
+            Command::Help => println!("{}", HELP),
+            Command::Quit => break,
+        }
     }
+
+    Ok(())
 }
@@ -0,0 +1,33 @@
+use crate::{environment::Environment, statement::Statement, step::Step};
+
+pub trait Observer {
+    fn start(&mut self, _statement: &Statement, _environment: &Environment) {}
+    fn step(&mut self, step: &Step);
+}
+
+impl<F: FnMut(&Step)> Observer for F {
+    fn step(&mut self, step: &Step) {
+        self(step)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutObserver;
+
+impl Observer for StdoutObserver {
+    fn start(&mut self, statement: &Statement, environment: &Environment) {
+        println!("{}, {}", statement, environment);
+    }
+
+    fn step(&mut self, step: &Step) {
+        match step.call_stack() {
+            [] => println!("{}, {}", step.after(), step.environment()),
+            call_stack => println!(
+                "{}, {} in {}",
+                step.after(),
+                step.environment(),
+                call_stack.join(" > ")
+            ),
+        }
+    }
+}
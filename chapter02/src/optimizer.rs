@@ -0,0 +1,406 @@
+use crate::{
+    environment::Environment, evaluator::Evaluator, expression::Expression, statement::Statement,
+};
+use std::sync::Arc;
+
+enum Task<'a> {
+    Visit(&'a Expression),
+    Build(&'a Expression),
+}
+
+pub trait Optimizer: Sized {
+    fn optimize(&self) -> (Self, usize) {
+        let mut rewrites = 0;
+        let optimized = self.simplify(&mut rewrites);
+        (optimized, rewrites)
+    }
+
+    fn simplify(&self, rewrites: &mut usize) -> Self;
+}
+
+impl Optimizer for Expression {
+    fn simplify(&self, rewrites: &mut usize) -> Self {
+        let mut tasks = vec![Task::Visit(self)];
+        let mut simplified: Vec<(Expression, bool)> = Vec::new();
+
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Visit(
+                    expression @ (Self::Number(_)
+                    | Self::Boolean(_)
+                    | Self::String(_)
+                    | Self::Variable(_)
+                    | Self::Input
+                    | Self::Invocation(_, _, _)),
+                ) => simplified.push((expression.clone(), value(expression))),
+                Task::Visit(expression) => {
+                    tasks.push(Task::Build(expression));
+                    tasks.extend(operands(expression).into_iter().rev().map(Task::Visit));
+                }
+                Task::Build(expression) => {
+                    let operands =
+                        simplified.split_off(simplified.len() - operands(expression).len());
+                    let constant = operands.iter().all(|(_, value)| *value);
+                    let expression = rebuild(
+                        expression,
+                        operands.into_iter().map(|(operand, _)| operand).collect(),
+                    );
+
+                    let folded = match expression {
+                        Self::List(_) | Self::Call(_, _) => None,
+                        _ if constant => fold(&expression),
+                        _ => None,
+                    };
+                    let rewritten = folded.map(|folded| (folded, true)).or_else(|| {
+                        identity(&expression).map(|operand| {
+                            let value = value(&operand);
+                            (operand, value)
+                        })
+                    });
+
+                    simplified.push(match rewritten {
+                        Some(rewritten) => {
+                            *rewrites += 1;
+                            rewritten
+                        }
+                        None => {
+                            let value = constant && matches!(expression, Self::List(_));
+                            (expression, value)
+                        }
+                    });
+                }
+            }
+        }
+
+        simplified.pop().map(|(expression, _)| expression).unwrap()
+    }
+}
+
+impl Optimizer for Statement {
+    fn simplify(&self, rewrites: &mut usize) -> Self {
+        let statement = match self {
+            Self::Expression(expression) => Self::Expression(expression.simplify(rewrites)),
+            Self::Assign(name, expression) => {
+                Self::Assign(name.clone(), expression.simplify(rewrites))
+            }
+            Self::If(condition, consequence, alternative) => Self::If(
+                condition.simplify(rewrites),
+                Box::new(consequence.simplify(rewrites)),
+                Box::new(alternative.simplify(rewrites)),
+            ),
+            Self::Sequence(first, second) => return sequence(first, second, rewrites),
+            Self::While(condition, body) => Self::While(
+                condition.simplify(rewrites),
+                Box::new(body.simplify(rewrites)),
+            ),
+            Self::For(name, start, end, body) => Self::For(
+                name.clone(),
+                start.simplify(rewrites),
+                end.simplify(rewrites),
+                Box::new(body.simplify(rewrites)),
+            ),
+            Self::Iteration(body, next) => Self::Iteration(
+                Box::new(body.simplify(rewrites)),
+                Box::new(next.simplify(rewrites)),
+            ),
+            Self::Let(name, expression, body) => Self::Let(
+                name.clone(),
+                expression.simplify(rewrites),
+                Box::new(body.simplify(rewrites)),
+            ),
+            Self::Function(name, parameters, body) => Self::Function(
+                name.clone(),
+                parameters.clone(),
+                Box::new(body.simplify(rewrites)),
+            ),
+            Self::Return(expression) => Self::Return(expression.simplify(rewrites)),
+            Self::Print(expression) => Self::Print(expression.simplify(rewrites)),
+            Self::Break | Self::Continue | Self::Nothing => return self.clone(),
+        };
+
+        let rewritten = match &statement {
+            Self::If(Expression::Boolean(true), consequence, _) => Some(*consequence.clone()),
+            Self::If(Expression::Boolean(false), _, alternative) => Some(*alternative.clone()),
+            Self::While(Expression::Boolean(false), _) => Some(Self::Nothing),
+            _ => None,
+        };
+
+        match rewritten {
+            Some(rewritten) => {
+                *rewrites += 1;
+                rewritten
+            }
+            None => statement,
+        }
+    }
+}
+
+fn sequence(first: &Statement, second: &Statement, rewrites: &mut usize) -> Statement {
+    let mut statements = vec![first.simplify(rewrites)];
+    let mut rest = second;
+
+    while let Statement::Sequence(first, second) = rest {
+        statements.push(first.simplify(rewrites));
+        rest = second;
+    }
+
+    let mut statement = rest.simplify(rewrites);
+
+    while let Some(first) = statements.pop() {
+        statement = match (first, statement) {
+            (Statement::Nothing, rest) | (rest, Statement::Nothing) => {
+                *rewrites += 1;
+                rest
+            }
+            (first, rest) => Statement::Sequence(Arc::new(first), Arc::new(rest)),
+        };
+    }
+
+    statement
+}
+
+fn fold(expression: &Expression) -> Option<Expression> {
+    expression.evaluate(&mut Environment::new()).ok()
+}
+
+fn value(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Number(_) | Expression::Boolean(_) | Expression::String(_)
+    )
+}
+
+fn operands(expression: &Expression) -> Vec<&Expression> {
+    match expression {
+        Expression::Number(_)
+        | Expression::Boolean(_)
+        | Expression::String(_)
+        | Expression::Variable(_)
+        | Expression::Input
+        | Expression::Invocation(_, _, _) => Vec::new(),
+        Expression::List(operands) | Expression::Call(_, operands) => operands.iter().collect(),
+        Expression::Negate(operand) | Expression::Not(operand) | Expression::Length(operand) => {
+            vec![operand]
+        }
+        Expression::Add(left, right)
+        | Expression::Subtract(left, right)
+        | Expression::Multiply(left, right)
+        | Expression::Divide(left, right)
+        | Expression::And(left, right)
+        | Expression::Or(left, right)
+        | Expression::Equal(left, right)
+        | Expression::NotEqual(left, right)
+        | Expression::LessThan(left, right)
+        | Expression::LessThanOrEqual(left, right)
+        | Expression::GreaterThan(left, right)
+        | Expression::GreaterThanOrEqual(left, right)
+        | Expression::Concatenate(left, right)
+        | Expression::Index(left, right)
+        | Expression::Append(left, right) => vec![left, right],
+    }
+}
+
+fn rebuild(expression: &Expression, operands: Vec<Expression>) -> Expression {
+    let mut operands = operands.into_iter().map(Box::new);
+    let mut operand = || operands.next().unwrap();
+
+    match expression {
+        Expression::List(_) => Expression::List(operands.map(|operand| *operand).collect()),
+        Expression::Call(name, _) => {
+            Expression::Call(name.clone(), operands.map(|operand| *operand).collect())
+        }
+        Expression::Negate(_) => Expression::Negate(operand()),
+        Expression::Not(_) => Expression::Not(operand()),
+        Expression::Length(_) => Expression::Length(operand()),
+        Expression::Add(_, _) => Expression::Add(operand(), operand()),
+        Expression::Subtract(_, _) => Expression::Subtract(operand(), operand()),
+        Expression::Multiply(_, _) => Expression::Multiply(operand(), operand()),
+        Expression::Divide(_, _) => Expression::Divide(operand(), operand()),
+        Expression::And(_, _) => Expression::And(operand(), operand()),
+        Expression::Or(_, _) => Expression::Or(operand(), operand()),
+        Expression::Equal(_, _) => Expression::Equal(operand(), operand()),
+        Expression::NotEqual(_, _) => Expression::NotEqual(operand(), operand()),
+        Expression::LessThan(_, _) => Expression::LessThan(operand(), operand()),
+        Expression::LessThanOrEqual(_, _) => Expression::LessThanOrEqual(operand(), operand()),
+        Expression::GreaterThan(_, _) => Expression::GreaterThan(operand(), operand()),
+        Expression::GreaterThanOrEqual(_, _) => {
+            Expression::GreaterThanOrEqual(operand(), operand())
+        }
+        Expression::Concatenate(_, _) => Expression::Concatenate(operand(), operand()),
+        Expression::Index(_, _) => Expression::Index(operand(), operand()),
+        Expression::Append(_, _) => Expression::Append(operand(), operand()),
+        Expression::Number(_)
+        | Expression::Boolean(_)
+        | Expression::String(_)
+        | Expression::Variable(_)
+        | Expression::Input
+        | Expression::Invocation(_, _, _) => expression.clone(),
+    }
+}
+
+fn identity(expression: &Expression) -> Option<Expression> {
+    let operand = match expression {
+        Expression::Add(left, right) => match (&**left, &**right) {
+            (operand, Expression::Number(0)) | (Expression::Number(0), operand) => operand,
+            _ => return None,
+        },
+        Expression::Subtract(left, right) => match &**right {
+            Expression::Number(0) => left,
+            _ => return None,
+        },
+        Expression::Multiply(left, right) => match (&**left, &**right) {
+            (operand, Expression::Number(1)) | (Expression::Number(1), operand) => operand,
+            _ => return None,
+        },
+        Expression::Divide(left, right) => match &**right {
+            Expression::Number(1) => left,
+            _ => return None,
+        },
+        Expression::Not(operand) => match &**operand {
+            Expression::Not(operand) => operand,
+            _ => return None,
+        },
+        Expression::And(left, right) => match (&**left, &**right) {
+            (Expression::Boolean(false), _) => return Some(Expression::Boolean(false)),
+            (operand, Expression::Boolean(true)) | (Expression::Boolean(true), operand) => operand,
+            _ => return None,
+        },
+        Expression::Or(left, right) => match (&**left, &**right) {
+            (Expression::Boolean(true), _) => return Some(Expression::Boolean(true)),
+            (operand, Expression::Boolean(false)) | (Expression::Boolean(false), operand) => {
+                operand
+            }
+            _ => return None,
+        },
+        Expression::Concatenate(left, right) => match (&**left, &**right) {
+            (operand, Expression::String(empty)) | (Expression::String(empty), operand)
+                if empty.is_empty() =>
+            {
+                operand
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    Some(operand.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_expression, parse_statement};
+
+    fn assert_simplifies(source: &str, expected: &str, rewrites: usize) {
+        let (optimized, count) = parse_expression(source).unwrap().optimize();
+        assert_eq!(
+            (optimized.to_string().as_str(), count),
+            (expected, rewrites)
+        );
+    }
+
+    fn assert_optimizes(source: &str, expected: &str, rewrites: usize) {
+        let (optimized, count) = parse_statement(source).unwrap().optimize();
+        assert_eq!(
+            (optimized.to_string().as_str(), count),
+            (expected, rewrites)
+        );
+    }
+
+    #[test]
+    fn constant_operations_are_folded_from_the_inside_out() {
+        assert_simplifies("1 + 2 * 3", "7", 2);
+        assert_simplifies("x + 2 * 3", "x + 6", 1);
+        assert_simplifies("!(1 < 2) || x", "x", 3);
+        assert_simplifies("length(\"ab\" ++ \"c\")", "3", 2);
+        assert_simplifies("length(append([1], 2))", "2", 2);
+        assert_simplifies("[1 + 1, x]", "[2, x]", 1);
+    }
+
+    #[test]
+    fn failing_operations_are_left_for_runtime() {
+        assert_simplifies("1 / 0", "1 / 0", 0);
+        assert_simplifies("(1 / 0) + 2", "1 / 0 + 2", 0);
+        assert_simplifies("9223372036854775807 + 1", "9223372036854775807 + 1", 0);
+        assert_simplifies("[1][3]", "[1][3]", 0);
+        assert_simplifies("f(1, 2)", "f(1, 2)", 0);
+    }
+
+    #[test]
+    fn multiplying_by_one_is_removed() {
+        assert_simplifies("x * 1", "x", 1);
+        assert_simplifies("1 * x", "x", 1);
+        assert_simplifies("x * (3 - 2)", "x", 2);
+        assert_simplifies("x / 1", "x", 1);
+    }
+
+    #[test]
+    fn adding_zero_is_removed() {
+        assert_simplifies("x + 0", "x", 1);
+        assert_simplifies("0 + x", "x", 1);
+        assert_simplifies("x - 0", "x", 1);
+        assert_simplifies("x + 0 + 0 + 0", "x", 3);
+    }
+
+    #[test]
+    fn double_negation_is_removed() {
+        assert_simplifies("!!b", "b", 1);
+        assert_simplifies("!!!b", "!b", 1);
+        assert_simplifies("!!!!b", "b", 2);
+    }
+
+    #[test]
+    fn boolean_identities_are_removed() {
+        assert_simplifies("b && true", "b", 1);
+        assert_simplifies("true && b", "b", 1);
+        assert_simplifies("b || false", "b", 1);
+        assert_simplifies("false && b", "false", 1);
+        assert_simplifies("true || b", "true", 1);
+        assert_simplifies("false && 1 / 0 == 1", "false", 1);
+        assert_simplifies("b && false", "b && false", 0);
+    }
+
+    #[test]
+    fn constant_conditions_select_a_branch() {
+        assert_optimizes("if (true) { x = 1 } else { x = 2 }", "x = 1", 1);
+        assert_optimizes("if (false) { x = 1 } else { x = 2 }", "x = 2", 1);
+        assert_optimizes("if (1 < 2) { x = 1 + 1 } else { x = 2 }", "x = 2", 3);
+        assert_optimizes(
+            "if (b) { x = 1 } else { x = 2 }",
+            "if (b) { x = 1 } else { x = 2 }",
+            0,
+        );
+    }
+
+    #[test]
+    fn loops_that_never_run_are_removed() {
+        assert_optimizes("while (false) { x = 1 }", "nothing", 1);
+        assert_optimizes("while (1 > 2) { x = x + 0 }", "nothing", 3);
+        assert_optimizes("while (b) { x = 1 }", "while (b) { x = 1 }", 0);
+    }
+
+    #[test]
+    fn empty_statements_are_dropped_from_sequences() {
+        assert_optimizes("nothing; x = 1", "x = 1", 1);
+        assert_optimizes("x = 1; nothing", "x = 1", 1);
+        assert_optimizes("nothing; nothing; x = 1", "x = 1", 2);
+        assert_optimizes("while (false) { x = 1 }; x = 2", "x = 2", 2);
+        assert_optimizes("x = 1; y = 2", "x = 1; y = 2", 0);
+    }
+
+    #[test]
+    fn long_expressions_are_simplified_without_recursion() {
+        let expression = (0..5_000).fold(Expression::Variable("x".into()), |left, _| {
+            Expression::Add(Box::new(left), Box::new(Expression::Number(0)))
+        });
+        assert_eq!(
+            expression.optimize(),
+            (Expression::Variable("x".into()), 5_000)
+        );
+
+        let expression = (0..5_000).fold(Expression::Number(0), |left, _| {
+            Expression::Add(Box::new(left), Box::new(Expression::Number(1)))
+        });
+        assert_eq!(expression.optimize(), (Expression::Number(5_000), 5_000));
+    }
+}
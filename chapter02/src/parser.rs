@@ -0,0 +1,706 @@
+use crate::{expression::Expression, statement::Statement};
+use nom::{
+    Err, IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, alphanumeric1, char, digit1, multispace0, none_of},
+    combinator::{cut, eof, map, map_res, not, opt, recognize, value, verify},
+    multi::{many0, separated_list0},
+    sequence::{delimited, pair, preceded, terminated},
+};
+use std::fmt::{Display, Formatter};
+
+const KEYWORDS: [&str; 18] = [
+    "append", "break", "continue", "else", "false", "for", "function", "if", "in", "input",
+    "length", "let", "nothing", "print", "return", "to", "true", "while",
+];
+
+const MAX_NESTING: usize = 32;
+
+type BinaryOperator = fn(Box<Expression>, Box<Expression>) -> Expression;
+type UnaryOperator = fn(Box<Expression>) -> Expression;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+    incomplete: bool,
+}
+
+impl ParseError {
+    fn new(source: &str, remaining: &str) -> Self {
+        let consumed = &source[..source.len() - remaining.len()];
+        let line = consumed.matches('\n').count() + 1;
+        let column = match consumed.rfind('\n') {
+            Some(index) => consumed[index + 1..].chars().count() + 1,
+            None => consumed.chars().count() + 1,
+        };
+        let message = match remaining.chars().next() {
+            Some(character) => format!("unexpected '{}'", character),
+            None => "unexpected end of input".into(),
+        };
+
+        Self {
+            line,
+            column,
+            message,
+            incomplete: remaining.is_empty(),
+        }
+    }
+
+    fn too_deep(source: &str, remaining: &str) -> Self {
+        Self {
+            message: format!("more than {} nested brackets", MAX_NESTING),
+            incomplete: false,
+            ..Self::new(source, remaining)
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn is_incomplete(&self) -> bool {
+        self.incomplete
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn parse_statement(input: &str) -> Result<Statement, ParseError> {
+    parse_all(input, parse_sequence)
+}
+
+pub fn parse_expression(input: &str) -> Result<Expression, ParseError> {
+    parse_all(input, parse_or)
+}
+
+fn parse_all<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, Output = O, Error = nom::error::Error<&'a str>>,
+) -> Result<O, ParseError> {
+    if let Some(index) = too_deep(input) {
+        return Err(ParseError::too_deep(input, &input[index..]));
+    }
+
+    match terminated(parser, token(eof)).parse(input) {
+        Ok((_, output)) => Ok(output),
+        Err(Err::Error(error) | Err::Failure(error)) => Err(ParseError::new(input, error.input)),
+        Err(Err::Incomplete(_)) => Err(ParseError::new(input, "")),
+    }
+}
+
+fn too_deep(input: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (index, character) in input.char_indices() {
+        match character {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '(' | '[' | '{' if !quoted => {
+                depth += 1;
+
+                if depth > MAX_NESTING {
+                    return Some(index);
+                }
+            }
+            ')' | ']' | '}' if !quoted => depth = usize::saturating_sub(depth, 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn token<'a, O>(
+    parser: impl Parser<&'a str, Output = O, Error = nom::error::Error<&'a str>>,
+) -> impl Parser<&'a str, Output = O, Error = nom::error::Error<&'a str>> {
+    preceded(multispace0, parser)
+}
+
+fn symbol<'a>(
+    symbol: &'static str,
+) -> impl Parser<&'a str, Output = &'a str, Error = nom::error::Error<&'a str>> {
+    token(tag(symbol))
+}
+
+fn keyword<'a>(
+    keyword: &'static str,
+) -> impl Parser<&'a str, Output = &'a str, Error = nom::error::Error<&'a str>> {
+    token(terminated(
+        tag(keyword),
+        not(alt((alphanumeric1, tag("_")))),
+    ))
+}
+
+fn parse_identifier(input: &str) -> IResult<&str, String> {
+    map(
+        verify(
+            token(recognize(pair(
+                alt((alpha1, tag("_"))),
+                many0(alt((alphanumeric1, tag("_")))),
+            ))),
+            |identifier: &str| !KEYWORDS.contains(&identifier),
+        ),
+        String::from,
+    )
+    .parse(input)
+}
+
+fn parse_sequence(input: &str) -> IResult<&str, Statement> {
+    let (mut input, first) = parse_statement_item(input)?;
+    let mut statements = vec![first];
+
+    while let Ok((remaining, _)) = symbol(";").parse(input) {
+        input = remaining;
+
+        match parse_statement_item(input) {
+            Ok((remaining, statement)) => {
+                input = remaining;
+                statements.push(statement);
+            }
+            Err(Err::Error(_)) => break,
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok((input, Statement::sequence(statements)))
+}
+
+fn parse_statement_item(input: &str) -> IResult<&str, Statement> {
+    alt((
+        parse_block,
+        parse_if,
+        parse_while,
+        parse_for,
+        parse_let,
+        parse_function,
+        parse_return,
+        parse_print,
+        value(Statement::Break, keyword("break")),
+        value(Statement::Continue, keyword("continue")),
+        value(Statement::Nothing, keyword("nothing")),
+        parse_assign,
+        map(parse_or, Statement::Expression),
+    ))
+    .parse(input)
+}
+
+fn parse_block(input: &str) -> IResult<&str, Statement> {
+    map(
+        delimited(symbol("{"), opt(parse_sequence), symbol("}")),
+        |statement| statement.unwrap_or(Statement::Nothing),
+    )
+    .parse(input)
+}
+
+fn parse_condition(input: &str) -> IResult<&str, Expression> {
+    delimited(symbol("("), parse_or, symbol(")")).parse(input)
+}
+
+fn parse_if(input: &str) -> IResult<&str, Statement> {
+    map(
+        preceded(
+            keyword("if"),
+            cut((
+                parse_condition,
+                parse_block,
+                opt(preceded(keyword("else"), cut(parse_block))),
+            )),
+        ),
+        |(condition, consequence, alternative)| {
+            Statement::If(
+                condition,
+                Box::new(consequence),
+                Box::new(alternative.unwrap_or(Statement::Nothing)),
+            )
+        },
+    )
+    .parse(input)
+}
+
+fn parse_while(input: &str) -> IResult<&str, Statement> {
+    map(
+        preceded(keyword("while"), cut((parse_condition, parse_block))),
+        |(condition, body)| Statement::While(condition, Box::new(body)),
+    )
+    .parse(input)
+}
+
+fn parse_for(input: &str) -> IResult<&str, Statement> {
+    map(
+        preceded(
+            keyword("for"),
+            cut((
+                delimited(
+                    symbol("("),
+                    (
+                        parse_identifier,
+                        symbol("="),
+                        parse_or,
+                        keyword("to"),
+                        parse_or,
+                    ),
+                    symbol(")"),
+                ),
+                parse_block,
+            )),
+        ),
+        |((name, _, start, _, end), body)| Statement::For(name, start, end, Box::new(body)),
+    )
+    .parse(input)
+}
+
+fn parse_let(input: &str) -> IResult<&str, Statement> {
+    map(
+        preceded(
+            keyword("let"),
+            cut((
+                parse_identifier,
+                symbol("="),
+                parse_or,
+                keyword("in"),
+                parse_block,
+            )),
+        ),
+        |(name, _, expression, _, body)| Statement::Let(name, expression, Box::new(body)),
+    )
+    .parse(input)
+}
+
+fn parse_function(input: &str) -> IResult<&str, Statement> {
+    map(
+        preceded(
+            keyword("function"),
+            cut((
+                parse_identifier,
+                delimited(
+                    symbol("("),
+                    separated_list0(symbol(","), parse_identifier),
+                    symbol(")"),
+                ),
+                parse_block,
+            )),
+        ),
+        |(name, parameters, body)| Statement::Function(name, parameters, Box::new(body)),
+    )
+    .parse(input)
+}
+
+fn parse_return(input: &str) -> IResult<&str, Statement> {
+    map(
+        preceded(keyword("return"), cut(parse_or)),
+        Statement::Return,
+    )
+    .parse(input)
+}
+
+fn parse_print(input: &str) -> IResult<&str, Statement> {
+    map(preceded(keyword("print"), cut(parse_or)), Statement::Print).parse(input)
+}
+
+fn parse_assign(input: &str) -> IResult<&str, Statement> {
+    map(
+        (
+            parse_identifier,
+            terminated(symbol("="), not(char('='))),
+            cut(parse_or),
+        ),
+        |(name, _, expression)| Statement::Assign(name, expression),
+    )
+    .parse(input)
+}
+
+fn parse_left_associative<'a>(
+    input: &'a str,
+    operand: fn(&'a str) -> IResult<&'a str, Expression>,
+    operator: fn(&'a str) -> IResult<&'a str, BinaryOperator>,
+) -> IResult<&'a str, Expression> {
+    let (mut input, mut left) = operand(input)?;
+
+    loop {
+        match (operator, cut(operand)).parse(input) {
+            Ok((remaining, (operator, right))) => {
+                input = remaining;
+                left = operator(Box::new(left), Box::new(right));
+            }
+            Err(Err::Error(_)) => return Ok((input, left)),
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn parse_or(input: &str) -> IResult<&str, Expression> {
+    parse_left_associative(input, parse_and, |input| {
+        value(Expression::Or as BinaryOperator, symbol("||")).parse(input)
+    })
+}
+
+fn parse_and(input: &str) -> IResult<&str, Expression> {
+    parse_left_associative(input, parse_equality, |input| {
+        value(Expression::And as BinaryOperator, symbol("&&")).parse(input)
+    })
+}
+
+fn parse_equality(input: &str) -> IResult<&str, Expression> {
+    parse_left_associative(input, parse_comparison, |input| {
+        alt((
+            value(Expression::Equal as BinaryOperator, symbol("==")),
+            value(Expression::NotEqual as BinaryOperator, symbol("!=")),
+        ))
+        .parse(input)
+    })
+}
+
+fn parse_comparison(input: &str) -> IResult<&str, Expression> {
+    parse_left_associative(input, parse_additive, |input| {
+        alt((
+            value(Expression::LessThanOrEqual as BinaryOperator, symbol("<=")),
+            value(Expression::LessThan as BinaryOperator, symbol("<")),
+            value(
+                Expression::GreaterThanOrEqual as BinaryOperator,
+                symbol(">="),
+            ),
+            value(Expression::GreaterThan as BinaryOperator, symbol(">")),
+        ))
+        .parse(input)
+    })
+}
+
+fn parse_additive(input: &str) -> IResult<&str, Expression> {
+    parse_left_associative(input, parse_multiplicative, |input| {
+        alt((
+            value(Expression::Concatenate as BinaryOperator, symbol("++")),
+            value(Expression::Add as BinaryOperator, symbol("+")),
+            value(Expression::Subtract as BinaryOperator, symbol("-")),
+        ))
+        .parse(input)
+    })
+}
+
+fn parse_multiplicative(input: &str) -> IResult<&str, Expression> {
+    parse_left_associative(input, parse_unary, |input| {
+        alt((
+            value(Expression::Multiply as BinaryOperator, symbol("*")),
+            value(Expression::Divide as BinaryOperator, symbol("/")),
+        ))
+        .parse(input)
+    })
+}
+
+fn parse_unary(input: &str) -> IResult<&str, Expression> {
+    let mut input = input;
+    let mut operators = Vec::new();
+
+    let (input, operand) = loop {
+        let operand = map_res(token(recognize(pair(char('-'), digit1))), |digits: &str| {
+            digits.parse().map(Expression::Number)
+        })
+        .parse(input);
+
+        if let Ok(operand) = operand {
+            break operand;
+        }
+
+        let operator = alt((
+            value(Expression::Not as UnaryOperator, symbol("!")),
+            value(Expression::Negate as UnaryOperator, symbol("-")),
+        ))
+        .parse(input);
+
+        if let Ok((remaining, operator)) = operator {
+            input = remaining;
+            operators.push(operator);
+            continue;
+        }
+
+        match parse_postfix(input) {
+            Ok(operand) => break operand,
+            Err(Err::Error(error)) if !operators.is_empty() => return Err(Err::Failure(error)),
+            Err(error) => return Err(error),
+        }
+    };
+
+    let expression = operators
+        .into_iter()
+        .rev()
+        .fold(operand, |operand, operator| operator(Box::new(operand)));
+
+    Ok((input, expression))
+}
+
+fn parse_postfix(input: &str) -> IResult<&str, Expression> {
+    let (mut input, mut expression) = parse_primary(input)?;
+
+    loop {
+        match delimited(symbol("["), cut(parse_or), cut(symbol("]"))).parse(input) {
+            Ok((remaining, index)) => {
+                input = remaining;
+                expression = Expression::Index(Box::new(expression), Box::new(index));
+            }
+            Err(Err::Error(_)) => return Ok((input, expression)),
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn parse_primary(input: &str) -> IResult<&str, Expression> {
+    alt((
+        map_res(token(digit1), |digits: &str| {
+            digits.parse().map(Expression::Number)
+        }),
+        value(Expression::Boolean(true), keyword("true")),
+        value(Expression::Boolean(false), keyword("false")),
+        parse_string,
+        parse_list,
+        parse_length,
+        parse_append,
+        value(
+            Expression::Input,
+            (keyword("input"), cut((symbol("("), symbol(")")))),
+        ),
+        parse_call,
+        map(parse_identifier, Expression::Variable),
+        delimited(symbol("("), cut(parse_or), cut(symbol(")"))),
+    ))
+    .parse(input)
+}
+
+fn parse_call(input: &str) -> IResult<&str, Expression> {
+    map(
+        (
+            parse_identifier,
+            delimited(
+                symbol("("),
+                cut(separated_list0(symbol(","), parse_or)),
+                cut(symbol(")")),
+            ),
+        ),
+        |(name, arguments)| Expression::Call(name, arguments),
+    )
+    .parse(input)
+}
+
+fn parse_string(input: &str) -> IResult<&str, Expression> {
+    map(
+        preceded(
+            token(char('"')),
+            cut(terminated(
+                many0(alt((
+                    none_of("\"\\"),
+                    preceded(
+                        char('\\'),
+                        alt((
+                            value('"', char('"')),
+                            value('\\', char('\\')),
+                            value('\n', char('n')),
+                            value('\t', char('t')),
+                        )),
+                    ),
+                ))),
+                char('"'),
+            )),
+        ),
+        |characters| Expression::String(characters.into_iter().collect()),
+    )
+    .parse(input)
+}
+
+fn parse_list(input: &str) -> IResult<&str, Expression> {
+    map(
+        delimited(
+            symbol("["),
+            cut(separated_list0(symbol(","), parse_or)),
+            cut(symbol("]")),
+        ),
+        Expression::List,
+    )
+    .parse(input)
+}
+
+fn parse_length(input: &str) -> IResult<&str, Expression> {
+    map(
+        preceded(
+            keyword("length"),
+            cut(delimited(symbol("("), parse_or, symbol(")"))),
+        ),
+        |expression| Expression::Length(Box::new(expression)),
+    )
+    .parse(input)
+}
+
+fn parse_append(input: &str) -> IResult<&str, Expression> {
+    map(
+        preceded(
+            keyword("append"),
+            cut(delimited(
+                symbol("("),
+                (parse_or, symbol(","), parse_or),
+                symbol(")"),
+            )),
+        ),
+        |(list, _, element)| Expression::Append(Box::new(list), Box::new(element)),
+    )
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(value: i64) -> Box<Expression> {
+        Box::new(Expression::Number(value))
+    }
+
+    fn variable(name: &str) -> Box<Expression> {
+        Box::new(Expression::Variable(name.into()))
+    }
+
+    #[test]
+    fn binary_operators_follow_precedence() {
+        assert_eq!(
+            parse_expression("1 + 2 * 3"),
+            Ok(Expression::Add(
+                number(1),
+                Box::new(Expression::Multiply(number(2), number(3)))
+            ))
+        );
+        assert_eq!(
+            parse_expression("a || b && c == d < e + f"),
+            Ok(Expression::Or(
+                variable("a"),
+                Box::new(Expression::And(
+                    variable("b"),
+                    Box::new(Expression::Equal(
+                        variable("c"),
+                        Box::new(Expression::LessThan(
+                            variable("d"),
+                            Box::new(Expression::Add(variable("e"), variable("f")))
+                        ))
+                    ))
+                ))
+            ))
+        );
+        assert_eq!(
+            parse_expression("-x[0] * !b"),
+            Ok(Expression::Multiply(
+                Box::new(Expression::Negate(Box::new(Expression::Index(
+                    variable("x"),
+                    number(0)
+                )))),
+                Box::new(Expression::Not(variable("b")))
+            ))
+        );
+        assert_eq!(
+            parse_expression("(1 + 2) * 3"),
+            Ok(Expression::Multiply(
+                Box::new(Expression::Add(number(1), number(2))),
+                number(3)
+            ))
+        );
+    }
+
+    #[test]
+    fn binary_operators_associate_to_the_left() {
+        assert_eq!(
+            parse_expression("1 - 2 - 3"),
+            Ok(Expression::Subtract(
+                Box::new(Expression::Subtract(number(1), number(2))),
+                number(3)
+            ))
+        );
+        assert_eq!(
+            parse_expression("8 / 4 / 2"),
+            Ok(Expression::Divide(
+                Box::new(Expression::Divide(number(8), number(4))),
+                number(2)
+            ))
+        );
+        assert_eq!(
+            parse_expression("a ++ b ++ c"),
+            Ok(Expression::Concatenate(
+                Box::new(Expression::Concatenate(variable("a"), variable("b"))),
+                variable("c")
+            ))
+        );
+    }
+
+    #[test]
+    fn keywords_are_not_identifiers() {
+        assert_eq!(
+            parse_expression("iffy + input_2 + truth"),
+            Ok(Expression::Add(
+                Box::new(Expression::Add(variable("iffy"), variable("input_2"))),
+                variable("truth")
+            ))
+        );
+        assert_eq!(parse_expression("true"), Ok(Expression::Boolean(true)));
+        assert_eq!(parse_statement("nothing"), Ok(Statement::Nothing));
+        assert!(parse_statement("while = 1").is_err());
+        assert!(parse_expression("length").is_err());
+    }
+
+    #[test]
+    fn a_minus_sign_before_digits_is_part_of_the_literal() {
+        assert_eq!(parse_expression("-5"), Ok(Expression::Number(-5)));
+        assert_eq!(parse_expression("- 5"), Ok(Expression::Negate(number(5))));
+        assert_eq!(parse_expression("--5"), Ok(Expression::Negate(number(-5))));
+        assert_eq!(
+            parse_expression("1 - -5"),
+            Ok(Expression::Subtract(number(1), number(-5)))
+        );
+        assert_eq!(
+            parse_expression("1-5"),
+            Ok(Expression::Subtract(number(1), number(5)))
+        );
+        assert_eq!(
+            parse_expression("-9223372036854775808"),
+            Ok(Expression::Number(i64::MIN))
+        );
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_unexpected_input() {
+        let error = parse_statement("x = 1;\ny = * 2").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 5));
+        assert_eq!(error.message(), "unexpected '*'");
+        assert_eq!(error.to_string(), "2:5: unexpected '*'");
+        assert!(!error.is_incomplete());
+
+        let error = parse_statement("x = 1)").unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 6));
+        assert!(!error.is_incomplete());
+    }
+
+    #[test]
+    fn unfinished_input_is_incomplete() {
+        for source in [
+            "while (x < 3) {\n  x = x + 1",
+            "x = \"abc",
+            "y = 1 +",
+            "print [1, 2",
+        ] {
+            let error = parse_statement(source).unwrap_err();
+            assert!(error.is_incomplete(), "{}", source);
+            assert_eq!(error.message(), "unexpected end of input");
+        }
+    }
+}
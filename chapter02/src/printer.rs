@@ -0,0 +1,211 @@
+use crate::{expression::Expression, statement::Statement};
+
+const INDENT: &str = "    ";
+
+pub trait PrettyPrinter {
+    fn pretty_print(&self) -> String;
+}
+
+impl PrettyPrinter for Expression {
+    fn pretty_print(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl PrettyPrinter for Statement {
+    fn pretty_print(&self) -> String {
+        print_statement(self, 0)
+    }
+}
+
+fn print_statement(statement: &Statement, depth: usize) -> String {
+    let indent = INDENT.repeat(depth);
+
+    match statement {
+        Statement::If(condition, consequence, alternative) => format!(
+            "{}if ({}) {{\n{}\n{}}} else {{\n{}\n{}}}",
+            indent,
+            condition,
+            print_statement(consequence, depth + 1),
+            indent,
+            print_statement(alternative, depth + 1),
+            indent,
+        ),
+        Statement::Sequence(_, _) => statement
+            .spine()
+            .into_iter()
+            .map(|statement| match statement {
+                Statement::Sequence(_, _) => format!(
+                    "{}{{\n{}\n{}}}",
+                    indent,
+                    print_statement(statement, depth + 1),
+                    indent,
+                ),
+                _ => print_statement(statement, depth),
+            })
+            .collect::<Vec<_>>()
+            .join(";\n"),
+        Statement::While(condition, body) => format!(
+            "{}while ({}) {{\n{}\n{}}}",
+            indent,
+            condition,
+            print_statement(body, depth + 1),
+            indent,
+        ),
+        Statement::For(name, start, end, body) => format!(
+            "{}for ({} = {} to {}) {{\n{}\n{}}}",
+            indent,
+            name,
+            start,
+            end,
+            print_statement(body, depth + 1),
+            indent,
+        ),
+        Statement::Let(name, expression, body) => format!(
+            "{}let {} = {} in {{\n{}\n{}}}",
+            indent,
+            name,
+            expression,
+            print_statement(body, depth + 1),
+            indent,
+        ),
+        Statement::Function(name, parameters, body) => format!(
+            "{}function {}({}) {{\n{}\n{}}}",
+            indent,
+            name,
+            parameters.join(", "),
+            print_statement(body, depth + 1),
+            indent,
+        ),
+        statement => format!("{}{}", indent, statement),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_expression, parse_statement};
+    use std::sync::Arc;
+
+    fn number(value: i64) -> Box<Expression> {
+        Box::new(Expression::Number(value))
+    }
+
+    fn variable(name: &str) -> Box<Expression> {
+        Box::new(Expression::Variable(name.into()))
+    }
+
+    fn assert_round_trip(source: &str) {
+        let statement = parse_statement(source).unwrap();
+        assert_eq!(
+            parse_statement(&statement.pretty_print()),
+            Ok(statement.clone())
+        );
+        assert_eq!(parse_statement(&statement.to_string()), Ok(statement));
+    }
+
+    #[test]
+    fn expressions_round_trip_through_the_parser() {
+        for source in [
+            "1 + 2 * 3",
+            "(1 + 2) * 3",
+            "1 - 2 - 3",
+            "1 - (2 - 3)",
+            "8 / (4 / 2)",
+            "a || b && c",
+            "(a || b) && c",
+            "!(a && b) == !a",
+            "1 < 2 == (3 <= 4)",
+            "-5 * -x",
+            "- 5 - -5",
+            "-(-5)",
+            "--5",
+            "!!true",
+            "\"say \\\"hi\\\"\\n\\tand \\\\ bye\" ++ \"!\"",
+            "[1, [2, 3], []][0][1]",
+            "append([1, 2], -3)",
+            "length(\"abc\" ++ s) + f(1, g(2), [x])",
+        ] {
+            let expression = parse_expression(source).unwrap();
+            assert_eq!(
+                parse_expression(&expression.pretty_print()),
+                Ok(expression),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn built_expressions_keep_their_grouping() {
+        for expression in [
+            Expression::Subtract(
+                number(1),
+                Box::new(Expression::Subtract(number(2), number(3))),
+            ),
+            Expression::Subtract(
+                Box::new(Expression::Subtract(number(1), number(2))),
+                number(3),
+            ),
+            Expression::Multiply(Box::new(Expression::Add(number(1), number(2))), number(3)),
+            Expression::Subtract(number(1), number(-1)),
+            Expression::Negate(number(5)),
+            Expression::Negate(number(-5)),
+            Expression::Negate(Box::new(Expression::Negate(variable("x")))),
+            Expression::Not(Box::new(Expression::And(variable("a"), variable("b")))),
+            Expression::Index(
+                Box::new(Expression::Append(
+                    Box::new(Expression::List(vec![Expression::Number(i64::MIN)])),
+                    number(2),
+                )),
+                Box::new(Expression::Add(number(0), number(1))),
+            ),
+        ] {
+            assert_eq!(
+                parse_expression(&expression.pretty_print()),
+                Ok(expression.clone()),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn statements_round_trip_through_the_parser() {
+        for source in [
+            "x = 1; y = x + 1; print y",
+            "{ x = 1; y = 2 }; z = 3",
+            "{ { a = 1; b = 2 }; c = 3 }; d = 4",
+            "if (x < 0) { y = -x } else { y = x }",
+            "while (i < 10) { if (i == 5) { break } else { continue } }",
+            "for (i = 1 to 10) { s = s ++ \"[\" }",
+            "let x = [1, 2] in { print x[0] }",
+            "function f(a, b) { return a * -b }; print f(2, 3)",
+            "nothing",
+        ] {
+            assert_round_trip(source);
+        }
+    }
+
+    #[test]
+    fn sequences_are_printed_one_statement_per_line() {
+        let statement = Statement::Sequence(
+            Arc::new(Statement::Sequence(
+                Arc::new(Statement::Assign("a".into(), Expression::Number(1))),
+                Arc::new(Statement::Assign("b".into(), Expression::Number(2))),
+            )),
+            Arc::new(Statement::While(
+                Expression::Boolean(false),
+                Box::new(Statement::Nothing),
+            )),
+        );
+
+        assert_eq!(
+            statement.pretty_print(),
+            "{\n    a = 1;\n    b = 2\n};\nwhile (false) {\n    nothing\n}"
+        );
+
+        let long = Statement::sequence(vec![Statement::Nothing; 100_000]);
+        assert_eq!(long.pretty_print().lines().count(), 100_000);
+    }
+}
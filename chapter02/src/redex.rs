@@ -0,0 +1,17 @@
+use crate::{expression::Expression, statement::Statement};
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Redex {
+    Expression(Expression),
+    Statement(Statement),
+}
+
+impl Display for Redex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Expression(expression) => write!(f, "{}", expression),
+            Self::Statement(statement) => write!(f, "{}", statement),
+        }
+    }
+}
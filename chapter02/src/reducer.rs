@@ -1,190 +1,554 @@
-use crate::{environment::Environment, expression::Expression, statement::Statement};
+use crate::{
+    environment::Environment, expression::Expression, function::Function, redex::Redex,
+    runtime_error::RuntimeError, statement::Statement,
+};
+use std::sync::Arc;
 
-pub trait Reducer {
+pub trait Reducer: Sized {
     fn is_reducible(&self) -> bool;
-    fn reduce(&self, environment: &mut Environment) -> Self;
+    fn redex(&self) -> Option<Redex>;
+    fn reduce(&self, environment: &mut Environment) -> Result<Self, RuntimeError>;
 }
 
+#[derive(Clone, Copy)]
+enum Term<'a> {
+    Expression(&'a Expression),
+    Statement(&'a Statement),
+}
+
+enum Reduced {
+    Expression(Expression),
+    Statement(Statement),
+}
+
+type BinaryOperator = fn(Box<Expression>, Box<Expression>) -> Expression;
+
 impl Reducer for Expression {
     fn is_reducible(&self) -> bool {
-        !matches!(self, Self::Number(_) | Self::Boolean(_))
+        match self {
+            Self::Number(_) | Self::Boolean(_) | Self::String(_) => false,
+            Self::List(elements) => elements.iter().any(|element| element.is_reducible()),
+            _ => true,
+        }
+    }
+
+    fn redex(&self) -> Option<Redex> {
+        redex(Term::Expression(self))
     }
 
-    fn reduce(&self, environment: &mut Environment) -> Self {
+    fn reduce(&self, environment: &mut Environment) -> Result<Self, RuntimeError> {
+        match reduce(Term::Expression(self), environment)? {
+            Reduced::Expression(expression) => Ok(expression),
+            Reduced::Statement(statement) => {
+                unreachable!("'{}' reduced to the statement '{}'", self, statement)
+            }
+        }
+    }
+}
+
+impl Reducer for Statement {
+    fn is_reducible(&self) -> bool {
         match self {
-            expression @ Self::Number(_) => expression.clone(),
-            expression @ Self::Boolean(_) => expression.clone(),
-            Self::Variable(value) => environment.get(value),
-            Self::Add(left, right) => {
-                if left.is_reducible() {
-                    Self::Add(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::Add(left.clone(), Box::new(right.reduce(environment)))
-                } else {
-                    Self::Number(left.unwrap_number() + right.unwrap_number())
-                }
+            Self::Expression(expression) => expression.is_reducible(),
+            Self::Assign(_, _)
+            | Self::If(_, _, _)
+            | Self::Sequence(_, _)
+            | Self::While(_, _)
+            | Self::For(_, _, _, _)
+            | Self::Iteration(_, _)
+            | Self::Let(_, _, _)
+            | Self::Function(_, _, _) => true,
+            Self::Return(expression) => expression.is_reducible(),
+            Self::Print(_) => true,
+            Self::Break | Self::Continue | Self::Nothing => false,
+        }
+    }
+
+    fn redex(&self) -> Option<Redex> {
+        redex(Term::Statement(self))
+    }
+
+    fn reduce(&self, environment: &mut Environment) -> Result<Self, RuntimeError> {
+        match reduce(Term::Statement(self), environment)? {
+            Reduced::Statement(statement) => Ok(statement),
+            Reduced::Expression(expression) => {
+                unreachable!("'{}' reduced to the expression '{}'", self, expression)
             }
-            Self::Subtract(left, right) => {
-                if left.is_reducible() {
-                    Self::Subtract(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::Subtract(left.clone(), Box::new(right.reduce(environment)))
-                } else {
-                    Self::Number(left.unwrap_number() - right.unwrap_number())
-                }
+        }
+    }
+}
+
+impl Term<'_> {
+    fn is_reducible(&self) -> bool {
+        match self {
+            Self::Expression(expression) => expression.is_reducible(),
+            Self::Statement(statement) => statement.is_reducible(),
+        }
+    }
+}
+
+fn redex(term: Term<'_>) -> Option<Redex> {
+    if !term.is_reducible() {
+        return None;
+    }
+
+    let mut term = term;
+
+    while let Some((_, child)) = focus(term) {
+        term = child;
+    }
+
+    Some(match term {
+        Term::Expression(expression) => Redex::Expression(expression.clone()),
+        Term::Statement(statement) => Redex::Statement(statement.clone()),
+    })
+}
+
+fn reduce(term: Term<'_>, environment: &mut Environment) -> Result<Reduced, RuntimeError> {
+    let depth = environment.depth();
+    let mut path = Vec::new();
+    let mut locals = Vec::new();
+    let mut term = term;
+
+    while let Some((position, child)) = focus(term) {
+        match (term, child) {
+            (Term::Statement(Statement::Let(name, value, _)), Term::Statement(_)) => {
+                current(environment, &mut locals).enter(name, value.clone());
+            }
+            (Term::Expression(Expression::Invocation(_, _, local)), _) => {
+                locals.push(local.as_ref().clone());
             }
-            Self::Multiply(left, right) => {
+            _ => {}
+        }
+
+        path.push((term, position));
+        term = child;
+    }
+
+    let mut reduced = match contract(term, current(environment, &mut locals)) {
+        Ok(reduced) => reduced,
+        Err(error) => {
+            environment.unwind(depth);
+            return Err(error);
+        }
+    };
+
+    while let Some((parent, position)) = path.pop() {
+        reduced = rebuild(parent, position, reduced, environment, &mut locals);
+    }
+
+    Ok(reduced)
+}
+
+fn current<'a>(
+    environment: &'a mut Environment,
+    locals: &'a mut [Environment],
+) -> &'a mut Environment {
+    match locals.last_mut() {
+        Some(local) => local,
+        None => environment,
+    }
+}
+
+fn focus(term: Term<'_>) -> Option<(usize, Term<'_>)> {
+    match term {
+        Term::Expression(expression) => match expression {
+            Expression::Number(_)
+            | Expression::Boolean(_)
+            | Expression::String(_)
+            | Expression::Variable(_)
+            | Expression::Input => None,
+            Expression::List(operands) | Expression::Call(_, operands) => operands
+                .iter()
+                .position(|operand| operand.is_reducible())
+                .map(|index| (index, Term::Expression(&operands[index]))),
+            Expression::Negate(operand)
+            | Expression::Not(operand)
+            | Expression::Length(operand) => operand
+                .is_reducible()
+                .then_some((0, Term::Expression(operand))),
+            Expression::And(left, right) | Expression::Or(left, right) => {
                 if left.is_reducible() {
-                    Self::Multiply(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::Multiply(left.clone(), Box::new(right.reduce(environment)))
+                    Some((0, Term::Expression(left)))
+                } else if **left == Expression::Boolean(matches!(expression, Expression::And(_, _)))
+                    && right.is_reducible()
+                {
+                    Some((1, Term::Expression(right)))
                 } else {
-                    Self::Number(left.unwrap_number() * right.unwrap_number())
+                    None
                 }
             }
-            Self::Divide(left, right) => {
+            Expression::Add(left, right)
+            | Expression::Subtract(left, right)
+            | Expression::Multiply(left, right)
+            | Expression::Divide(left, right)
+            | Expression::Equal(left, right)
+            | Expression::NotEqual(left, right)
+            | Expression::LessThan(left, right)
+            | Expression::LessThanOrEqual(left, right)
+            | Expression::GreaterThan(left, right)
+            | Expression::GreaterThanOrEqual(left, right)
+            | Expression::Concatenate(left, right)
+            | Expression::Index(left, right)
+            | Expression::Append(left, right) => {
                 if left.is_reducible() {
-                    Self::Divide(Box::new(left.reduce(environment)), right.clone())
+                    Some((0, Term::Expression(left)))
                 } else if right.is_reducible() {
-                    Self::Divide(left.clone(), Box::new(right.reduce(environment)))
+                    Some((1, Term::Expression(right)))
                 } else {
-                    Self::Number(left.unwrap_number() / right.unwrap_number())
+                    None
                 }
             }
-            Self::Not(expression) => {
-                if expression.is_reducible() {
-                    Self::Not(Box::new(expression.reduce(environment)))
-                } else {
-                    Self::Boolean(!expression.unwrap_boolean())
-                }
+            Expression::Invocation(_, body, _) => {
+                body.is_reducible().then_some((0, Term::Statement(body)))
             }
-            Self::And(left, right) => {
-                if left.is_reducible() {
-                    Self::And(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::And(left.clone(), Box::new(right.reduce(environment)))
-                } else {
-                    Self::Boolean(left.unwrap_boolean() && right.unwrap_boolean())
-                }
+        },
+        Term::Statement(statement) => match statement {
+            Statement::Expression(expression)
+            | Statement::Assign(_, expression)
+            | Statement::If(expression, _, _)
+            | Statement::For(_, expression, _, _)
+            | Statement::Return(expression)
+            | Statement::Print(expression) => expression
+                .is_reducible()
+                .then_some((0, Term::Expression(expression))),
+            Statement::Sequence(first, _) => {
+                first.is_reducible().then_some((0, Term::Statement(first)))
             }
-            Self::Or(left, right) => {
-                if left.is_reducible() {
-                    Self::Or(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::Or(left.clone(), Box::new(right.reduce(environment)))
-                } else {
-                    Self::Boolean(left.unwrap_boolean() || right.unwrap_boolean())
-                }
+            Statement::Iteration(body, _) => {
+                body.is_reducible().then_some((0, Term::Statement(body)))
             }
-            Self::Equal(left, right) => {
-                if left.is_reducible() {
-                    Self::Equal(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::Equal(left.clone(), Box::new(right.reduce(environment)))
+            Statement::Let(_, expression, body) => {
+                if expression.is_reducible() {
+                    Some((0, Term::Expression(expression)))
+                } else if body.is_reducible() {
+                    Some((1, Term::Statement(body)))
                 } else {
-                    Self::Boolean(left == right)
+                    None
                 }
             }
-            Self::NotEqual(left, right) => {
-                if left.is_reducible() {
-                    Self::NotEqual(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::NotEqual(left.clone(), Box::new(right.reduce(environment)))
-                } else {
-                    Self::Boolean(left != right)
-                }
+            Statement::While(_, _)
+            | Statement::Function(_, _, _)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Nothing => None,
+        },
+    }
+}
+
+fn contract(term: Term<'_>, environment: &mut Environment) -> Result<Reduced, RuntimeError> {
+    match term {
+        Term::Expression(expression) => {
+            contract_expression(expression, environment).map(Reduced::Expression)
+        }
+        Term::Statement(statement) => {
+            contract_statement(statement, environment).map(Reduced::Statement)
+        }
+    }
+}
+
+fn contract_expression(
+    expression: &Expression,
+    environment: &mut Environment,
+) -> Result<Expression, RuntimeError> {
+    let contracted = match expression {
+        Expression::Number(_)
+        | Expression::Boolean(_)
+        | Expression::String(_)
+        | Expression::List(_) => expression.clone(),
+        Expression::Variable(value) => environment.get(value)?,
+        Expression::Input => environment.input()?,
+        Expression::Add(left, right) => Expression::Number(
+            left.unwrap_number(expression)?
+                .checked_add(right.unwrap_number(expression)?)
+                .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+        ),
+        Expression::Subtract(left, right) => Expression::Number(
+            left.unwrap_number(expression)?
+                .checked_sub(right.unwrap_number(expression)?)
+                .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+        ),
+        Expression::Multiply(left, right) => Expression::Number(
+            left.unwrap_number(expression)?
+                .checked_mul(right.unwrap_number(expression)?)
+                .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+        ),
+        Expression::Divide(left, right) => {
+            let dividend = left.unwrap_number(expression)?;
+
+            match right.unwrap_number(expression)? {
+                0 => return Err(RuntimeError::DivisionByZero(expression.clone())),
+                divisor => Expression::Number(
+                    dividend
+                        .checked_div(divisor)
+                        .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+                ),
             }
-            Self::LessThan(left, right) => {
-                if left.is_reducible() {
-                    Self::LessThan(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::LessThan(left.clone(), Box::new(right.reduce(environment)))
-                } else {
-                    Self::Boolean(left.unwrap_number() < right.unwrap_number())
-                }
+        }
+        Expression::Negate(operand) => Expression::Number(
+            operand
+                .unwrap_number(expression)?
+                .checked_neg()
+                .ok_or_else(|| RuntimeError::Overflow(expression.clone()))?,
+        ),
+        Expression::Not(operand) => Expression::Boolean(!operand.unwrap_boolean(expression)?),
+        Expression::And(left, right) => {
+            if !left.unwrap_boolean(expression)? {
+                Expression::Boolean(false)
+            } else {
+                Expression::Boolean(right.unwrap_boolean(expression)?)
             }
-            Self::LessThanOrEqual(left, right) => {
-                if left.is_reducible() {
-                    Self::LessThanOrEqual(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::LessThanOrEqual(left.clone(), Box::new(right.reduce(environment)))
-                } else {
-                    Self::Boolean(left.unwrap_number() <= right.unwrap_number())
-                }
+        }
+        Expression::Or(left, right) => {
+            if left.unwrap_boolean(expression)? {
+                Expression::Boolean(true)
+            } else {
+                Expression::Boolean(right.unwrap_boolean(expression)?)
             }
-            Self::GreaterThan(left, right) => {
-                if left.is_reducible() {
-                    Self::GreaterThan(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::GreaterThan(left.clone(), Box::new(right.reduce(environment)))
-                } else {
-                    Self::Boolean(left.unwrap_number() > right.unwrap_number())
-                }
+        }
+        Expression::Equal(left, right) => Expression::Boolean(left == right),
+        Expression::NotEqual(left, right) => Expression::Boolean(left != right),
+        Expression::LessThan(left, right) => {
+            Expression::Boolean(left.unwrap_number(expression)? < right.unwrap_number(expression)?)
+        }
+        Expression::LessThanOrEqual(left, right) => {
+            Expression::Boolean(left.unwrap_number(expression)? <= right.unwrap_number(expression)?)
+        }
+        Expression::GreaterThan(left, right) => {
+            Expression::Boolean(left.unwrap_number(expression)? > right.unwrap_number(expression)?)
+        }
+        Expression::GreaterThanOrEqual(left, right) => {
+            Expression::Boolean(left.unwrap_number(expression)? >= right.unwrap_number(expression)?)
+        }
+        Expression::Concatenate(left, right) => left.concatenate(right, expression)?,
+        Expression::Length(operand) => operand.length(expression)?,
+        Expression::Index(list, index) => list.element(index, expression)?,
+        Expression::Append(list, element) => list.append(element, expression)?,
+        Expression::Call(name, arguments) => {
+            let (function, local) = environment.call(name, arguments.clone())?;
+            Expression::Invocation(
+                name.clone(),
+                Box::new(function.body().clone()),
+                Box::new(local),
+            )
+        }
+        Expression::Invocation(name, body, _) => match &**body {
+            Statement::Return(value) => value.clone(),
+            Statement::Break | Statement::Continue => {
+                return Err(RuntimeError::OutsideLoop(body.clone()));
             }
-            Self::GreaterThanOrEqual(left, right) => {
-                if left.is_reducible() {
-                    Self::GreaterThanOrEqual(Box::new(left.reduce(environment)), right.clone())
-                } else if right.is_reducible() {
-                    Self::GreaterThanOrEqual(left.clone(), Box::new(right.reduce(environment)))
-                } else {
-                    Self::Boolean(left.unwrap_number() >= right.unwrap_number())
-                }
+            _ => return Err(RuntimeError::MissingReturn(name.clone())),
+        },
+    };
+
+    Ok(contracted)
+}
+
+fn contract_statement(
+    statement: &Statement,
+    environment: &mut Environment,
+) -> Result<Statement, RuntimeError> {
+    let contracted = match statement {
+        Statement::Assign(name, expression) => {
+            environment.insert(name, expression.clone());
+            Statement::Nothing
+        }
+        Statement::If(condition, consequence, alternative) => {
+            if condition.unwrap_boolean(condition)? {
+                *consequence.clone()
+            } else {
+                *alternative.clone()
             }
         }
+        Statement::Sequence(first, second) => match **first {
+            Statement::Return(_) | Statement::Break | Statement::Continue => (**first).clone(),
+            _ => (**second).clone(),
+        },
+        Statement::While(condition, body) => Statement::If(
+            condition.clone(),
+            Box::new(Statement::Iteration(
+                body.clone(),
+                Box::new(statement.clone()),
+            )),
+            Box::new(Statement::Nothing),
+        ),
+        Statement::For(name, start, end, body) => {
+            let counter = Box::new(Expression::Variable(name.clone()));
+            let next = Statement::For(
+                name.clone(),
+                Expression::Add(counter.clone(), Box::new(Expression::Number(1))),
+                end.clone(),
+                body.clone(),
+            );
+
+            Statement::Sequence(
+                Arc::new(Statement::Assign(name.clone(), start.clone())),
+                Arc::new(Statement::If(
+                    Expression::LessThanOrEqual(counter, Box::new(end.clone())),
+                    Box::new(Statement::Iteration(body.clone(), Box::new(next))),
+                    Box::new(Statement::Nothing),
+                )),
+            )
+        }
+        Statement::Iteration(body, next) => match **body {
+            Statement::Break => Statement::Nothing,
+            Statement::Return(_) => *body.clone(),
+            _ => *next.clone(),
+        },
+        Statement::Let(_, _, body) => match **body {
+            Statement::Return(_) | Statement::Break | Statement::Continue => *body.clone(),
+            _ => Statement::Nothing,
+        },
+        Statement::Function(name, parameters, body) => {
+            environment.define(name, Function::new(parameters.clone(), *body.clone()));
+            Statement::Nothing
+        }
+        Statement::Print(expression) => {
+            environment.print(expression);
+            Statement::Nothing
+        }
+        statement @ (Statement::Expression(_)
+        | Statement::Return(_)
+        | Statement::Break
+        | Statement::Continue
+        | Statement::Nothing) => statement.clone(),
+    };
+
+    Ok(contracted)
+}
+
+fn rebuild(
+    parent: Term<'_>,
+    position: usize,
+    reduced: Reduced,
+    environment: &mut Environment,
+    locals: &mut Vec<Environment>,
+) -> Reduced {
+    match (parent, reduced) {
+        (Term::Expression(parent), Reduced::Expression(child)) => {
+            Reduced::Expression(rebuild_expression(parent, position, child))
+        }
+        (Term::Expression(Expression::Invocation(name, _, _)), Reduced::Statement(body)) => {
+            let local = locals.pop().unwrap();
+            Reduced::Expression(Expression::Invocation(
+                name.clone(),
+                Box::new(body),
+                Box::new(local),
+            ))
+        }
+        (Term::Statement(Statement::Let(name, expression, _)), Reduced::Statement(body)) => {
+            let value = current(environment, locals)
+                .leave(name)
+                .unwrap_or_else(|| expression.clone());
+            Reduced::Statement(Statement::Let(name.clone(), value, Box::new(body)))
+        }
+        (Term::Statement(parent), Reduced::Expression(child)) => {
+            Reduced::Statement(rebuild_statement(parent, child))
+        }
+        (Term::Statement(Statement::Sequence(_, second)), Reduced::Statement(first)) => {
+            Reduced::Statement(Statement::Sequence(Arc::new(first), second.clone()))
+        }
+        (Term::Statement(Statement::Iteration(_, next)), Reduced::Statement(body)) => {
+            Reduced::Statement(Statement::Iteration(Box::new(body), next.clone()))
+        }
+        (Term::Expression(parent), Reduced::Statement(_)) => {
+            unreachable!("'{}' has no statement to rebuild", parent)
+        }
+        (Term::Statement(parent), Reduced::Statement(_)) => {
+            unreachable!("'{}' has no statement to rebuild", parent)
+        }
     }
 }
 
-impl Reducer for Statement {
-    fn is_reducible(&self) -> bool {
-        match self {
-            Self::Expression(expression) => expression.is_reducible(),
-            Self::Assign(_, _) | Self::If(_, _, _) | Self::Sequence(_, _) | Self::While(_, _) => {
-                true
-            }
-            Self::Nothing => false,
+fn rebuild_expression(parent: &Expression, position: usize, child: Expression) -> Expression {
+    match parent {
+        Expression::List(elements) => Expression::List(replace(elements, position, child)),
+        Expression::Call(name, arguments) => {
+            Expression::Call(name.clone(), replace(arguments, position, child))
+        }
+        Expression::Negate(_) => Expression::Negate(Box::new(child)),
+        Expression::Not(_) => Expression::Not(Box::new(child)),
+        Expression::Length(_) => Expression::Length(Box::new(child)),
+        Expression::Add(left, right) => binary(Expression::Add, left, right, position, child),
+        Expression::Subtract(left, right) => {
+            binary(Expression::Subtract, left, right, position, child)
+        }
+        Expression::Multiply(left, right) => {
+            binary(Expression::Multiply, left, right, position, child)
+        }
+        Expression::Divide(left, right) => binary(Expression::Divide, left, right, position, child),
+        Expression::And(left, right) => binary(Expression::And, left, right, position, child),
+        Expression::Or(left, right) => binary(Expression::Or, left, right, position, child),
+        Expression::Equal(left, right) => binary(Expression::Equal, left, right, position, child),
+        Expression::NotEqual(left, right) => {
+            binary(Expression::NotEqual, left, right, position, child)
+        }
+        Expression::LessThan(left, right) => {
+            binary(Expression::LessThan, left, right, position, child)
+        }
+        Expression::LessThanOrEqual(left, right) => {
+            binary(Expression::LessThanOrEqual, left, right, position, child)
+        }
+        Expression::GreaterThan(left, right) => {
+            binary(Expression::GreaterThan, left, right, position, child)
+        }
+        Expression::GreaterThanOrEqual(left, right) => {
+            binary(Expression::GreaterThanOrEqual, left, right, position, child)
+        }
+        Expression::Concatenate(left, right) => {
+            binary(Expression::Concatenate, left, right, position, child)
+        }
+        Expression::Index(left, right) => binary(Expression::Index, left, right, position, child),
+        Expression::Append(left, right) => binary(Expression::Append, left, right, position, child),
+        Expression::Number(_)
+        | Expression::Boolean(_)
+        | Expression::String(_)
+        | Expression::Variable(_)
+        | Expression::Input
+        | Expression::Invocation(_, _, _) => {
+            unreachable!("'{}' has no operand to rebuild", parent)
         }
     }
+}
 
-    fn reduce(&self, environment: &mut Environment) -> Self {
-        match self {
-            Self::Expression(expression) => {
-                if expression.is_reducible() {
-                    Self::Expression(expression.reduce(environment))
-                } else {
-                    Self::Expression(expression.clone())
-                }
-            }
-            Self::Assign(name, expression) => {
-                if expression.is_reducible() {
-                    Self::Assign(name.into(), expression.reduce(environment))
-                } else {
-                    environment.insert(name, expression.clone());
-                    Self::Nothing
-                }
-            }
-            Self::If(condition, consequence, alternative) => {
-                if condition.is_reducible() {
-                    Self::If(
-                        condition.reduce(environment),
-                        consequence.clone(),
-                        alternative.clone(),
-                    )
-                } else if condition.unwrap_boolean() {
-                    *consequence.clone()
-                } else {
-                    *alternative.clone()
-                }
-            }
-            Self::Sequence(first, second) => match *first.clone() {
-                Self::Nothing => *second.clone(),
-                _ => Self::Sequence(Box::new(first.reduce(environment)), second.clone()),
-            },
-            statement @ Self::While(condition, body) => Self::If(
-                condition.clone(),
-                Box::new(Self::Sequence(body.clone(), Box::new(statement.clone()))),
-                Box::new(Self::Nothing),
-            ),
-            statement @ Self::Nothing => statement.clone(),
+fn rebuild_statement(parent: &Statement, child: Expression) -> Statement {
+    match parent {
+        Statement::Expression(_) => Statement::Expression(child),
+        Statement::Assign(name, _) => Statement::Assign(name.clone(), child),
+        Statement::If(_, consequence, alternative) => {
+            Statement::If(child, consequence.clone(), alternative.clone())
+        }
+        Statement::For(name, _, end, body) => {
+            Statement::For(name.clone(), child, end.clone(), body.clone())
         }
+        Statement::Let(name, _, body) => Statement::Let(name.clone(), child, body.clone()),
+        Statement::Return(_) => Statement::Return(child),
+        Statement::Print(_) => Statement::Print(child),
+        Statement::Sequence(_, _)
+        | Statement::While(_, _)
+        | Statement::Iteration(_, _)
+        | Statement::Function(_, _, _)
+        | Statement::Break
+        | Statement::Continue
+        | Statement::Nothing => unreachable!("'{}' has no expression to rebuild", parent),
+    }
+}
+
+fn replace(operands: &[Expression], position: usize, operand: Expression) -> Vec<Expression> {
+    let mut operands = operands.to_vec();
+    operands[position] = operand;
+    operands
+}
+
+fn binary(
+    operator: BinaryOperator,
+    left: &Expression,
+    right: &Expression,
+    position: usize,
+    operand: Expression,
+) -> Expression {
+    match position {
+        0 => operator(Box::new(operand), Box::new(right.clone())),
+        _ => operator(Box::new(left.clone()), Box::new(operand)),
     }
 }
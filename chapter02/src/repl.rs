@@ -0,0 +1,3 @@
+pub mod command;
+pub mod mode;
+pub mod session;
@@ -0,0 +1,39 @@
+use super::mode::Mode;
+use crate::{parser::parse_statement, statement::Statement, target::Target};
+use anyhow::{Error, bail};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Execute(Statement),
+    Switch(Mode),
+    Environment,
+    Transpile(Target),
+    Reset,
+    Help,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Error> {
+        let input = input.trim();
+
+        if !input.starts_with(':') {
+            return Ok(Self::Execute(parse_statement(input)?));
+        }
+
+        match input.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [":small"] => Ok(Self::Switch(Mode::SmallStep)),
+            [":big"] => Ok(Self::Switch(Mode::BigStep)),
+            [":env"] => Ok(Self::Environment),
+            [":transpile"] => Ok(Self::Transpile(Target::Ruby)),
+            [":transpile", target] => Ok(Self::Transpile(target.parse()?)),
+            [":reset"] => Ok(Self::Reset),
+            [":help"] => Ok(Self::Help),
+            [":quit"] => Ok(Self::Quit),
+            _ => bail!("unknown command '{}'", input),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Mode {
+    SmallStep,
+    #[default]
+    BigStep,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::SmallStep => write!(f, "small-step"),
+            Self::BigStep => write!(f, "big-step"),
+        }
+    }
+}
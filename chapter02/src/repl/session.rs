@@ -0,0 +1,79 @@
+use super::mode::Mode;
+use crate::{
+    environment::Environment, evaluator::Evaluator, machine::Machine, observer::Observer,
+    runtime_error::RuntimeError, statement::Statement, target::Target,
+    transpile_error::TranspileError,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    environment: Environment,
+    mode: Mode,
+    last: Option<(Statement, Environment)>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::with_environment(Environment::new())
+    }
+
+    pub fn with_environment(environment: Environment) -> Self {
+        Self {
+            environment,
+            mode: Mode::default(),
+            last: None,
+        }
+    }
+
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn switch(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    pub fn execute(
+        &mut self,
+        statement: Statement,
+        observer: &mut impl Observer,
+    ) -> Result<(), RuntimeError> {
+        self.last = Some((statement.clone(), self.environment.clone()));
+
+        self.environment = match self.mode {
+            Mode::SmallStep => {
+                let mut machine = Machine::new(statement, self.environment.clone());
+                machine.run_with_observer(observer)?;
+                machine.environment().clone()
+            }
+            Mode::BigStep => {
+                let mut environment = self.environment.clone();
+                statement.evaluate(&mut environment)?;
+                environment
+            }
+        };
+
+        Ok(())
+    }
+
+    pub fn transpile(&self, target: Target) -> Option<Result<String, TranspileError>> {
+        self.last
+            .as_ref()
+            .map(|(statement, environment)| target.backend().program(statement, environment))
+    }
+
+    pub fn reset(&mut self) {
+        self.environment = self.environment.isolated();
+        self.last = None;
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
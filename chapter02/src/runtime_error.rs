@@ -0,0 +1,83 @@
+use crate::{
+    environment::Environment, expression::Expression, limit::Limit, statement::Statement,
+    value_type::ValueType,
+};
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeError {
+    UnboundVariable(String),
+    TypeMismatch {
+        expected: ValueType,
+        actual: Expression,
+        expression: Expression,
+    },
+    DivisionByZero(Expression),
+    Overflow(Expression),
+    IndexOutOfBounds {
+        index: i64,
+        length: usize,
+        expression: Expression,
+    },
+    UndefinedFunction(String),
+    ArityMismatch {
+        name: String,
+        expected: usize,
+        actual: usize,
+    },
+    MissingReturn(String),
+    OutsideLoop(Box<Statement>),
+    EndOfInput,
+    OutOfFuel {
+        limit: Limit,
+        environment: Environment,
+    },
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::UnboundVariable(name) => write!(f, "variable '{}' is not defined", name),
+            Self::TypeMismatch {
+                expected,
+                actual,
+                expression,
+            } => write!(
+                f,
+                "expected a {} but found '{}' in '{}'",
+                expected, actual, expression
+            ),
+            Self::DivisionByZero(expression) => write!(f, "division by zero in '{}'", expression),
+            Self::Overflow(expression) => write!(f, "arithmetic overflow in '{}'", expression),
+            Self::IndexOutOfBounds {
+                index,
+                length,
+                expression,
+            } => write!(
+                f,
+                "index {} is out of bounds for a list of length {} in '{}'",
+                index, length, expression
+            ),
+            Self::UndefinedFunction(name) => write!(f, "function '{}' is not defined", name),
+            Self::ArityMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "function '{}' expects {} arguments but was given {}",
+                name, expected, actual
+            ),
+            Self::MissingReturn(name) => {
+                write!(f, "function '{}' finished without returning a value", name)
+            }
+            Self::OutsideLoop(statement) => write!(f, "'{}' used outside of a loop", statement),
+            Self::EndOfInput => write!(f, "no more input to read"),
+            Self::OutOfFuel { limit, environment } => {
+                write!(f, "ran out of {} with {}", limit, environment)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuntimeError {}
@@ -0,0 +1,37 @@
+use crate::value_type::ValueType;
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    parameters: Vec<ValueType>,
+    result: Option<ValueType>,
+}
+
+impl Signature {
+    pub fn new(parameters: Vec<ValueType>, result: Option<ValueType>) -> Self {
+        Self { parameters, result }
+    }
+
+    pub fn parameters(&self) -> &[ValueType] {
+        &self.parameters
+    }
+
+    pub fn result(&self) -> Option<ValueType> {
+        self.result.clone()
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|parameter| parameter.to_string())
+            .collect::<Vec<_>>();
+
+        match &self.result {
+            Some(result) => write!(f, "({}) -> {}", parameters.join(", "), result),
+            None => write!(f, "({}) -> ?", parameters.join(", ")),
+        }
+    }
+}
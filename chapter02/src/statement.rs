@@ -1,16 +1,95 @@
 use crate::expression::Expression;
-use std::fmt::{Display, Formatter, Result};
+use std::{
+    fmt::{Display, Formatter, Result},
+    mem,
+    sync::Arc,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Statement {
     Expression(Expression),
     Assign(String, Expression),
     If(Expression, Box<Statement>, Box<Statement>),
-    Sequence(Box<Statement>, Box<Statement>),
+    Sequence(Arc<Statement>, Arc<Statement>),
     While(Expression, Box<Statement>),
+    For(String, Expression, Expression, Box<Statement>),
+    Iteration(Box<Statement>, Box<Statement>),
+    Break,
+    Continue,
+    Let(String, Expression, Box<Statement>),
+    Function(String, Vec<String>, Box<Statement>),
+    Return(Expression),
+    Print(Expression),
     Nothing,
 }
 
+impl Statement {
+    pub fn sequence(statements: Vec<Statement>) -> Self {
+        statements
+            .into_iter()
+            .rev()
+            .reduce(|second, first| Self::Sequence(Arc::new(first), Arc::new(second)))
+            .unwrap_or(Self::Nothing)
+    }
+
+    pub fn spine(&self) -> Vec<&Statement> {
+        let mut statements = Vec::new();
+        let mut statement = self;
+
+        while let Self::Sequence(first, second) = statement {
+            statements.push(&**first);
+            statement = second;
+        }
+
+        statements.push(statement);
+        statements
+    }
+
+    fn detach(&mut self, pending: &mut Vec<Statement>) {
+        match self {
+            Self::Sequence(first, second) => {
+                for part in [first, second] {
+                    if let Some(part) = Arc::get_mut(part) {
+                        detach(part, pending);
+                    }
+                }
+            }
+            Self::If(_, first, second) | Self::Iteration(first, second) => {
+                detach(first, pending);
+                detach(second, pending);
+            }
+            Self::While(_, body)
+            | Self::For(_, _, _, body)
+            | Self::Let(_, _, body)
+            | Self::Function(_, _, body) => detach(body, pending),
+            Self::Expression(_)
+            | Self::Assign(_, _)
+            | Self::Break
+            | Self::Continue
+            | Self::Return(_)
+            | Self::Print(_)
+            | Self::Nothing => {}
+        }
+    }
+}
+
+impl Drop for Statement {
+    fn drop(&mut self) {
+        let mut pending = Vec::new();
+        self.detach(&mut pending);
+
+        while let Some(mut statement) = pending.pop() {
+            statement.detach(&mut pending);
+        }
+    }
+}
+
+fn detach(statement: &mut Statement, pending: &mut Vec<Statement>) {
+    if !matches!(statement, Statement::Nothing) {
+        pending.push(mem::replace(statement, Statement::Nothing));
+    }
+}
+
 impl Display for Statement {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -23,8 +102,42 @@ impl Display for Statement {
                     condition, consequence, alternative
                 )
             }
-            Self::Sequence(first, second) => write!(f, "{}; {}", first, second),
+            Self::Sequence(first, second) => {
+                let (mut first, mut second) = (first, second);
+
+                loop {
+                    match **first {
+                        Self::Sequence(_, _) => write!(f, "{{ {} }}; ", first)?,
+                        _ => write!(f, "{}; ", first)?,
+                    }
+
+                    match &**second {
+                        Self::Sequence(next, rest) => (first, second) = (next, rest),
+                        last => return write!(f, "{}", last),
+                    }
+                }
+            }
             Self::While(condition, body) => write!(f, "while ({}) {{ {} }}", condition, body),
+            Self::For(name, start, end, body) => {
+                write!(f, "for ({} = {} to {}) {{ {} }}", name, start, end, body)
+            }
+            Self::Iteration(body, next) => {
+                write!(f, "iteration {{ {} }} then {{ {} }}", body, next)
+            }
+            Self::Break => write!(f, "break"),
+            Self::Continue => write!(f, "continue"),
+            Self::Let(name, expression, body) => {
+                write!(f, "let {} = {} in {{ {} }}", name, expression, body)
+            }
+            Self::Function(name, parameters, body) => write!(
+                f,
+                "function {}({}) {{ {} }}",
+                name,
+                parameters.join(", "),
+                body
+            ),
+            Self::Return(expression) => write!(f, "return {}", expression),
+            Self::Print(expression) => write!(f, "print {}", expression),
             Self::Nothing => write!(f, "nothing"),
         }
     }
@@ -0,0 +1,129 @@
+use crate::{
+    environment::Environment, expression::Expression, redex::Redex, reducer::Reducer,
+    statement::Statement,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    index: usize,
+    before: Statement,
+    after: Statement,
+    environment: Environment,
+    redex: Redex,
+    call_stack: Vec<String>,
+}
+
+impl Step {
+    pub fn new(
+        index: usize,
+        before: Statement,
+        after: Statement,
+        environment: Environment,
+        redex: Redex,
+    ) -> Self {
+        let mut call_stack = Vec::new();
+        statement_calls(&after, &mut call_stack);
+
+        Self {
+            index,
+            before,
+            after,
+            environment,
+            redex,
+            call_stack,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn before(&self) -> &Statement {
+        &self.before
+    }
+
+    pub fn after(&self) -> &Statement {
+        &self.after
+    }
+
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    pub fn redex(&self) -> &Redex {
+        &self.redex
+    }
+
+    pub fn call_stack(&self) -> &[String] {
+        &self.call_stack
+    }
+}
+
+fn statement_calls(statement: &Statement, call_stack: &mut Vec<String>) {
+    let mut statement = statement;
+
+    loop {
+        match statement {
+            Statement::Expression(expression)
+            | Statement::Assign(_, expression)
+            | Statement::If(expression, _, _)
+            | Statement::While(expression, _)
+            | Statement::For(_, expression, _, _)
+            | Statement::Return(expression)
+            | Statement::Print(expression) => return expression_calls(expression, call_stack),
+            Statement::Sequence(first, _) => statement = first,
+            Statement::Iteration(first, _) => statement = first,
+            Statement::Let(_, expression, body) => {
+                if expression.is_reducible() {
+                    return expression_calls(expression, call_stack);
+                }
+
+                statement = body;
+            }
+            Statement::Function(_, _, _)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Nothing => return,
+        }
+    }
+}
+
+fn expression_calls(expression: &Expression, call_stack: &mut Vec<String>) {
+    match expression {
+        Expression::Number(_)
+        | Expression::Boolean(_)
+        | Expression::String(_)
+        | Expression::Variable(_)
+        | Expression::Input => {}
+        Expression::Negate(operand) | Expression::Not(operand) | Expression::Length(operand) => {
+            expression_calls(operand, call_stack)
+        }
+        Expression::Add(left, right)
+        | Expression::Subtract(left, right)
+        | Expression::Multiply(left, right)
+        | Expression::Divide(left, right)
+        | Expression::And(left, right)
+        | Expression::Or(left, right)
+        | Expression::Equal(left, right)
+        | Expression::NotEqual(left, right)
+        | Expression::LessThan(left, right)
+        | Expression::LessThanOrEqual(left, right)
+        | Expression::GreaterThan(left, right)
+        | Expression::GreaterThanOrEqual(left, right)
+        | Expression::Concatenate(left, right)
+        | Expression::Index(left, right)
+        | Expression::Append(left, right) => {
+            expression_calls(left, call_stack);
+            expression_calls(right, call_stack);
+        }
+        Expression::List(expressions) | Expression::Call(_, expressions) => {
+            for expression in expressions {
+                expression_calls(expression, call_stack);
+            }
+        }
+        Expression::Invocation(name, body, _) => {
+            call_stack.push(name.clone());
+            statement_calls(body, call_stack);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::backend::{
+    Backend, c::CBackend, javascript::JavaScriptBackend, python::PythonBackend, ruby::RubyBackend,
+};
+use anyhow::{Error, bail};
+use std::{
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    Ruby,
+    JavaScript,
+    Python,
+    C,
+}
+
+impl Target {
+    pub fn backend(&self) -> &'static dyn Backend {
+        match self {
+            Self::Ruby => &RubyBackend,
+            Self::JavaScript => &JavaScriptBackend,
+            Self::Python => &PythonBackend,
+            Self::C => &CBackend,
+        }
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Ruby => write!(f, "ruby"),
+            Self::JavaScript => write!(f, "javascript"),
+            Self::Python => write!(f, "python"),
+            Self::C => write!(f, "c"),
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = Error;
+
+    fn from_str(name: &str) -> std::result::Result<Self, Error> {
+        match name {
+            "ruby" | "rb" => Ok(Self::Ruby),
+            "javascript" | "js" => Ok(Self::JavaScript),
+            "python" | "py" => Ok(Self::Python),
+            "c" => Ok(Self::C),
+            _ => bail!("unknown target '{}'", name),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{expression::Expression, statement::Statement, target::Target, type_error::TypeError};
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranspileError {
+    Unsupported {
+        target: Target,
+        expression: Expression,
+    },
+    Intermediate {
+        target: Target,
+        statement: Box<Statement>,
+    },
+    IllTyped {
+        target: Target,
+        errors: Vec<TypeError>,
+    },
+    Unsigned {
+        target: Target,
+        name: String,
+    },
+}
+
+impl Display for TranspileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Unsupported { target, expression } => write!(
+                f,
+                "the {} backend does not support '{}'",
+                target, expression
+            ),
+            Self::Intermediate { target, statement } => write!(
+                f,
+                "the {} backend cannot translate '{}' because it only arises while reducing a program",
+                target, statement
+            ),
+            Self::IllTyped { target, errors } => write!(
+                f,
+                "the {} backend needs a well-typed program: {}",
+                target,
+                errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+            Self::Unsigned { target, name } => write!(
+                f,
+                "the {} backend cannot type function '{}' because it is never called",
+                target, name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TranspileError {}
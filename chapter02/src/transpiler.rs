@@ -1,4 +1,11 @@
-use crate::{expression::Expression, statement::Statement};
+use crate::{
+    desugar::Desugarer, environment::Environment, expression::Expression, statement::Statement,
+};
+
+const CHECKED: &str = "-> v { v.between?(-2**63, 2**63 - 1) ? v : raise('arithmetic overflow') }";
+const DIVIDE: &str = "-> l, r { r.zero? ? raise('division by zero') : (l.to_r / r).truncate }";
+const CONFINED: &str = "->(f; d, r, b, c) { d = Object.new; r = nil; b = catch(:break) { c = catch(:continue) { r = f.(); d }; c.equal?(d) ? d : raise(\"'continue' used outside of a loop\") }; b.equal?(d) ? r : raise(\"'break' used outside of a loop\") }";
+const INDEX: &str = "-> l, i { i.between?(0, l.length - 1) ? l[i] : raise('index out of bounds') }";
 
 pub trait Transpiler {
     fn transpile(&self) -> String;
@@ -9,28 +16,47 @@ impl Transpiler for Expression {
         match self {
             Self::Number(value) => format!("-> e {{ {} }}", value),
             Self::Boolean(value) => format!("-> e {{ {} }}", value),
+            Self::String(_) => format!("-> e {{ {} }}", literal(self)),
+            Self::List(elements) => format!(
+                "-> e {{ [{}] }}",
+                elements
+                    .iter()
+                    .map(|element| format!("({}).call(e)", element.transpile()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
             Self::Variable(value) => format!("-> e {{ e[:{}] }}", value),
             Self::Add(left, right) => format!(
-                "-> e {{ ({}).call(e) + ({}).call(e) }}",
+                "-> e {{ ({}).(({}).call(e) + ({}).call(e)) }}",
+                CHECKED,
                 left.transpile(),
                 right.transpile()
             ),
             Self::Subtract(left, right) => format!(
-                "-> e {{ ({}).call(e) - ({}).call(e) }}",
+                "-> e {{ ({}).(({}).call(e) - ({}).call(e)) }}",
+                CHECKED,
                 left.transpile(),
                 right.transpile()
             ),
             Self::Multiply(left, right) => format!(
-                "-> e {{ ({}).call(e) * ({}).call(e) }}",
+                "-> e {{ ({}).(({}).call(e) * ({}).call(e)) }}",
+                CHECKED,
                 left.transpile(),
                 right.transpile()
             ),
             Self::Divide(left, right) => format!(
-                "-> e {{ ({}).call(e) / ({}).call(e) }}",
+                "-> e {{ ({}).(({}).(({}).call(e), ({}).call(e))) }}",
+                CHECKED,
+                DIVIDE,
                 left.transpile(),
                 right.transpile()
             ),
-            Self::Not(expression) => format!("-> e {{ !({}).call(e) }}", expression),
+            Self::Negate(expression) => format!(
+                "-> e {{ ({}).(-({}).call(e)) }}",
+                CHECKED,
+                expression.transpile()
+            ),
+            Self::Not(expression) => format!("-> e {{ !({}).call(e) }}", expression.transpile()),
             Self::And(left, right) => format!(
                 "-> e {{ ({}).call(e) && ({}).call(e) }}",
                 left.transpile(),
@@ -71,6 +97,43 @@ impl Transpiler for Expression {
                 left.transpile(),
                 right.transpile()
             ),
+            Self::Concatenate(left, right) => format!(
+                "-> e {{ ({}).call(e) + ({}).call(e) }}",
+                left.transpile(),
+                right.transpile()
+            ),
+            Self::Length(expression) => {
+                format!("-> e {{ ({}).call(e).length }}", expression.transpile())
+            }
+            Self::Index(list, index) => format!(
+                "-> e {{ ({}).(({}).call(e), ({}).call(e)) }}",
+                INDEX,
+                list.transpile(),
+                index.transpile()
+            ),
+            Self::Append(list, element) => format!(
+                "-> e {{ ({}).call(e) + [({}).call(e)] }}",
+                list.transpile(),
+                element.transpile()
+            ),
+            Self::Input => "-> e { ($stdin.gets or raise('no more input to read')).chomp }".into(),
+            Self::Call(name, arguments) => format!(
+                "-> e {{ catch(:return) {{ (e[\"{}\"]).call(e, [{}]); {} }}.first }}",
+                name,
+                arguments
+                    .iter()
+                    .map(|argument| format!("({}).call(e)", argument.transpile()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                missing_return(name),
+            ),
+            Self::Invocation(name, body, environment) => format!(
+                "-> e {{ catch(:return) {{ ({}).(-> {{ ({}).call({}) }}); {} }}.first }}",
+                CONFINED,
+                body.transpile(),
+                hash(environment),
+                missing_return(name),
+            ),
         }
     }
 }
@@ -78,7 +141,9 @@ impl Transpiler for Expression {
 impl Transpiler for Statement {
     fn transpile(&self) -> String {
         match self {
-            Self::Expression(expression) => expression.transpile(),
+            Self::Expression(expression) => {
+                format!("-> e {{ ({}).call(e); e }}", expression.transpile())
+            }
             Self::Assign(name, expression) => format!(
                 "-> e {{ e.merge({{ :{} => ({}).call(e) }}) }}",
                 name,
@@ -100,11 +165,91 @@ impl Transpiler for Statement {
                 )
             }
             Self::While(condition, body) => format!(
-                "-> e {{ while ({}).call(e); e = ({}).call(e); end; e; }}",
+                "-> e {{ catch(:break) {{ while ({}).call(e); e = catch(:continue) {{ ({}).call(e) }}; end; e }} }}",
                 condition.transpile(),
                 body.transpile(),
             ),
-            Self::Nothing => "-> e {{ e }}".into(),
+            Self::For(_, _, _, _) => self.desugar().transpile(),
+            Self::Iteration(body, next) => format!(
+                "-> e {{ b = true; r = catch(:break) {{ r = catch(:continue) {{ ({}).call(e) }}; b = false; r }}; b ? r : ({}).call(r) }}",
+                body.transpile(),
+                next.transpile(),
+            ),
+            Self::Break => "-> e { throw :break, e }".into(),
+            Self::Continue => "-> e { throw :continue, e }".into(),
+            Self::Let(name, expression, body) => {
+                let restore = format!(
+                    "s = -> v {{ e.key?(:{name}) ? v.merge({{ :{name} => e[:{name}] }}) : v.reject {{ |k, _| k == :{name} }} }}"
+                );
+                let run = format!(
+                    "r = ({}).call(e.merge({{ :{} => ({}).call(e) }})); d",
+                    body.transpile(),
+                    name,
+                    expression.transpile(),
+                );
+                let returning = format!(
+                    "x = catch(:return) {{ {run} }}; x.equal?(d) ? d : throw(:return, [x[0], s.(x[1])])"
+                );
+                let continuing = format!(
+                    "c = catch(:continue) {{ {returning} }}; c.equal?(d) ? d : throw(:continue, s.(c))"
+                );
+                let breaking = format!(
+                    "b = catch(:break) {{ {continuing} }}; b.equal?(d) ? s.(r) : throw(:break, s.(b))"
+                );
+
+                format!("->(e; s, d, r, b, c, x) {{ {restore}; d = Object.new; {breaking} }}")
+            }
+            Self::Function(name, parameters, body) => format!(
+                "-> e {{ e.merge({{ \"{}\" => -> c, a {{ ({}).(-> {{ ({}).call(c.select {{ |_, v| v.is_a?(Proc) }}.merge({{ {} }})) }}) }} }}) }}",
+                name,
+                CONFINED,
+                body.transpile(),
+                parameters
+                    .iter()
+                    .enumerate()
+                    .map(|(index, parameter)| format!(":{} => a[{}]", parameter, index))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            Self::Return(expression) => format!(
+                "-> e {{ throw :return, [({}).call(e), e] }}",
+                expression.transpile()
+            ),
+            Self::Print(expression) => format!(
+                "-> e {{ v = ({}).call(e); puts(v.is_a?(String) ? v : v.inspect); e }}",
+                expression.transpile()
+            ),
+            Self::Nothing => "-> e { e }".into(),
         }
     }
 }
+
+pub fn transpile_program(statement: &Statement, environment: &Environment) -> String {
+    format!(
+        "environment = {}\nprogram = {}\nresult = catch(:return) {{ [nil, ({}).(-> {{ program.call(environment) }})] }}.last\nresult.each {{ |name, value| puts \"#{{name}} = #{{value.inspect}}\" unless value.is_a?(Proc) }}\n",
+        hash(environment),
+        statement.transpile(),
+        CONFINED,
+    )
+}
+
+fn hash(environment: &Environment) -> String {
+    let mut variables = environment
+        .variables()
+        .map(|(name, value)| format!("{}: {}", name, literal(value)))
+        .collect::<Vec<_>>();
+    variables.sort();
+
+    format!("{{ {} }}", variables.join(", "))
+}
+
+fn literal(value: &Expression) -> String {
+    value.to_string().replace('#', "\\#")
+}
+
+fn missing_return(name: &str) -> String {
+    format!(
+        "raise(\"function '{}' finished without returning a value\")",
+        name
+    )
+}
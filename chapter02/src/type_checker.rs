@@ -0,0 +1,559 @@
+use crate::{
+    expression::Expression, function::Function, signature::Signature, statement::Statement,
+    type_context::TypeContext, type_error::TypeError, value_type::ValueType,
+};
+use std::sync::Arc;
+
+pub trait TypeChecker {
+    type Output;
+
+    fn type_check(&self, context: &mut TypeContext) -> Result<Self::Output, Vec<TypeError>> {
+        let mut errors = Vec::new();
+
+        match self.check(context, &mut errors) {
+            Some(output) if errors.is_empty() => Ok(output),
+            _ => Err(errors),
+        }
+    }
+
+    fn check(&self, context: &mut TypeContext, errors: &mut Vec<TypeError>)
+    -> Option<Self::Output>;
+}
+
+impl TypeChecker for Expression {
+    type Output = ValueType;
+
+    fn check(&self, context: &mut TypeContext, errors: &mut Vec<TypeError>) -> Option<ValueType> {
+        match self {
+            Self::Number(_) => Some(ValueType::Number),
+            Self::Boolean(_) => Some(ValueType::Boolean),
+            Self::String(_) | Self::Input => Some(ValueType::String),
+            Self::List(elements) => {
+                let mut list = Some(ValueType::List(None));
+
+                for element in elements {
+                    let actual = element.check(context, errors);
+
+                    list = match (list, actual) {
+                        (Some(ValueType::List(Some(expected))), Some(actual)) => {
+                            match expected.unify(&actual) {
+                                Some(element) => Some(ValueType::list(element)),
+                                None => {
+                                    errors.push(TypeError::Mismatch {
+                                        expected: *expected,
+                                        actual,
+                                        expression: element.clone(),
+                                    });
+                                    None
+                                }
+                            }
+                        }
+                        (Some(_), Some(actual)) => Some(ValueType::list(actual)),
+                        _ => None,
+                    };
+                }
+
+                list
+            }
+            Self::Variable(name) => {
+                let value_type = context.get(name);
+
+                if value_type.is_none() {
+                    errors.push(TypeError::UnboundVariable(name.into()));
+                }
+
+                value_type
+            }
+            Self::Add(left, right)
+            | Self::Subtract(left, right)
+            | Self::Multiply(left, right)
+            | Self::Divide(left, right) => {
+                let left = expect(left, ValueType::Number, context, errors);
+                let right = expect(right, ValueType::Number, context, errors);
+                left.and(right).map(|_| ValueType::Number)
+            }
+            Self::Negate(expression) => {
+                expect(expression, ValueType::Number, context, errors).map(|_| ValueType::Number)
+            }
+            Self::Not(expression) => {
+                expect(expression, ValueType::Boolean, context, errors).map(|_| ValueType::Boolean)
+            }
+            Self::And(left, right) | Self::Or(left, right) => {
+                let left = expect(left, ValueType::Boolean, context, errors);
+                let right = expect(right, ValueType::Boolean, context, errors);
+                left.and(right).map(|_| ValueType::Boolean)
+            }
+            Self::Equal(left, right) | Self::NotEqual(left, right) => {
+                let left = left.check(context, errors);
+                let right = right.check(context, errors);
+
+                let (left, right) = (left?, right?);
+
+                if left.unify(&right).is_some() {
+                    Some(ValueType::Boolean)
+                } else {
+                    errors.push(TypeError::Incomparable {
+                        left,
+                        right,
+                        expression: self.clone(),
+                    });
+                    None
+                }
+            }
+            Self::LessThan(left, right)
+            | Self::LessThanOrEqual(left, right)
+            | Self::GreaterThan(left, right)
+            | Self::GreaterThanOrEqual(left, right) => {
+                let left = expect(left, ValueType::Number, context, errors);
+                let right = expect(right, ValueType::Number, context, errors);
+                left.and(right).map(|_| ValueType::Boolean)
+            }
+            Self::Concatenate(left, right) => {
+                let left = expect(left, ValueType::String, context, errors);
+                let right = expect(right, ValueType::String, context, errors);
+                left.and(right).map(|_| ValueType::String)
+            }
+            Self::Length(expression) => match expression.check(context, errors)? {
+                ValueType::String | ValueType::List(_) => Some(ValueType::Number),
+                actual => {
+                    errors.push(TypeError::Mismatch {
+                        expected: ValueType::List(None),
+                        actual,
+                        expression: *expression.clone(),
+                    });
+                    None
+                }
+            },
+            Self::Index(list, index) => {
+                let element = expect(list, ValueType::List(None), context, errors);
+                let index = expect(index, ValueType::Number, context, errors);
+
+                match (element?, index?) {
+                    (ValueType::List(Some(element)), _) => Some(*element),
+                    _ => {
+                        errors.push(TypeError::UnknownElement(self.clone()));
+                        None
+                    }
+                }
+            }
+            Self::Append(list, element) => {
+                let list = expect(list, ValueType::List(None), context, errors);
+                let actual = element.check(context, errors);
+
+                match (list?, actual?) {
+                    (ValueType::List(Some(expected)), actual) => match expected.unify(&actual) {
+                        Some(element) => Some(ValueType::list(element)),
+                        None => {
+                            errors.push(TypeError::Mismatch {
+                                expected: *expected,
+                                actual,
+                                expression: *element.clone(),
+                            });
+                            None
+                        }
+                    },
+                    (_, actual) => Some(ValueType::list(actual)),
+                }
+            }
+            Self::Call(name, arguments) => {
+                let actuals = arguments
+                    .iter()
+                    .map(|argument| argument.check(context, errors))
+                    .collect::<Vec<_>>();
+
+                let Some(function) = context.function(name) else {
+                    errors.push(TypeError::UndefinedFunction(name.into()));
+                    return None;
+                };
+
+                if function.parameters().len() != arguments.len() {
+                    errors.push(TypeError::ArityMismatch {
+                        name: name.into(),
+                        expected: function.parameters().len(),
+                        actual: arguments.len(),
+                    });
+                    return None;
+                }
+
+                let actuals = actuals.into_iter().collect::<Option<Vec<_>>>()?;
+
+                match context.signature(name).cloned() {
+                    Some(signature) => {
+                        let mut matched = true;
+
+                        for ((argument, actual), expected) in
+                            arguments.iter().zip(actuals).zip(signature.parameters())
+                        {
+                            if actual.unify(expected).is_none() {
+                                errors.push(TypeError::Mismatch {
+                                    expected: expected.clone(),
+                                    actual,
+                                    expression: argument.clone(),
+                                });
+                                matched = false;
+                            }
+                        }
+
+                        if !matched {
+                            return None;
+                        }
+                    }
+                    None => {
+                        context.sign(name, Signature::new(actuals, None));
+
+                        let mut attempt = Vec::new();
+                        let mut local = context.local(name);
+                        function.body().check(&mut local, &mut attempt);
+                        context.absorb(local);
+
+                        if context
+                            .signature(name)
+                            .and_then(Signature::result)
+                            .is_some()
+                        {
+                            let mut local = context.local(name);
+                            function.body().check(&mut local, errors);
+                            context.absorb(local);
+                        } else {
+                            errors.extend(attempt);
+                        }
+                    }
+                }
+
+                let result = context
+                    .signature(name)
+                    .and_then(|signature| signature.result());
+                let unresolved = TypeError::UnresolvedReturn(name.into());
+
+                if result.is_none() && !errors.contains(&unresolved) {
+                    errors.push(unresolved);
+                }
+
+                result
+            }
+            Self::Invocation(name, body, environment) => {
+                let mut local = TypeContext::from(environment.as_ref()).within(name);
+                let parameters = match environment.function(name) {
+                    Ok(function) => function
+                        .parameters()
+                        .iter()
+                        .filter_map(|parameter| local.get(parameter))
+                        .collect(),
+                    Err(_) => Vec::new(),
+                };
+                local.sign(name, Signature::new(parameters, None));
+                body.check(&mut local, errors);
+
+                let result = local.result();
+
+                if result.is_none() {
+                    errors.push(TypeError::UnresolvedReturn(name.into()));
+                }
+
+                result
+            }
+        }
+    }
+}
+
+impl TypeChecker for Statement {
+    type Output = ();
+
+    fn check(&self, context: &mut TypeContext, errors: &mut Vec<TypeError>) -> Option<()> {
+        match self {
+            Self::Expression(expression) | Self::Print(expression) => {
+                expression.check(context, errors).map(|_| ())
+            }
+            Self::Assign(name, expression) => {
+                let actual = expression.check(context, errors)?;
+
+                match context.declared(name) {
+                    Some(previous) => match previous.unify(&actual) {
+                        Some(unified) => {
+                            context.insert(name, unified);
+                            Some(())
+                        }
+                        None => {
+                            errors.push(TypeError::Retyped {
+                                name: name.into(),
+                                previous,
+                                actual,
+                                statement: self.clone(),
+                            });
+                            None
+                        }
+                    },
+                    None => {
+                        context.insert(name, actual);
+                        Some(())
+                    }
+                }
+            }
+            Self::If(condition, consequence, alternative) => {
+                let condition = expect(condition, ValueType::Boolean, context, errors);
+                let mut otherwise = context.clone();
+                let consequence = consequence.check(context, errors);
+                otherwise.share_signatures(context);
+                let alternative = alternative.check(&mut otherwise, errors);
+                let mut joined = Some(());
+
+                for (name, previous, actual) in context.join(otherwise) {
+                    errors.push(TypeError::Retyped {
+                        name,
+                        previous,
+                        actual,
+                        statement: self.clone(),
+                    });
+                    joined = None;
+                }
+
+                condition.and(consequence).and(alternative).and(joined)
+            }
+            Self::Sequence(first, second) => {
+                let (mut first, mut second) = (first, second);
+                let mut checked = Some(());
+
+                loop {
+                    checked = checked.and(first.check(context, errors));
+
+                    match &**second {
+                        Self::Sequence(next, rest) => (first, second) = (next, rest),
+                        last => break checked.and(last.check(context, errors)),
+                    }
+                }
+            }
+            Self::While(condition, body) => {
+                let condition = expect(condition, ValueType::Boolean, context, errors);
+                let body = repeat(body, self, context, errors);
+                condition.and(body)
+            }
+            Self::For(name, start, end, body) => {
+                let start = expect(start, ValueType::Number, context, errors);
+                let counter = match context.declared(name) {
+                    Some(previous) if previous != ValueType::Number => {
+                        errors.push(TypeError::Retyped {
+                            name: name.into(),
+                            previous,
+                            actual: ValueType::Number,
+                            statement: self.clone(),
+                        });
+                        None
+                    }
+                    _ => {
+                        context.insert(name, ValueType::Number);
+                        Some(())
+                    }
+                };
+                let end = expect(end, ValueType::Number, context, errors);
+                let body = repeat(body, self, context, errors);
+                start.and(counter).and(end).and(body).map(|_| ())
+            }
+            Self::Iteration(body, next) => {
+                context.enter_loop();
+                let body = body.check(context, errors);
+                context.leave_loop();
+                let next = next.check(context, errors);
+                body.and(next)
+            }
+            Self::Break | Self::Continue => {
+                if context.is_looping() {
+                    Some(())
+                } else {
+                    errors.push(TypeError::OutsideLoop(self.clone()));
+                    None
+                }
+            }
+            Self::Let(name, expression, body) => {
+                let actual = expression.check(context, errors)?;
+                let shadowed = context.remove(name);
+                context.insert(name, actual);
+
+                let body = body.check(context, errors);
+
+                match shadowed {
+                    Some(shadowed) => context.insert(name, shadowed),
+                    None => {
+                        context.remove(name);
+                    }
+                }
+
+                body
+            }
+            Self::Function(name, parameters, body) => {
+                if context.function(name).is_some() {
+                    errors.push(TypeError::Redefined(name.into()));
+                    return None;
+                }
+
+                context.define(
+                    name,
+                    Arc::new(Function::new(parameters.clone(), *body.clone())),
+                );
+                Some(())
+            }
+            Self::Return(expression) => {
+                let actual = expression.check(context, errors)?;
+
+                match context.result() {
+                    Some(expected) => match expected.unify(&actual) {
+                        Some(unified) => {
+                            context.resolve(unified);
+                            Some(())
+                        }
+                        None => {
+                            errors.push(TypeError::Mismatch {
+                                expected,
+                                actual,
+                                expression: expression.clone(),
+                            });
+                            None
+                        }
+                    },
+                    None => {
+                        context.resolve(actual);
+                        Some(())
+                    }
+                }
+            }
+            Self::Nothing => Some(()),
+        }
+    }
+}
+
+fn repeat(
+    body: &Statement,
+    statement: &Statement,
+    context: &mut TypeContext,
+    errors: &mut Vec<TypeError>,
+) -> Option<()> {
+    let mut skipped = context.clone();
+    context.enter_loop();
+    let body = body.check(context, errors);
+    context.leave_loop();
+    skipped.share_signatures(context);
+    let mut joined = Some(());
+
+    for (name, previous, actual) in context.join(skipped) {
+        errors.push(TypeError::Retyped {
+            name,
+            previous,
+            actual,
+            statement: statement.clone(),
+        });
+        joined = None;
+    }
+
+    body.and(joined)
+}
+
+fn expect(
+    expression: &Expression,
+    expected: ValueType,
+    context: &mut TypeContext,
+    errors: &mut Vec<TypeError>,
+) -> Option<ValueType> {
+    let actual = expression.check(context, errors)?;
+
+    if let Some(unified) = expected.unify(&actual) {
+        Some(unified)
+    } else {
+        errors.push(TypeError::Mismatch {
+            expected,
+            actual,
+            expression: expression.clone(),
+        });
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_statement;
+
+    fn check(source: &str) -> Result<TypeContext, Vec<TypeError>> {
+        let mut context = TypeContext::new();
+        parse_statement(source).unwrap().type_check(&mut context)?;
+        Ok(context)
+    }
+
+    #[test]
+    fn assignments_in_loop_bodies_are_only_possibly_defined() {
+        for source in [
+            "while (false) { y = 1 }; z = y + 1",
+            "for (i = 1 to 0) { y = i }; z = y + 1",
+        ] {
+            assert_eq!(
+                check(source).unwrap_err(),
+                vec![TypeError::UnboundVariable("y".into())]
+            );
+        }
+
+        let context = check("y = 0; while (y < 3) { y = y + 1; z = true }; y = y + 1").unwrap();
+        assert_eq!(context.get("y"), Some(ValueType::Number));
+        assert_eq!(context.get("z"), None);
+        assert_eq!(context.declared("z"), Some(ValueType::Boolean));
+
+        let context = check("for (i = 1 to 3) { nothing }; j = i").unwrap();
+        assert_eq!(context.get("j"), Some(ValueType::Number));
+
+        assert!(matches!(
+            check("while (false) { y = 1 }; y = true").unwrap_err()[..],
+            [TypeError::Retyped { .. }]
+        ));
+    }
+
+    #[test]
+    fn recursive_functions_take_their_type_from_the_base_case() {
+        let context = check(
+            "function f(n) { if (n > 0) { return f(n - 1) + 1 } else { return 0 } }; y = f(3)",
+        )
+        .unwrap();
+        assert_eq!(context.get("y"), Some(ValueType::Number));
+
+        let context = check(
+            "function f(n) { if (n > 0) { x = f(n - 1); return x ++ \"!\" } else { return \"\" } }; y = f(3)",
+        )
+        .unwrap();
+        assert_eq!(context.get("y"), Some(ValueType::String));
+
+        assert!(matches!(
+            check("function f(n) { if (n > 0) { return f(n - 1) ++ \"!\" } else { return 0 } }; y = f(3)")
+                .unwrap_err()[..],
+            [TypeError::Mismatch { .. }]
+        ));
+    }
+
+    #[test]
+    fn unbounded_recursion_has_no_return_type() {
+        assert_eq!(
+            check("function f(n) { return f(n + 1) }; y = f(0)").unwrap_err(),
+            vec![TypeError::UnresolvedReturn("f".into())]
+        );
+    }
+
+    #[test]
+    fn branches_and_scopes_join_their_assignments() {
+        let context = check("if (true) { x = 1; y = 2 } else { x = 3 }").unwrap();
+        assert_eq!(context.get("x"), Some(ValueType::Number));
+        assert_eq!(context.get("y"), None);
+
+        assert!(matches!(
+            check("if (true) { x = 1 } else { x = false }").unwrap_err()[..],
+            [TypeError::Retyped { .. }]
+        ));
+
+        let context = check("x = true; let x = 1 in { y = x + 1 }").unwrap();
+        assert_eq!(context.get("x"), Some(ValueType::Boolean));
+        assert_eq!(context.get("y"), Some(ValueType::Number));
+    }
+
+    #[test]
+    fn break_and_continue_must_be_inside_a_loop() {
+        assert!(check("while (true) { break }").is_ok());
+        assert_eq!(
+            check("continue").unwrap_err(),
+            vec![TypeError::OutsideLoop(Statement::Continue)]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    environment::Environment, function::Function, signature::Signature, value_type::ValueType,
+};
+use std::{collections::HashMap, sync::Arc};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TypeContext {
+    types: HashMap<String, ValueType>,
+    possible: HashMap<String, ValueType>,
+    functions: HashMap<String, Arc<Function>>,
+    signatures: HashMap<String, Signature>,
+    current: Option<String>,
+    loops: usize,
+}
+
+impl TypeContext {
+    pub fn new() -> Self {
+        Self {
+            types: HashMap::new(),
+            possible: HashMap::new(),
+            functions: HashMap::new(),
+            signatures: HashMap::new(),
+            current: None,
+            loops: 0,
+        }
+    }
+
+    pub fn insert(&mut self, name: &str, value_type: ValueType) {
+        self.types.insert(name.into(), value_type);
+    }
+
+    pub fn get(&self, name: &str) -> Option<ValueType> {
+        self.types.get(name).cloned()
+    }
+
+    pub fn declared(&self, name: &str) -> Option<ValueType> {
+        self.get(name).or_else(|| self.possible.get(name).cloned())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ValueType> {
+        self.types.remove(name)
+    }
+
+    pub fn join(&mut self, other: Self) -> Vec<(String, ValueType, ValueType)> {
+        let mut conflicts = Vec::new();
+        let mut types = HashMap::new();
+
+        for (name, value_type) in self.types.drain() {
+            match other.types.get(&name) {
+                Some(other_type) => match value_type.unify(other_type) {
+                    Some(unified) => {
+                        types.insert(name, unified);
+                    }
+                    None => conflicts.push((name, value_type, other_type.clone())),
+                },
+                None => {
+                    self.possible.insert(name, value_type);
+                }
+            }
+        }
+
+        for (name, value_type) in other.types.into_iter().chain(other.possible) {
+            if !types.contains_key(&name) {
+                self.possible.entry(name).or_insert(value_type);
+            }
+        }
+
+        self.types = types;
+        self.functions
+            .retain(|name, _| other.functions.contains_key(name));
+        self.signatures = other.signatures;
+
+        conflicts
+    }
+
+    pub fn settle(&mut self) {
+        for (name, value_type) in self.possible.drain() {
+            self.types.entry(name).or_insert(value_type);
+        }
+    }
+
+    pub fn define(&mut self, name: &str, function: Arc<Function>) {
+        self.functions.insert(name.into(), function);
+        self.signatures.remove(name);
+    }
+
+    pub fn function(&self, name: &str) -> Option<Arc<Function>> {
+        self.functions.get(name).cloned()
+    }
+
+    pub fn sign(&mut self, name: &str, signature: Signature) {
+        self.signatures.insert(name.into(), signature);
+    }
+
+    pub fn signature(&self, name: &str) -> Option<&Signature> {
+        self.signatures.get(name)
+    }
+
+    pub fn share_signatures(&mut self, other: &Self) {
+        self.signatures = other.signatures.clone();
+    }
+
+    pub fn result(&self) -> Option<ValueType> {
+        self.current
+            .as_ref()
+            .and_then(|name| self.signature(name))
+            .and_then(|signature| signature.result())
+    }
+
+    pub fn resolve(&mut self, result: ValueType) {
+        if let Some(signature) = self
+            .current
+            .as_ref()
+            .and_then(|name| self.signatures.get_mut(name))
+        {
+            *signature = Signature::new(signature.parameters().to_vec(), Some(result));
+        }
+    }
+
+    pub fn enter_loop(&mut self) {
+        self.loops += 1;
+    }
+
+    pub fn leave_loop(&mut self) {
+        self.loops -= 1;
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.loops > 0
+    }
+
+    pub fn within(self, name: &str) -> Self {
+        Self {
+            current: Some(name.into()),
+            ..self
+        }
+    }
+
+    pub fn local(&self, name: &str) -> Self {
+        let mut local = Self {
+            types: HashMap::new(),
+            possible: HashMap::new(),
+            functions: self.functions.clone(),
+            signatures: self.signatures.clone(),
+            current: None,
+            loops: 0,
+        }
+        .within(name);
+
+        if let (Some(function), Some(signature)) = (self.function(name), self.signature(name)) {
+            for (parameter, value_type) in function.parameters().iter().zip(signature.parameters())
+            {
+                local.insert(parameter, value_type.clone());
+            }
+        }
+
+        local
+    }
+
+    pub fn absorb(&mut self, local: Self) {
+        for (name, signature) in local.signatures {
+            let shared = match (self.functions.get(&name), local.functions.get(&name)) {
+                (Some(outer), Some(inner)) => Arc::ptr_eq(outer, inner),
+                _ => false,
+            };
+
+            if shared {
+                self.signatures.insert(name, signature);
+            }
+        }
+    }
+}
+
+impl From<&Environment> for TypeContext {
+    fn from(environment: &Environment) -> Self {
+        let mut context = Self::new();
+
+        for (name, value) in environment.variables() {
+            if let Some(value_type) = value.value_type() {
+                context.insert(name, value_type);
+            }
+        }
+
+        for (name, function) in environment.functions() {
+            context.define(name, function.clone());
+        }
+
+        context
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{expression::Expression, statement::Statement, value_type::ValueType};
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeError {
+    UnboundVariable(String),
+    Mismatch {
+        expected: ValueType,
+        actual: ValueType,
+        expression: Expression,
+    },
+    Incomparable {
+        left: ValueType,
+        right: ValueType,
+        expression: Expression,
+    },
+    Retyped {
+        name: String,
+        previous: ValueType,
+        actual: ValueType,
+        statement: Statement,
+    },
+    UndefinedFunction(String),
+    Redefined(String),
+    ArityMismatch {
+        name: String,
+        expected: usize,
+        actual: usize,
+    },
+    UnresolvedReturn(String),
+    UnknownElement(Expression),
+    OutsideLoop(Statement),
+}
+
+impl Display for TypeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::UnboundVariable(name) => write!(f, "variable '{}' is not defined", name),
+            Self::Mismatch {
+                expected,
+                actual,
+                expression,
+            } => write!(
+                f,
+                "'{}' is a {} but a {} is expected here",
+                expression, actual, expected
+            ),
+            Self::Incomparable {
+                left,
+                right,
+                expression,
+            } => write!(f, "'{}' compares a {} with a {}", expression, left, right),
+            Self::Retyped {
+                name,
+                previous,
+                actual,
+                statement,
+            } => write!(
+                f,
+                "'{}' assigns a {} to '{}' which already holds a {}",
+                statement, actual, name, previous
+            ),
+            Self::UndefinedFunction(name) => write!(f, "function '{}' is not defined", name),
+            Self::Redefined(name) => write!(f, "function '{}' is already defined", name),
+            Self::ArityMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "function '{}' expects {} arguments but is given {}",
+                name, expected, actual
+            ),
+            Self::UnresolvedReturn(name) => write!(
+                f,
+                "the return type of function '{}' cannot be determined",
+                name
+            ),
+            Self::UnknownElement(expression) => write!(
+                f,
+                "'{}' indexes a list whose element type cannot be determined",
+                expression
+            ),
+            Self::OutsideLoop(statement) => write!(f, "'{}' is used outside of a loop", statement),
+        }
+    }
+}
+
+impl std::error::Error for TypeError {}
@@ -0,0 +1,37 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    Number,
+    Boolean,
+    String,
+    List(Option<Box<ValueType>>),
+}
+
+impl ValueType {
+    pub fn list(element: ValueType) -> Self {
+        Self::List(Some(Box::new(element)))
+    }
+
+    pub fn unify(&self, other: &ValueType) -> Option<ValueType> {
+        match (self, other) {
+            (Self::List(None), Self::List(_)) => Some(other.clone()),
+            (Self::List(_), Self::List(None)) => Some(self.clone()),
+            (Self::List(Some(left)), Self::List(Some(right))) => left.unify(right).map(Self::list),
+            _ if self == other => Some(self.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ValueType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Number => write!(f, "number"),
+            Self::Boolean => write!(f, "boolean"),
+            Self::String => write!(f, "string"),
+            Self::List(None) => write!(f, "list"),
+            Self::List(Some(element)) => write!(f, "list of {}", element),
+        }
+    }
+}
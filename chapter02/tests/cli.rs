@@ -0,0 +1,162 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+struct Run {
+    code: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+fn source(name: &str, program: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("cli-{}-{}.simple", std::process::id(), name));
+    fs::write(&path, program).unwrap();
+    path
+}
+
+fn simple(arguments: &[&str]) -> Run {
+    let Output {
+        status,
+        stdout,
+        stderr,
+    } = Command::new(env!("CARGO_BIN_EXE_simple"))
+        .args(arguments)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    Run {
+        code: status.code(),
+        stdout: String::from_utf8(stdout).unwrap(),
+        stderr: String::from_utf8(stderr).unwrap(),
+    }
+}
+
+#[test]
+fn successful_runs_exit_with_zero() {
+    let path = source("success", "y = x + 1; print y");
+    let path = path.to_str().unwrap();
+
+    for arguments in [
+        &["--set", "x=5", path][..],
+        &["--small-step", "--set", "x=5", path],
+        &["--check", "--set", "x=5", path],
+        &["--optimize", "--set", "x=5", path],
+        &["--transpile", "python", "--set", "x=5", path],
+    ] {
+        let run = simple(arguments);
+        assert_eq!(run.code, Some(0), "{:?}: {}", arguments, run.stderr);
+    }
+
+    let run = simple(&["--set", "x=5", path]);
+    assert!(run.stdout.starts_with("6\n"), "{}", run.stdout);
+}
+
+#[test]
+fn failing_programs_exit_with_one() {
+    let undefined = source("undefined", "y = x + 1");
+    let division = source("division", "x = 1 / 0");
+    let unparsable = source("unparsable", "x = 1;\ny = * 2");
+    let strings = source("strings", "x = \"a\"");
+
+    for (arguments, message) in [
+        (vec![undefined.to_str().unwrap()], "runtime error"),
+        (
+            vec!["--small-step", division.to_str().unwrap()],
+            "division by zero",
+        ),
+        (vec!["--check", undefined.to_str().unwrap()], "type error"),
+        (
+            vec!["--optimize", undefined.to_str().unwrap()],
+            "type error",
+        ),
+        (vec![unparsable.to_str().unwrap()], ":2:5:"),
+        (
+            vec!["--transpile", "c", strings.to_str().unwrap()],
+            "does not support",
+        ),
+        (vec!["/nonexistent/program.simple"], "cannot read"),
+    ] {
+        let run = simple(&arguments);
+        assert_eq!(run.code, Some(1), "{:?}", arguments);
+        assert!(
+            run.stderr.contains(message),
+            "{:?}: {}",
+            arguments,
+            run.stderr
+        );
+        assert!(!run.stderr.contains("usage:"), "{:?}", arguments);
+    }
+}
+
+#[test]
+fn usage_errors_exit_with_two() {
+    let path = source("usage", "y = x + 1");
+    let path = path.to_str().unwrap();
+
+    for (arguments, message) in [
+        (&[][..], "missing source file"),
+        (&["--bogus", path], "unknown option '--bogus'"),
+        (&["--big-step", "--check", path], "choose only one"),
+        (&["--transpile"], "--transpile expects a target"),
+        (&["--transpile", "cobol", path], "unknown target 'cobol'"),
+        (&[path, path], "expected a single source file"),
+        (&["--set"], "--set expects name=value"),
+        (&["--set", "x", path], "expected name=value but got 'x'"),
+        (&["--set", "1=2", path], "'1' is not a variable name"),
+        (&["--set", "x=1 / 0", path], "invalid value for 'x'"),
+        (&["--set", "x=y", path], "invalid value for 'x'"),
+        (&["--set", "x=(", path], "invalid value for 'x'"),
+    ] {
+        let run = simple(arguments);
+        assert_eq!(run.code, Some(2), "{:?}", arguments);
+        assert!(
+            run.stderr.contains(message),
+            "{:?}: {}",
+            arguments,
+            run.stderr
+        );
+        assert!(run.stderr.contains("usage: simple"), "{:?}", arguments);
+    }
+}
+
+#[test]
+fn set_evaluates_values_of_every_type() {
+    let path = source("values", "print a; print b; print c; print d");
+    let run = simple(&[
+        "--set",
+        "a=2 * 3",
+        "--set",
+        "b=!true",
+        "--set",
+        "c=\"x\" ++ \"y\"",
+        "--set",
+        "d=append([1], -2)",
+        "--set",
+        "a=-1",
+        path.to_str().unwrap(),
+    ]);
+
+    assert_eq!(run.code, Some(0), "{}", run.stderr);
+    assert!(
+        run.stdout.starts_with("-1\nfalse\nxy\n[1, -2]\n"),
+        "{}",
+        run.stdout
+    );
+}
+
+#[test]
+fn set_values_are_checked_against_the_program() {
+    let path = source("typed", "y = x + 1");
+    let run = simple(&["--check", "--set", "x=true", path.to_str().unwrap()]);
+
+    assert_eq!(run.code, Some(1));
+    assert!(
+        run.stderr
+            .contains("'x' is a boolean but a number is expected here"),
+        "{}",
+        run.stderr
+    );
+}
@@ -0,0 +1,118 @@
+use chapter02::{
+    bytecode::{chunk::Chunk, virtual_machine::VirtualMachine},
+    console::ScriptedConsole,
+    denotation::Denotation,
+    environment::Environment,
+    evaluator::Evaluator,
+    expression::Expression,
+    fuel::Fuel,
+    machine::Machine,
+    parser::parse_statement,
+    runtime_error::RuntimeError,
+};
+use std::sync::{Arc, Mutex};
+
+type Run = fn(&str, &mut Environment) -> Result<(), RuntimeError>;
+
+fn evaluate(source: &str, environment: &mut Environment) -> Result<(), RuntimeError> {
+    parse_statement(source).unwrap().evaluate(environment)?;
+    Ok(())
+}
+
+fn reduce(source: &str, environment: &mut Environment) -> Result<(), RuntimeError> {
+    let mut machine = Machine::new(parse_statement(source).unwrap(), environment.clone());
+    let result = machine.run_with_observer(&mut |_: &_| {});
+    *environment = machine.environment().clone();
+    result
+}
+
+fn denote(source: &str, environment: &mut Environment) -> Result<(), RuntimeError> {
+    parse_statement(source).unwrap().denote()(environment, &mut Fuel::default())?;
+    Ok(())
+}
+
+fn execute(source: &str, environment: &mut Environment) -> Result<(), RuntimeError> {
+    VirtualMachine::new(Chunk::compile(&parse_statement(source).unwrap())).run(environment)
+}
+
+const RUNS: [Run; 4] = [evaluate, reduce, denote, execute];
+
+fn scripted(
+    run: Run,
+    source: &str,
+    input: &[&str],
+) -> (Result<Environment, RuntimeError>, Vec<String>) {
+    let console = Arc::new(Mutex::new(ScriptedConsole::new(input.iter().copied())));
+    let mut environment = Environment::with_console(console.clone());
+    let result = run(source, &mut environment).map(|_| environment);
+    let output = console.lock().unwrap().output().to_vec();
+
+    (result, output)
+}
+
+#[test]
+fn printed_values_are_written_to_the_console_in_order() {
+    for run in RUNS {
+        let (result, output) = scripted(
+            run,
+            "x = 1; print x; print x + 1 > 1; print \"a \\\"b\\\"\"; print [1, -2]; print append([], \"s\")",
+            &[],
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(output, ["1", "true", "a \"b\"", "[1, -2]", "[\"s\"]"]);
+    }
+}
+
+#[test]
+fn output_before_a_failure_is_kept() {
+    for run in RUNS {
+        let (result, output) = scripted(run, "print 1; x = 1 / 0; print 2", &[]);
+
+        assert!(matches!(result, Err(RuntimeError::DivisionByZero(_))));
+        assert_eq!(output, ["1"]);
+    }
+}
+
+#[test]
+fn input_reads_scripted_lines_in_order() {
+    for run in RUNS {
+        let (result, output) = scripted(
+            run,
+            "a = input(); b = input(); print b ++ a; n = length(input())",
+            &["first", "second", ""],
+        );
+
+        let environment = result.unwrap();
+        assert_eq!(
+            environment.get("a").ok(),
+            Some(Expression::String("first".into()))
+        );
+        assert_eq!(environment.get("n").ok(), Some(Expression::Number(0)));
+        assert_eq!(output, ["secondfirst"]);
+    }
+}
+
+#[test]
+fn reading_past_the_end_of_the_script_is_a_runtime_error() {
+    for run in RUNS {
+        let (result, output) = scripted(run, "a = input(); print a; b = input()", &["only"]);
+
+        assert!(matches!(result, Err(RuntimeError::EndOfInput)));
+        assert_eq!(output, ["only"]);
+    }
+}
+
+#[test]
+fn loops_and_functions_share_the_console() {
+    for run in RUNS {
+        let (result, output) = scripted(
+            run,
+            "function echo(n) { print input(); return n }; for (i = 1 to 3) { x = echo(i); print x }",
+            &["a", "b", "c"],
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(output, ["a", "1", "b", "2", "c", "3"]);
+    }
+}
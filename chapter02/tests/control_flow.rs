@@ -0,0 +1,181 @@
+use chapter02::{
+    desugar::Desugarer,
+    differential::{outcome::Outcome, semantics::Semantics},
+    environment::Environment,
+    expression::Expression,
+    parser::parse_statement,
+    statement::Statement,
+    target::Target,
+    transpile_error::TranspileError,
+    type_checker::TypeChecker,
+    type_context::TypeContext,
+    type_error::TypeError,
+};
+
+fn semantics() -> Vec<Semantics> {
+    let transpiled = [Target::Ruby, Target::JavaScript, Target::Python, Target::C]
+        .map(Semantics::Transpiled)
+        .into_iter()
+        .filter(Semantics::is_available);
+
+    [
+        Semantics::SmallStep,
+        Semantics::Denotational,
+        Semantics::Bytecode,
+        Semantics::Optimized,
+    ]
+    .into_iter()
+    .chain(transpiled)
+    .collect()
+}
+
+fn run(statement: &Statement) -> Outcome {
+    let expected = Semantics::BigStep
+        .run(statement, &Environment::new())
+        .unwrap();
+
+    for semantics in semantics() {
+        let actual = match semantics.run(statement, &Environment::new()) {
+            Err(error) if error.is::<TranspileError>() => continue,
+            result => result.unwrap(),
+        };
+
+        assert!(
+            actual.agrees_with(&expected),
+            "'{}': big-step gives {} but {} gives {}",
+            statement,
+            expected,
+            semantics,
+            actual
+        );
+    }
+
+    expected
+}
+
+fn assert_variables(source: &str, expected: &[(&str, i64)]) {
+    let statement = parse_statement(source).unwrap();
+    let Outcome::Finished { environment, .. } = run(&statement) else {
+        panic!("'{}' did not finish", source);
+    };
+
+    for (name, value) in expected {
+        assert_eq!(
+            environment.get(name).ok(),
+            Some(Expression::Number(*value)),
+            "'{}': {}",
+            source,
+            name
+        );
+    }
+}
+
+#[test]
+fn break_leaves_only_the_innermost_loop() {
+    assert_variables(
+        "x = 0; while (true) { x = x + 1; if (x == 3) { break } else { nothing } }",
+        &[("x", 3)],
+    );
+    assert_variables(
+        "x = 0; for (i = 1 to 5) { for (j = 1 to 5) { if (j > i) { break } else { x = x + 1 } } }",
+        &[("x", 15), ("i", 6)],
+    );
+}
+
+#[test]
+fn continue_skips_the_rest_of_the_body() {
+    assert_variables(
+        "x = 0; i = 0; while (i < 5) { i = i + 1; if (i == 2 || i == 4) { continue } else { nothing }; x = x + i }",
+        &[("x", 9), ("i", 5)],
+    );
+    assert_variables(
+        "x = 0; for (i = 1 to 5) { if (i == 2 || i == 4) { continue } else { nothing }; x = x + i }",
+        &[("x", 9), ("i", 6)],
+    );
+}
+
+#[test]
+fn for_loops_count_inclusively_and_may_not_run_at_all() {
+    assert_variables(
+        "x = 0; for (i = 1 to 4) { x = x + i }",
+        &[("x", 10), ("i", 5)],
+    );
+    assert_variables("x = 0; for (i = 5 to 1) { x = 1 }", &[("x", 0), ("i", 5)]);
+    assert_variables(
+        "n = 3; x = 0; for (i = n - 1 to n * 2) { x = x + 1 }",
+        &[("x", 5), ("i", 7)],
+    );
+}
+
+#[test]
+fn for_loops_desugar_into_while_loops_that_increment_before_continuing() {
+    let statement =
+        parse_statement("for (i = 1 to 3) { if (i == 2) { continue } else { nothing }; x = i }")
+            .unwrap();
+
+    assert_eq!(
+        statement.desugar().to_string(),
+        "i = 1; while (i <= 3) { { if (i == 2) { i = i + 1; continue } else { nothing }; x = i }; i = i + 1 }"
+    );
+}
+
+#[test]
+fn desugared_programs_behave_like_the_original() {
+    for source in [
+        "x = 0; for (i = 1 to 3) { if (i == 2) { continue } else { nothing }; x = x + i }",
+        "for (i = 1 to 3) { let i = 10 in { continue } }",
+        "x = 0; for (i = 1 to 3) { for (j = i to 3) { if (j == 2) { continue } else { x = x + j } } }",
+        "x = 0; for (i = 1 to 10) { if (i > 4) { break } else { x = x + i } }",
+        "function f(n) { x = 0; for (i = 1 to n) { x = x + i }; return x }; y = f(4)",
+    ] {
+        let statement = parse_statement(source).unwrap();
+        let desugared = statement.desugar();
+
+        assert!(
+            !desugared.to_string().contains("for ("),
+            "'{}' still contains a for loop",
+            desugared
+        );
+        assert!(
+            run(&desugared).agrees_with(&run(&statement)),
+            "'{}' desugars to '{}'",
+            statement,
+            desugared
+        );
+    }
+}
+
+#[test]
+fn jumps_outside_a_loop_are_rejected() {
+    for source in [
+        "break",
+        "x = 1; continue",
+        "function f(n) { break }; while (true) { x = f(1) }",
+    ] {
+        let statement = parse_statement(source).unwrap();
+
+        for semantics in [
+            Semantics::SmallStep,
+            Semantics::BigStep,
+            Semantics::Denotational,
+            Semantics::Bytecode,
+        ] {
+            match semantics.run(&statement, &Environment::new()).unwrap() {
+                Outcome::Failed(message) => {
+                    assert!(message.contains("outside of a loop"), "{}", message)
+                }
+                outcome => panic!("'{}': {} gives {}", source, semantics, outcome),
+            }
+        }
+
+        let errors = statement.type_check(&mut TypeContext::new()).unwrap_err();
+        assert!(
+            errors
+                .iter()
+                .any(|error| matches!(error, TypeError::OutsideLoop(_))),
+            "'{}' gives {:?}",
+            source,
+            errors
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use chapter02::{
+    differential::{outcome::Outcome, property::Property, semantics::Semantics, shrink::shrink},
+    environment::Environment,
+    limit::Limit,
+    parser::parse_statement,
+    statement::Statement,
+    target::Target,
+    type_checker::TypeChecker,
+    type_context::TypeContext,
+};
+
+fn forget_output(outcome: &Outcome) -> Outcome {
+    match outcome {
+        Outcome::Finished { environment, .. } => Outcome::Finished {
+            output: Vec::new(),
+            environment: environment.clone(),
+        },
+        _ => outcome.clone(),
+    }
+}
+
+#[test]
+fn available_semantics_agree_on_generated_programs() {
+    if let Some(counterexample) = Property::new(Semantics::available()).check().unwrap() {
+        panic!("{}", counterexample);
+    }
+}
+
+#[test]
+fn shrinking_a_planted_discrepancy_finds_the_minimal_program() {
+    let program = parse_statement(
+        "x = 3; y = x * 2; if (y > 4) { z = y - 1; print z + x } else { nothing }; w = !true",
+    )
+    .unwrap();
+
+    let shrunk = shrink(&program, |candidate: &Statement| {
+        if candidate.type_check(&mut TypeContext::new()).is_err() {
+            return false;
+        }
+
+        let expected = Semantics::BigStep
+            .run(candidate, &Environment::new())
+            .unwrap();
+        !forget_output(&expected).agrees_with(&expected)
+    });
+
+    assert_eq!(shrunk.to_string(), "print 0");
+}
+
+#[test]
+fn transpiled_programs_that_never_finish_time_out() {
+    let statement = parse_statement("x = 0; while (true) { x = x + 1 }").unwrap();
+
+    for semantics in [Target::JavaScript, Target::Python]
+        .map(Semantics::Transpiled)
+        .into_iter()
+        .filter(Semantics::is_available)
+    {
+        assert_eq!(
+            semantics.run(&statement, &Environment::new()).unwrap(),
+            Outcome::Exhausted(Limit::WallClock)
+        );
+    }
+}
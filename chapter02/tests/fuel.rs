@@ -0,0 +1,133 @@
+use chapter02::{
+    bytecode::{chunk::Chunk, virtual_machine::VirtualMachine},
+    denotation::Denotation,
+    environment::Environment,
+    evaluator::Evaluator,
+    expression::Expression,
+    fuel::Fuel,
+    limit::Limit,
+    machine::Machine,
+    parser::parse_statement,
+    runtime_error::RuntimeError,
+};
+
+const COUNTDOWN: &str =
+    "function f(n) { if (n == 0) { return 0 } else { return f(n - 1) } }; y = f(2)";
+const UNBOUNDED: &str = "function f(n) { return f(n + 1) }; y = f(0)";
+
+fn evaluate(source: &str, fuel: Fuel) -> Result<Environment, RuntimeError> {
+    let mut environment = Environment::new();
+    parse_statement(source)
+        .unwrap()
+        .evaluate_with_fuel(&mut environment, &mut fuel.clone())?;
+    Ok(environment)
+}
+
+fn reduce(source: &str, fuel: Fuel) -> Result<Environment, RuntimeError> {
+    let mut machine =
+        Machine::with_fuel(parse_statement(source).unwrap(), Environment::new(), fuel);
+    machine.run_with_observer(&mut |_: &_| {})?;
+    Ok(machine.environment().clone())
+}
+
+fn denote(source: &str, fuel: Fuel) -> Result<Environment, RuntimeError> {
+    let mut environment = Environment::new();
+    parse_statement(source).unwrap().denote()(&mut environment, &mut fuel.clone())?;
+    Ok(environment)
+}
+
+fn execute(source: &str, fuel: Fuel) -> Result<Environment, RuntimeError> {
+    let mut environment = Environment::new();
+    VirtualMachine::with_fuel(Chunk::compile(&parse_statement(source).unwrap()), fuel)
+        .run(&mut environment)?;
+    Ok(environment)
+}
+
+fn assert_exhausted(result: Result<Environment, RuntimeError>, expected: Limit) -> Environment {
+    match result {
+        Err(RuntimeError::OutOfFuel { limit, environment }) if limit == expected => environment,
+        result => panic!("expected to run out of {} but got {:?}", expected, result),
+    }
+}
+
+#[test]
+fn loops_stop_after_the_maximum_number_of_iterations() {
+    let fuel = Fuel::unlimited().with_max_loop_iterations(5);
+
+    for run in [evaluate, reduce, denote, execute] {
+        let environment = assert_exhausted(
+            run("x = 0; while (true) { x = x + 1 }", fuel),
+            Limit::LoopIterations,
+        );
+        assert_eq!(environment.get("x").ok(), Some(Expression::Number(5)));
+
+        let environment = assert_exhausted(
+            run("x = 0; for (i = 1 to 10) { x = x + i }", fuel),
+            Limit::LoopIterations,
+        );
+        assert_eq!(environment.get("x").ok(), Some(Expression::Number(15)));
+
+        let environment = assert_exhausted(
+            run(
+                "x = 0; while (true) { x = x + 1; if (x > 0) { continue } else { nothing } }",
+                fuel,
+            ),
+            Limit::LoopIterations,
+        );
+        assert_eq!(environment.get("x").ok(), Some(Expression::Number(5)));
+    }
+}
+
+#[test]
+fn only_nested_calls_count_against_the_recursion_depth() {
+    for run in [evaluate, reduce, denote, execute] {
+        let environment = run(COUNTDOWN, Fuel::unlimited().with_max_recursion_depth(3)).unwrap();
+        assert_eq!(environment.get("y").ok(), Some(Expression::Number(0)));
+
+        assert_exhausted(
+            run(COUNTDOWN, Fuel::unlimited().with_max_recursion_depth(2)),
+            Limit::RecursionDepth,
+        );
+
+        let environment = run(
+            "function f(n) { return n }; y = f(1) + f(2) + f(3); let z = 1 in { if (true) { while (y < 10) { y = y + z } } else { nothing } }",
+            Fuel::unlimited().with_max_recursion_depth(1),
+        )
+        .unwrap();
+        assert_eq!(environment.get("y").ok(), Some(Expression::Number(10)));
+    }
+}
+
+#[test]
+fn unbounded_recursion_runs_out_of_fuel() {
+    for run in [evaluate, reduce, denote, execute] {
+        assert_exhausted(
+            run(UNBOUNDED, Fuel::unlimited().with_max_recursion_depth(20)),
+            Limit::RecursionDepth,
+        );
+    }
+}
+
+#[test]
+fn the_default_recursion_depth_stops_before_the_native_stack_overflows() {
+    for run in [evaluate, reduce, denote, execute] {
+        assert_exhausted(run(UNBOUNDED, Fuel::default()), Limit::RecursionDepth);
+
+        let environment = run(
+            "function f(n) { if (n == 0) { return 0 } else { return 1 + f(n - 1) } }; y = f(90)",
+            Fuel::default(),
+        )
+        .unwrap();
+        assert_eq!(environment.get("y").ok(), Some(Expression::Number(90)));
+    }
+}
+
+#[test]
+fn the_virtual_machine_keeps_call_frames_off_the_native_stack() {
+    let environment = execute(
+        "function f(n) { if (n == 0) { return 0 } else { return 1 + f(n - 1) } }; y = f(100000)",
+        Fuel::unlimited(),
+    )
+    .unwrap();
+    assert_eq!(environment.get("y").ok(), Some(Expression::Number(100000)));
+}
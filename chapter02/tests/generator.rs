@@ -0,0 +1,105 @@
+use chapter02::{
+    differential::{outcome::Outcome, semantics::Semantics},
+    environment::Environment,
+    fuel::Fuel,
+    generator::{Generator, operator::Operator},
+    type_checker::TypeChecker,
+    type_context::TypeContext,
+};
+
+const SEEDS: u64 = 300;
+
+#[test]
+fn generated_programs_type_check() {
+    for seed in 0..SEEDS {
+        let program = Generator::new(seed).program();
+
+        if let Err(errors) = program.type_check(&mut TypeContext::new()) {
+            panic!("seed {}: '{}' is ill-typed: {:?}", seed, program, errors);
+        }
+    }
+}
+
+#[test]
+fn generated_programs_terminate() {
+    for seed in 0..SEEDS {
+        let mut generator = Generator::new(seed);
+        let program = generator.program();
+        let outcome = Semantics::BigStep
+            .run_with_input(
+                &program,
+                &Environment::new(),
+                Fuel::default(),
+                generator.input(),
+            )
+            .unwrap();
+
+        assert!(
+            !matches!(outcome, Outcome::Exhausted(_)),
+            "seed {}: '{}' gives {}",
+            seed,
+            program,
+            outcome
+        );
+    }
+}
+
+#[test]
+fn generated_programs_never_run_out_of_scripted_input() {
+    let mut reading = 0;
+
+    for seed in 0..SEEDS {
+        let mut generator = Generator::new(seed);
+        let program = generator.program();
+        let outcome = Semantics::BigStep
+            .run_with_input(
+                &program,
+                &Environment::new(),
+                Fuel::default(),
+                generator.input(),
+            )
+            .unwrap();
+
+        reading += usize::from(program.to_string().contains("input()"));
+        assert!(
+            !matches!(outcome, Outcome::Failed(ref message) if message == "no more input to read"),
+            "seed {}: '{}' reads more than {:?}",
+            seed,
+            program,
+            generator.input()
+        );
+    }
+
+    assert!(reading > 0, "no generated program reads input");
+}
+
+#[test]
+fn default_weights_cover_every_operator() {
+    let generator = Generator::new(0);
+
+    for operator in Operator::all() {
+        assert!(
+            generator.weight(operator) > 0,
+            "{} is never generated",
+            operator
+        );
+    }
+
+    let programs = (0..SEEDS)
+        .map(|seed| Generator::new(seed).program().to_string())
+        .collect::<Vec<_>>();
+
+    for (name, fragment) in [
+        ("concatenate", " ++ "),
+        ("length", "length("),
+        ("index", "]["),
+        ("append", "append("),
+        ("input", "input()"),
+    ] {
+        assert!(
+            programs.iter().any(|program| program.contains(fragment)),
+            "no generated program uses {}",
+            name
+        );
+    }
+}
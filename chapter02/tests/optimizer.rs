@@ -0,0 +1,71 @@
+use chapter02::{
+    console::ScriptedConsole, environment::Environment, evaluator::Evaluator,
+    expression::Expression, fuel::Fuel, generator::Generator, optimizer::Optimizer,
+    parser::parse_statement, statement::Statement,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+type Run = (Result<BTreeMap<String, Expression>, String>, Vec<String>);
+
+fn evaluate(statement: &Statement, input: &[String]) -> Run {
+    let console = Arc::new(Mutex::new(ScriptedConsole::new(input.iter().cloned())));
+    let mut environment = Environment::new();
+    environment.attach(console.clone());
+
+    let result = statement
+        .evaluate_with_fuel(
+            &mut environment,
+            &mut Fuel::default().with_max_loop_iterations(10_000),
+        )
+        .map(|_| {
+            environment
+                .variables()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect()
+        })
+        .map_err(|error| error.to_string());
+    let output = console.lock().unwrap().output().to_vec();
+
+    (result, output)
+}
+
+fn assert_equivalent(statement: &Statement, input: &[String]) {
+    let (optimized, _) = statement.optimize();
+
+    assert_eq!(
+        evaluate(&optimized, input),
+        evaluate(statement, input),
+        "'{}' optimizes to '{}'",
+        statement,
+        optimized
+    );
+}
+
+#[test]
+fn optimized_programs_evaluate_like_the_original() {
+    for source in [
+        "x = 1 + 2 * 3; y = x * 1 + 0; print y",
+        "b = !!(x == x); if (b && true) { print 1 } else { print 2 }",
+        "x = 5; while (false) { x = x + 1 }; nothing; print x",
+        "x = 1 / 0 + 0",
+        "y = false && 1 / 0 == 1; z = 9223372036854775807 + 1",
+        "s = \"a\" ++ \"\"; print s; print length(append([1], 2 * 1))",
+        "function f(n) { return n * 1 + 0 }; x = f(3); for (i = 1 to 0 + 3) { x = x + i }",
+        "let x = 1 + 1 in { print x * 1 }; print input()",
+    ] {
+        let statement = parse_statement(source).unwrap();
+        assert_equivalent(&statement, &["line".into()]);
+    }
+}
+
+#[test]
+fn optimized_generated_programs_evaluate_like_the_original() {
+    for seed in 0..300 {
+        let mut generator = Generator::new(seed);
+        let program = generator.program();
+        assert_equivalent(&program, generator.input());
+    }
+}
@@ -0,0 +1,164 @@
+use chapter02::{
+    expression::Expression,
+    parser::ParseError,
+    repl::{command::Command, mode::Mode, session::Session},
+    step::Step,
+    target::Target,
+};
+use std::{
+    io::Write,
+    process::{Command as Process, Stdio},
+};
+
+fn command(input: &str) -> Command {
+    input.parse().unwrap()
+}
+
+fn execute(session: &mut Session, source: &str) {
+    let Command::Execute(statement) = command(source) else {
+        panic!("'{}' is not a statement", source);
+    };
+    session.execute(statement, &mut |_: &Step| {}).unwrap();
+}
+
+fn repl(script: &str) -> String {
+    let mut child = Process::new(env!("CARGO_BIN_EXE_chapter02"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(script.as_bytes())
+        .unwrap();
+
+    String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap()
+}
+
+#[test]
+fn commands_are_parsed_from_colon_prefixed_input() {
+    assert_eq!(command(":small"), Command::Switch(Mode::SmallStep));
+    assert_eq!(command("  :big  "), Command::Switch(Mode::BigStep));
+    assert_eq!(command(":env"), Command::Environment);
+    assert_eq!(command(":transpile"), Command::Transpile(Target::Ruby));
+    assert_eq!(
+        command(":transpile python"),
+        Command::Transpile(Target::Python)
+    );
+    assert_eq!(command(":reset"), Command::Reset);
+    assert_eq!(command(":help"), Command::Help);
+    assert_eq!(command(":quit"), Command::Quit);
+    assert!(matches!(command("x = 1"), Command::Execute(_)));
+
+    for input in [":bogus", ":small now", ":transpile cobol"] {
+        assert!(input.parse::<Command>().is_err(), "{}", input);
+    }
+}
+
+#[test]
+fn unfinished_statements_are_reported_as_incomplete() {
+    let error = "if (x > 0) {".parse::<Command>().unwrap_err();
+    assert!(error.downcast_ref::<ParseError>().unwrap().is_incomplete());
+
+    let error = "x = * 1".parse::<Command>().unwrap_err();
+    assert!(!error.downcast_ref::<ParseError>().unwrap().is_incomplete());
+}
+
+#[test]
+fn sessions_keep_variables_and_functions_between_inputs() {
+    for mode in [Mode::BigStep, Mode::SmallStep] {
+        let mut session = Session::new();
+        session.switch(mode);
+        assert_eq!(session.mode(), mode);
+
+        execute(&mut session, "x = 2");
+        execute(&mut session, "function double(n) { return n * 2 }");
+        execute(&mut session, "y = double(x) + 1");
+
+        assert_eq!(
+            session.environment().get("y").ok(),
+            Some(Expression::Number(5))
+        );
+    }
+}
+
+#[test]
+fn failed_inputs_leave_the_environment_unchanged() {
+    let mut session = Session::new();
+    execute(&mut session, "x = 1");
+
+    let Command::Execute(statement) = command("x = 2; y = 1 / 0") else {
+        unreachable!()
+    };
+    assert!(session.execute(statement, &mut |_: &Step| {}).is_err());
+    assert_eq!(
+        session.environment().get("x").ok(),
+        Some(Expression::Number(1))
+    );
+    assert!(session.environment().get("y").is_err());
+}
+
+#[test]
+fn small_step_sessions_report_every_step() {
+    let mut session = Session::new();
+    session.switch(Mode::SmallStep);
+    execute(&mut session, "x = 1");
+
+    let Command::Execute(statement) = command("y = x + 1") else {
+        unreachable!()
+    };
+    let mut steps = Vec::new();
+    session
+        .execute(statement, &mut |step: &Step| {
+            steps.push(step.after().to_string())
+        })
+        .unwrap();
+
+    assert_eq!(steps, ["y = 1 + 1", "y = 2", "nothing"]);
+}
+
+#[test]
+fn the_last_input_is_transpiled_with_the_environment_it_ran_in() {
+    let mut session = Session::new();
+    assert!(session.transpile(Target::JavaScript).is_none());
+
+    execute(&mut session, "x = 40");
+    execute(&mut session, "y = x + 2");
+
+    let program = session.transpile(Target::JavaScript).unwrap().unwrap();
+    assert!(program.contains("40"), "{}", program);
+    assert!(!program.contains("42"), "{}", program);
+}
+
+#[test]
+fn resetting_forgets_variables_functions_and_the_last_input() {
+    let mut session = Session::new();
+    execute(&mut session, "function f(n) { return n }; x = f(1)");
+
+    session.reset();
+
+    assert!(session.environment().get("x").is_err());
+    assert!(session.transpile(Target::Ruby).is_none());
+
+    let Command::Execute(statement) = command("y = f(1)") else {
+        unreachable!()
+    };
+    assert!(session.execute(statement, &mut |_: &Step| {}).is_err());
+}
+
+#[test]
+fn the_repl_runs_a_scripted_session() {
+    let output = repl(
+        "x = 1\nif (x > 0) {\ny = 2\n} else { nothing }\n:env\nz = 1 / 0\n:bogus\n:reset\n:env\n:transpile\n:quit\nq = 1\n",
+    );
+
+    assert!(output.starts_with("SIMPLE (big-step), type :help for commands\n"));
+    assert!(output.contains("{ y = 2, x = 1 }") || output.contains("{ x = 1, y = 2 }"));
+    assert!(output.contains("error: division by zero in '1 / 0'"));
+    assert!(output.contains("error: unknown command ':bogus'"));
+    assert!(output.contains("environment cleared"));
+    assert!(output.contains("nothing to transpile yet"));
+    assert!(!output.contains("q = 1"));
+}
@@ -0,0 +1,70 @@
+use chapter02::{
+    differential::semantics::Semantics, environment::Environment, expression::Expression,
+    parser::parse_statement, target::Target,
+};
+
+const RUBY: Semantics = Semantics::Transpiled(Target::Ruby);
+
+fn assert_agrees(source: &str, environment: &Environment) {
+    let statement = parse_statement(source).unwrap();
+    let expected = Semantics::BigStep.run(&statement, environment).unwrap();
+    let actual = RUBY.run(&statement, environment).unwrap();
+
+    assert!(
+        actual.agrees_with(&expected),
+        "'{}' evaluates to {} but ruby produces {}",
+        source,
+        expected,
+        actual
+    );
+}
+
+#[test]
+#[ignore = "needs ruby on the PATH, run with --ignored"]
+fn transpiled_ruby_matches_the_evaluator() {
+    assert!(RUBY.is_available(), "ruby is not installed");
+
+    let mut environment = Environment::new();
+    environment.insert("y", Expression::Number(3));
+
+    for source in [
+        "x = !(1 < 2); nothing",
+        "x = 1; if (x < 2) { y = y + x } else { nothing }",
+        "x = 0; while (x < 5) { x = x + 1 }",
+        "for (i = 1 to 4) { y = y * i }",
+        "function f(a) { return a + 1 }; x = f(y)",
+        "s = \"a#b\" ++ \"c\"; l = append([1, 2], 3); n = length(l) + l[0]",
+    ] {
+        assert_agrees(source, &environment);
+    }
+}
+
+#[test]
+#[ignore = "needs ruby on the PATH, run with --ignored"]
+fn let_scopes_are_restored_when_jumping_out() {
+    assert!(RUBY.is_available(), "ruby is not installed");
+
+    for source in [
+        "x = 0; while (x < 3) { x = x + 1; let y = 1 in { break } }",
+        "x = 0; while (x < 3) { x = x + 1; let y = 1 in { continue } }",
+        "y = 5; let y = 1 in { let z = 2 in { return y } }",
+        "x = 0; for (i = 1 to 3) { let x = i in { if (x == 2) { break } else { nothing } } }",
+    ] {
+        assert_agrees(source, &Environment::new());
+    }
+}
+
+#[test]
+#[ignore = "needs ruby on the PATH, run with --ignored"]
+fn jumps_do_not_escape_function_bodies() {
+    assert!(RUBY.is_available(), "ruby is not installed");
+
+    for source in [
+        "function f(n) { break }; x = 0; while (x < 3) { x = x + 1; y = f(x) }",
+        "function f(n) { continue }; for (i = 1 to 3) { y = f(i) }",
+        "function f(n) { while (true) { break }; return n }; x = f(1)",
+        "break",
+    ] {
+        assert_agrees(source, &Environment::new());
+    }
+}
@@ -0,0 +1,145 @@
+use chapter02::{
+    differential::{outcome::Outcome, semantics::Semantics},
+    environment::Environment,
+    expression::Expression,
+    parser::parse_statement,
+    target::Target,
+    transpile_error::TranspileError,
+    type_checker::TypeChecker,
+    type_context::TypeContext,
+    type_error::TypeError,
+    value_type::ValueType,
+};
+
+fn semantics() -> Vec<Semantics> {
+    let transpiled = [Target::Ruby, Target::JavaScript, Target::Python, Target::C]
+        .map(Semantics::Transpiled)
+        .into_iter()
+        .filter(Semantics::is_available);
+
+    [
+        Semantics::SmallStep,
+        Semantics::Denotational,
+        Semantics::Bytecode,
+        Semantics::Optimized,
+    ]
+    .into_iter()
+    .chain(transpiled)
+    .collect()
+}
+
+fn run(source: &str) -> Outcome {
+    let statement = parse_statement(source).unwrap();
+    let expected = Semantics::BigStep
+        .run(&statement, &Environment::new())
+        .unwrap();
+
+    for semantics in semantics() {
+        let actual = match semantics.run(&statement, &Environment::new()) {
+            Err(error) if error.is::<TranspileError>() => continue,
+            result => result.unwrap(),
+        };
+
+        assert!(
+            actual.agrees_with(&expected),
+            "'{}': big-step gives {} but {} gives {}",
+            source,
+            expected,
+            semantics,
+            actual
+        );
+    }
+
+    expected
+}
+
+fn assert_variables(source: &str, expected: &[(&str, Option<i64>)]) {
+    let Outcome::Finished { environment, .. } = run(source) else {
+        panic!("'{}' did not finish", source);
+    };
+
+    for (name, value) in expected {
+        assert_eq!(
+            environment.get(name).ok(),
+            value.map(Expression::Number),
+            "'{}': {}",
+            source,
+            name
+        );
+    }
+}
+
+#[test]
+fn let_bindings_shadow_and_then_restore_outer_variables() {
+    assert_variables(
+        "x = 1; let x = 2 in { y = x; x = 3; z = x }",
+        &[("x", Some(1)), ("y", Some(2)), ("z", Some(3))],
+    );
+    assert_variables(
+        "x = 1; let x = x + 1 in { let x = x * 10 in { y = x }; z = x }",
+        &[("x", Some(1)), ("y", Some(20)), ("z", Some(2))],
+    );
+}
+
+#[test]
+fn let_bindings_do_not_outlive_their_body() {
+    assert_variables("let x = 2 in { y = x }", &[("x", None), ("y", Some(2))]);
+
+    match run("let x = 1 in { nothing }; y = x") {
+        Outcome::Failed(message) => assert!(message.contains("is not defined"), "{}", message),
+        outcome => panic!("expected 'x' to be undefined but got {}", outcome),
+    }
+}
+
+#[test]
+fn assignments_to_other_variables_escape_the_body() {
+    assert_variables(
+        "x = 1; let y = x in { x = y + 1; w = 5 }",
+        &[("x", Some(2)), ("w", Some(5)), ("y", None)],
+    );
+}
+
+#[test]
+fn jumping_out_of_a_let_restores_the_outer_scope() {
+    assert_variables(
+        "x = 1; while (true) { let x = 5 in { break } }; y = x",
+        &[("x", Some(1)), ("y", Some(1))],
+    );
+    assert_variables(
+        "x = 1; for (i = 1 to 3) { let x = i in { if (x == 2) { continue } else { y = x } } }",
+        &[("x", Some(1)), ("y", Some(3))],
+    );
+    assert_variables(
+        "function f(n) { let n = n + 1 in { return n } }; n = 10; y = f(1)",
+        &[("n", Some(10)), ("y", Some(2))],
+    );
+}
+
+#[test]
+fn function_bodies_do_not_see_let_bindings_at_the_call_site() {
+    match run("function f(n) { return x }; let x = 1 in { y = f(0) }") {
+        Outcome::Failed(message) => assert!(message.contains("is not defined"), "{}", message),
+        outcome => panic!(
+            "expected 'x' to be undefined inside 'f' but got {}",
+            outcome
+        ),
+    }
+}
+
+#[test]
+fn shadowing_bindings_may_have_a_different_type() {
+    let mut context = TypeContext::new();
+    parse_statement("x = 1; let x = true in { b = !x }; y = x + 1")
+        .unwrap()
+        .type_check(&mut context)
+        .unwrap();
+    assert_eq!(context.get("x"), Some(ValueType::Number));
+    assert_eq!(context.get("b"), Some(ValueType::Boolean));
+
+    assert_eq!(
+        parse_statement("let x = 1 in { nothing }; y = x")
+            .unwrap()
+            .type_check(&mut TypeContext::new()),
+        Err(vec![TypeError::UnboundVariable("x".into())])
+    );
+}
@@ -0,0 +1,68 @@
+use chapter02::{
+    differential::{outcome::Outcome, semantics::Semantics},
+    environment::Environment,
+    expression::Expression,
+    parser::parse_statement,
+    target::Target,
+    transpile_error::TranspileError,
+};
+
+fn assert_short_circuits(source: &str, x: i64, expected: bool) {
+    let statement = parse_statement(source).unwrap();
+    let mut environment = Environment::new();
+    environment.insert("x", Expression::Number(x));
+
+    let evaluated = Semantics::BigStep.run(&statement, &environment).unwrap();
+    let Outcome::Finished {
+        environment: ref result,
+        ..
+    } = evaluated
+    else {
+        panic!("'{}' with x = {} gives {}", source, x, evaluated);
+    };
+    assert_eq!(result.get("y").ok(), Some(Expression::Boolean(expected)));
+
+    let semantics =
+        [Target::Ruby, Target::JavaScript, Target::Python, Target::C].map(Semantics::Transpiled);
+
+    for semantics in [Semantics::SmallStep]
+        .into_iter()
+        .chain(semantics.into_iter().filter(Semantics::is_available))
+    {
+        let outcome = match semantics.run(&statement, &environment) {
+            Err(error) if error.is::<TranspileError>() => continue,
+            result => result.unwrap(),
+        };
+
+        assert!(
+            outcome.agrees_with(&evaluated),
+            "'{}' with x = {}: big-step gives {} but {} gives {}",
+            source,
+            x,
+            evaluated,
+            semantics,
+            outcome
+        );
+    }
+}
+
+#[test]
+fn and_skips_the_right_operand_when_the_left_is_false() {
+    assert_short_circuits("y = x != 0 && 10 / x > 1", 0, false);
+    assert_short_circuits("y = x != 0 && 10 / x > 1", 5, true);
+    assert_short_circuits("y = false && [1][x] == 1", 3, false);
+}
+
+#[test]
+fn or_skips_the_right_operand_when_the_left_is_true() {
+    assert_short_circuits("y = x == 0 || 10 / x > 1", 0, true);
+    assert_short_circuits("y = x == 0 || 10 / x > 1", 20, false);
+    assert_short_circuits("y = true || [1][x] == 1", 3, true);
+}
+
+#[test]
+fn nested_operators_short_circuit_from_the_left() {
+    assert_short_circuits("y = x == 0 || x != 0 && 10 / x > 1", 0, true);
+    assert_short_circuits("y = (x != 0 && 10 / x > 1) || x == 0", 0, true);
+    assert_short_circuits("y = !(x != 0 && 10 / x > 1)", 0, true);
+}
@@ -0,0 +1,73 @@
+use chapter02::{
+    environment::Environment, evaluator::Evaluator, expression::Expression, machine::Machine,
+    parser::parse_statement, type_checker::TypeChecker, type_context::TypeContext,
+};
+
+fn evaluate(source: &str) -> Environment {
+    let mut environment = Environment::new();
+    parse_statement(source)
+        .unwrap()
+        .evaluate(&mut environment)
+        .unwrap();
+    environment
+}
+
+#[test]
+fn long_programs_are_parsed_run_and_dropped_without_recursion() {
+    let source = vec!["x = x + 1"; 100_000].join("; ");
+    let statement = parse_statement(&format!("x = 0; {}", source)).unwrap();
+
+    statement.type_check(&mut TypeContext::default()).unwrap();
+
+    let mut environment = Environment::new();
+    statement.evaluate(&mut environment).unwrap();
+    assert_eq!(environment.get("x").ok(), Some(Expression::Number(100_000)));
+
+    let mut machine = Machine::new(statement, Environment::new());
+    machine.run_with_observer(&mut |_: &_| {}).unwrap();
+    assert_eq!(
+        machine.environment().get("x").ok(),
+        Some(Expression::Number(100_000))
+    );
+}
+
+#[test]
+fn long_expressions_are_parsed_and_evaluated_without_recursion() {
+    let environment = evaluate(&format!("x = {}", vec!["1"; 1_200].join(" + ")));
+    assert_eq!(environment.get("x").ok(), Some(Expression::Number(1_200)));
+
+    let environment = evaluate(&format!("x = {}1", "- ".repeat(1_200)));
+    assert_eq!(environment.get("x").ok(), Some(Expression::Number(1)));
+
+    let environment = evaluate(&format!("x = {}true", "!".repeat(1_201)));
+    assert_eq!(environment.get("x").ok(), Some(Expression::Boolean(false)));
+}
+
+#[test]
+fn deeply_nested_brackets_are_a_parse_error() {
+    let environment = evaluate(&format!("x = {}1{}", "(".repeat(32), ")".repeat(32)));
+    assert_eq!(environment.get("x").ok(), Some(Expression::Number(1)));
+
+    let environment = evaluate(&format!(
+        "{}x = 1{}",
+        "if (true) { ".repeat(31),
+        " } else { nothing }".repeat(31)
+    ));
+    assert_eq!(environment.get("x").ok(), Some(Expression::Number(1)));
+
+    let error = parse_statement(&format!(
+        "{}x = 1{}",
+        "{ ".repeat(1_500),
+        " }".repeat(1_500)
+    ))
+    .unwrap_err();
+    assert_eq!((error.line(), error.column()), (1, 65));
+    assert_eq!(error.message(), "more than 32 nested brackets");
+    assert!(!error.is_incomplete());
+
+    let environment = evaluate(&format!("x = \"{}\"", "(".repeat(1_500)));
+    assert_eq!(
+        environment.get("x").ok(),
+        Some(Expression::String("(".repeat(1_500)))
+    );
+}
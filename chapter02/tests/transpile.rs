@@ -0,0 +1,104 @@
+use chapter02::{
+    differential::semantics::Semantics, environment::Environment, expression::Expression,
+    machine::Machine, parser::parse_statement, statement::Statement, target::Target,
+    transpile_error::TranspileError, type_error::TypeError,
+};
+
+fn transpile(target: Target, statement: &Statement) -> Result<String, TranspileError> {
+    target.backend().program(statement, &Environment::new())
+}
+
+#[test]
+fn ill_typed_programs_are_not_translated_to_c() {
+    let statement = parse_statement("x = 1; if (x > 0) { x = false } else { nothing }").unwrap();
+
+    match transpile(Target::C, &statement) {
+        Err(TranspileError::IllTyped { target, errors }) => {
+            assert_eq!(target, Target::C);
+            assert!(matches!(errors[..], [TypeError::Retyped { .. }]));
+        }
+        result => panic!("expected a type error but got {:?}", result),
+    }
+}
+
+#[test]
+fn functions_that_are_never_called_cannot_be_typed_for_c() {
+    let statement = parse_statement("function f(n) { return n }; x = 1").unwrap();
+
+    assert_eq!(
+        transpile(Target::C, &statement),
+        Err(TranspileError::Unsigned {
+            target: Target::C,
+            name: "f".into(),
+        })
+    );
+    assert!(
+        transpile(
+            Target::C,
+            &parse_statement("function f(n) { return n }; x = f(1)").unwrap()
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn reduction_terms_are_rejected_by_every_target() {
+    let iteration = Statement::Iteration(
+        Box::new(Statement::Assign("x".into(), Expression::Number(1))),
+        Box::new(Statement::Nothing),
+    );
+
+    let mut machine = Machine::new(
+        parse_statement("function f(n) { return n }; y = f(1) + 1").unwrap(),
+        Environment::new(),
+    );
+    let invocation = machine
+        .steps()
+        .map(|step| step.unwrap().after().clone())
+        .find(|statement| statement.to_string().contains("call f"))
+        .unwrap();
+
+    for target in [Target::JavaScript, Target::Python, Target::C] {
+        for statement in [&iteration, &invocation] {
+            match transpile(target, statement) {
+                Err(TranspileError::Intermediate {
+                    target: rejected, ..
+                }) => assert_eq!(rejected, target),
+                result => panic!(
+                    "expected {} to reject '{}' but got {:?}",
+                    target, statement, result
+                ),
+            }
+        }
+    }
+
+    assert!(transpile(Target::Ruby, &iteration).is_ok());
+}
+
+#[test]
+fn c_evaluates_operands_and_arguments_from_left_to_right() {
+    let c = Semantics::Transpiled(Target::C);
+    if !c.is_available() {
+        return;
+    }
+
+    for source in [
+        "x = -(-9223372036854775807 - 1) + 1 / 0",
+        "b = 1 / 0 == 9223372036854775807 * 2",
+        "function f(a, b) { return a }; x = f(1 / 0, 9223372036854775807 + 1)",
+    ] {
+        let statement = parse_statement(source).unwrap();
+        let expected = Semantics::BigStep
+            .run(&statement, &Environment::new())
+            .unwrap();
+        let actual = c.run(&statement, &Environment::new()).unwrap();
+
+        assert!(
+            actual.agrees_with(&expected),
+            "'{}': big-step gives {} but c gives {}",
+            source,
+            expected,
+            actual
+        );
+    }
+}
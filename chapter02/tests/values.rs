@@ -0,0 +1,190 @@
+use chapter02::{
+    differential::{outcome::Outcome, semantics::Semantics},
+    environment::Environment,
+    evaluator::Evaluator,
+    expression::Expression,
+    parser::parse_statement,
+    runtime_error::RuntimeError,
+    target::Target,
+    transpile_error::TranspileError,
+    type_checker::TypeChecker,
+    type_context::TypeContext,
+    type_error::TypeError,
+    value_type::ValueType,
+};
+
+fn semantics() -> Vec<Semantics> {
+    let transpiled = [Target::Ruby, Target::JavaScript, Target::Python, Target::C]
+        .map(Semantics::Transpiled)
+        .into_iter()
+        .filter(Semantics::is_available);
+
+    [
+        Semantics::SmallStep,
+        Semantics::Denotational,
+        Semantics::Bytecode,
+        Semantics::Optimized,
+    ]
+    .into_iter()
+    .chain(transpiled)
+    .collect()
+}
+
+fn assert_agree(source: &str) -> Outcome {
+    let statement = parse_statement(source).unwrap();
+    let expected = Semantics::BigStep
+        .run(&statement, &Environment::new())
+        .unwrap();
+
+    for semantics in semantics() {
+        let actual = match semantics.run(&statement, &Environment::new()) {
+            Err(error) if error.is::<TranspileError>() => continue,
+            result => result.unwrap(),
+        };
+
+        assert!(
+            actual.agrees_with(&expected),
+            "'{}': big-step gives {} but {} gives {}",
+            source,
+            expected,
+            semantics,
+            actual
+        );
+    }
+
+    expected
+}
+
+fn assert_values(source: &str, expected: &[(&str, Expression)]) {
+    let Outcome::Finished { environment, .. } = assert_agree(source) else {
+        panic!("'{}' did not finish", source);
+    };
+
+    for (name, value) in expected {
+        assert_eq!(environment.get(name).ok().as_ref(), Some(value), "{}", name);
+    }
+}
+
+fn type_errors(source: &str) -> Vec<TypeError> {
+    parse_statement(source)
+        .unwrap()
+        .type_check(&mut TypeContext::new())
+        .unwrap_err()
+}
+
+fn string(value: &str) -> Expression {
+    Expression::String(value.into())
+}
+
+fn list(values: &[i64]) -> Expression {
+    Expression::List(values.iter().copied().map(Expression::Number).collect())
+}
+
+#[test]
+fn strings_concatenate_and_have_a_length() {
+    assert_values(
+        "s = \"ab\" ++ \"c\"; t = s ++ \"\"; n = length(s); e = length(\"\"); u = length(\"h\u{e9}llo\")",
+        &[
+            ("s", string("abc")),
+            ("t", string("abc")),
+            ("n", Expression::Number(3)),
+            ("e", Expression::Number(0)),
+            ("u", Expression::Number(5)),
+        ],
+    );
+    assert_values(
+        "q = \"say \\\"hi\\\"\" ++ \" # \\\\\"",
+        &[("q", string("say \"hi\" # \\"))],
+    );
+}
+
+#[test]
+fn lists_are_indexed_appended_and_have_a_length() {
+    assert_values(
+        "xs = append([1, 2], 3); y = xs[0] + xs[2]; n = length(xs); e = length([]); ys = append(xs, y)",
+        &[
+            ("xs", list(&[1, 2, 3])),
+            ("y", Expression::Number(4)),
+            ("n", Expression::Number(3)),
+            ("e", Expression::Number(0)),
+            ("ys", list(&[1, 2, 3, 4])),
+        ],
+    );
+    assert_values(
+        "xs = [1]; i = 0; while (i < 3) { i = i + 1; xs = append(xs, xs[i - 1] * 2) }",
+        &[("xs", list(&[1, 2, 4, 8]))],
+    );
+}
+
+#[test]
+fn indexing_outside_a_list_is_a_runtime_error() {
+    for (source, index, length) in [
+        ("xs = [1, 2]; y = xs[2]", 2, 2),
+        ("xs = [1, 2]; y = xs[-1]", -1, 2),
+        ("y = length([]) + [][0]", 0, 0),
+    ] {
+        match parse_statement(source)
+            .unwrap()
+            .evaluate(&mut Environment::new())
+        {
+            Err(RuntimeError::IndexOutOfBounds {
+                index: actual_index,
+                length: actual_length,
+                ..
+            }) => assert_eq!((actual_index, actual_length), (index, length), "{}", source),
+            result => panic!("'{}' gives {:?}", source, result),
+        }
+
+        assert!(matches!(assert_agree(source), Outcome::Failed(_)));
+    }
+}
+
+#[test]
+fn strings_and_lists_are_type_checked() {
+    assert!(matches!(
+        type_errors("x = \"a\" ++ 1")[..],
+        [TypeError::Mismatch {
+            expected: ValueType::String,
+            actual: ValueType::Number,
+            ..
+        }]
+    ));
+    assert!(matches!(
+        type_errors("x = length(true)")[..],
+        [TypeError::Mismatch {
+            actual: ValueType::Boolean,
+            ..
+        }]
+    ));
+    assert!(matches!(
+        type_errors("x = append([1], false)")[..],
+        [TypeError::Mismatch {
+            expected: ValueType::Number,
+            actual: ValueType::Boolean,
+            ..
+        }]
+    ));
+    assert!(matches!(
+        type_errors("x = [][0]")[..],
+        [TypeError::UnknownElement(_)]
+    ));
+
+    let mut context = TypeContext::new();
+    parse_statement("xs = append([], 1); y = xs[0] + length(\"a\")")
+        .unwrap()
+        .type_check(&mut context)
+        .unwrap();
+    assert_eq!(context.get("xs"), Some(ValueType::list(ValueType::Number)));
+}
+
+#[test]
+fn strings_and_lists_print_back_to_the_same_program() {
+    for source in [
+        "s = \"a\\\"b\" ++ \"\\\\\"",
+        "xs = append([1, -2], 3)[0]",
+        "n = length([[1], []]) + length(\"\")",
+    ] {
+        let statement = parse_statement(source).unwrap();
+        assert_eq!(parse_statement(&statement.to_string()), Ok(statement));
+    }
+}